@@ -0,0 +1,65 @@
+//! This crate provides the derive macro of the [`Fingerprint`](https://docs.rs/chksum-md5/latest/chksum_md5/fingerprint/trait.Fingerprint.html) trait.
+//!
+//! It shouldn't be used directly, since the macro is re-exported by the [`chksum-md5`](https://docs.rs/chksum-md5/) crate with the `derive` feature enabled.
+
+#![forbid(unsafe_code)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Index};
+
+/// Derives the `Fingerprint` trait, feeding fields to the hash in declaration order.
+///
+/// Only structs are supported, and every field has to implement the `Fingerprint` trait.
+#[proc_macro_derive(Hashable)]
+pub fn derive_hashable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(input.span(), "Hashable can be derived only for structs"));
+    };
+
+    let fields: Vec<_> = match &data.fields {
+        Fields::Named(fields) => {
+            fields
+                .named
+                .iter()
+                .map(|field| {
+                    let name = &field.ident;
+                    quote!(&self.#name)
+                })
+                .collect()
+        },
+        Fields::Unnamed(fields) => {
+            (0..fields.unnamed.len())
+                .map(|index| {
+                    let index = Index::from(index);
+                    quote!(&self.#index)
+                })
+                .collect()
+        },
+        Fields::Unit => Vec::new(),
+    };
+
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(::chksum_md5::fingerprint::Fingerprint));
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::chksum_md5::fingerprint::Fingerprint for #name #type_generics #where_clause {
+            fn fingerprint_with(&self, hash: &mut ::chksum_md5::MD5) {
+                let _ = &hash;
+                #(::chksum_md5::fingerprint::Fingerprint::fingerprint_with(#fields, hash);)*
+            }
+        }
+    })
+}
@@ -0,0 +1,44 @@
+/* C interface to the MD5 hash of the chksum-md5 crate, built with the `capi` feature. */
+
+#ifndef CHKSUM_MD5_H
+#define CHKSUM_MD5_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The length of digests in bytes. */
+#define CHKSUM_MD5_DIGEST_LENGTH 16
+
+/* The length of hexadecimal digests, including the terminating NUL. */
+#define CHKSUM_MD5_HEX_LENGTH 33
+
+/* An opaque hash state. */
+typedef struct chksum_md5 chksum_md5;
+
+/* Creates a new hash, which has to be released with chksum_md5_free. */
+chksum_md5 *chksum_md5_new(void);
+
+/* Updates the hash state with `length` bytes of data; the data can be NULL if the length is zero. */
+void chksum_md5_update(chksum_md5 *hash, const uint8_t *data, size_t length);
+
+/* Resets the hash state to its initial state. */
+void chksum_md5_reset(chksum_md5 *hash);
+
+/* Writes the CHKSUM_MD5_DIGEST_LENGTH bytes of the digest, without changing the hash state. */
+void chksum_md5_finalize(const chksum_md5 *hash, uint8_t *digest);
+
+/* Writes the lowercase hexadecimal digest as a NUL-terminated string of CHKSUM_MD5_HEX_LENGTH bytes, without changing the hash state. */
+void chksum_md5_hex(const chksum_md5 *hash, char *hex);
+
+/* Releases the hash; NULL is ignored. */
+void chksum_md5_free(chksum_md5 *hash);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
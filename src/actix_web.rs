@@ -0,0 +1,247 @@
+//! This module is optional and can be enabled using the `actix-web` Cargo feature.
+//!
+//! The [`ContentMd5`] middleware sets the `Content-MD5` header of responses, defined by [RFC 1864](https://www.rfc-editor.org/rfc/rfc1864), and can validate the bodies of requests against the header, like the [`tower`](crate::tower) middleware for `actix-web` applications.
+//!
+//! Bodies are buffered up to a size limit, 256 KiB by default like the payload limit of `actix-web` extractors. Responses with bodies of a known size within the limit get the header; streamed and larger responses are sent as they are.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `actix-web` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["actix-web"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features actix-web
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use actix_web::{web, App, HttpResponse};
+//! use chksum_md5::actix_web::ContentMd5;
+//!
+//! let app = App::new()
+//!     .wrap(ContentMd5::new().validate_requests(true).limit(1024 * 1024))
+//!     .route(
+//!         "/",
+//!         web::post().to(|body: String| async move { HttpResponse::Ok().body(body) }),
+//!     );
+//! ```
+
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+
+use actix_web::body::{self, BodySize, BoxBody, EitherBody, MessageBody};
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::ErrorInternalServerError;
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::http::StatusCode;
+use actix_web::{Error, HttpMessage, HttpResponse};
+use futures_util::{stream, StreamExt};
+
+use crate::{Digest, MD5};
+
+/// The name of the `Content-MD5` header.
+pub const CONTENT_MD5: HeaderName = HeaderName::from_static("content-md5");
+
+/// The default limit of buffered bodies, 256 KiB.
+const LIMIT: usize = 256 * 1024;
+
+/// The handling of requests failing the validation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnFailure {
+    /// Rejects the requests with `400 Bad Request`, or with `413 Payload Too Large` if the body exceeds the limit.
+    #[default]
+    Reject,
+    /// Passes the requests to the service with the [`ValidationFailure`] in their extensions, leaving the decision to the handlers.
+    Continue,
+}
+
+/// The reason of a failed validation of a request, available from the extensions of requests passed with [`OnFailure::Continue`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationFailure {
+    /// The header is not a valid `Content-MD5` value.
+    Malformed,
+    /// The digest of the body doesn't match the header.
+    Mismatch,
+    /// The body exceeds the limit, so it was not validated.
+    TooLarge,
+}
+
+/// A middleware setting the `Content-MD5` header of responses and validating requests, registered with `App::wrap`.
+#[derive(Clone, Copy, Debug)]
+pub struct ContentMd5 {
+    limit: usize,
+    validate_requests: bool,
+    on_failure: OnFailure,
+}
+
+impl ContentMd5 {
+    /// Creates a middleware setting the header of responses, without validating requests.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the limit of the size of bodies buffered to calculate their digests.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets whether bodies of requests with the `Content-MD5` header are validated.
+    ///
+    /// These bodies are buffered before calling the service, which receives them from memory.
+    #[must_use]
+    pub fn validate_requests(mut self, validate_requests: bool) -> Self {
+        self.validate_requests = validate_requests;
+        self
+    }
+
+    /// Sets the handling of requests failing the validation.
+    #[must_use]
+    pub fn on_failure(mut self, on_failure: OnFailure) -> Self {
+        self.on_failure = on_failure;
+        self
+    }
+}
+
+impl Default for ContentMd5 {
+    fn default() -> Self {
+        Self {
+            limit: LIMIT,
+            validate_requests: false,
+            on_failure: OnFailure::default(),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ContentMd5
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Error = Error;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+    type InitError = ();
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Transform = ContentMd5Middleware<S>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        let service = Rc::new(service);
+        let config = *self;
+        ready(Ok(ContentMd5Middleware { service, config }))
+    }
+}
+
+/// The service created by the [`ContentMd5`] middleware.
+#[derive(Debug)]
+pub struct ContentMd5Middleware<S> {
+    service: Rc<S>,
+    config: ContentMd5,
+}
+
+impl<S, B> Service<ServiceRequest> for ContentMd5Middleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+    type Response = ServiceResponse<EitherBody<B>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut request: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let config = self.config;
+        Box::pin(async move {
+            if config.validate_requests && request.headers().contains_key(CONTENT_MD5) {
+                if let Err(failure) = validate(&mut request, config.limit).await? {
+                    if config.on_failure == OnFailure::Reject {
+                        let status = match failure {
+                            ValidationFailure::TooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+                            _ => StatusCode::BAD_REQUEST,
+                        };
+                        let response = HttpResponse::new(status);
+                        return Ok(request.into_response(response).map_into_right_body());
+                    }
+                    request.extensions_mut().insert(failure);
+                }
+            }
+            let response = service.call(request).await?;
+            buffer(response, config.limit).await
+        })
+    }
+}
+
+/// Buffers the body of the request and checks it against the header, restoring the payload for the service.
+async fn validate(request: &mut ServiceRequest, limit: usize) -> Result<Result<(), ValidationFailure>, Error> {
+    let expected = request
+        .headers()
+        .get(CONTENT_MD5)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Digest::from_content_md5(value).ok());
+    let Some(expected) = expected else {
+        return Ok(Err(ValidationFailure::Malformed));
+    };
+    let mut payload = request.take_payload();
+    let mut chunks = Vec::new();
+    let mut length = 0;
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk?;
+        length += chunk.len();
+        chunks.push(chunk);
+        if length > limit {
+            // the remaining payload follows the buffered chunks
+            let chunks = stream::iter(chunks.into_iter().map(Ok));
+            let payload: Payload = Payload::Stream {
+                payload: Box::pin(chunks.chain(payload)),
+            };
+            request.set_payload(payload);
+            return Ok(Err(ValidationFailure::TooLarge));
+        }
+    }
+    let mut hash = MD5::new();
+    chunks.iter().for_each(|chunk| hash.update(chunk));
+    let data = chunks.concat();
+    request.set_payload(Payload::from(data));
+    if !crate::verify::eq(&hash.digest(), &expected) {
+        return Ok(Err(ValidationFailure::Mismatch));
+    }
+    Ok(Ok(()))
+}
+
+/// Buffers the body of the response and sets the header, unless the size of the body is unknown or exceeds the limit.
+async fn buffer<B>(response: ServiceResponse<B>, limit: usize) -> Result<ServiceResponse<EitherBody<B>>, Error>
+where
+    B: MessageBody + 'static,
+{
+    let within_limit = match response.response().body().size() {
+        BodySize::Sized(size) => size > 0 && size <= limit as u64,
+        BodySize::None | BodySize::Stream => false,
+    };
+    if !within_limit {
+        return Ok(response.map_into_left_body());
+    }
+    let (request, response) = response.into_parts();
+    let (mut response, body) = response.into_parts();
+    let data = match body::to_bytes_limited(body, limit).await {
+        Ok(Ok(data)) => data,
+        Ok(Err(error)) => return Err(ErrorInternalServerError(error.into())),
+        // the body is larger than its declared size
+        Err(error) => return Err(ErrorInternalServerError(error)),
+    };
+    let value = HeaderValue::from_str(&crate::hash(&data).to_content_md5()).expect("base64 is a valid header value");
+    response.headers_mut().insert(CONTENT_MD5, value);
+    let response = response.set_body(BoxBody::new(data));
+    Ok(ServiceResponse::new(request, response).map_into_right_body())
+}
@@ -0,0 +1,166 @@
+//! This module is optional and can be enabled using the `zip` or `tar` Cargo features.
+//!
+//! The [`zip_entries`] and [`tar_entries`] functions calculate digests of the content of every file stored in an archive, streaming the data through the hash without extracting it to the disk.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `zip` and `tar` features:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["zip", "tar"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features zip,tar
+//! ```
+//!
+//! # Example
+//!
+//! ## ZIP
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let file = File::open(path)?;
+//! for (name, digest) in md5::archive::zip_entries(file)? {
+//!     println!("{digest}  {name}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## TAR
+//!
+//! Compressed archives can be processed by wrapping the reader with a decoder, e.g. created with the [`Compression::decoder`](crate::Compression::decoder) method when the `gzip` or `zstd` feature is enabled.
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let file = File::open(path)?;
+//! let digests = md5::archive::tar_entries(file)?;
+//! for (path, digest) in digests.entries() {
+//!     println!("{digest}  {}", path.display());
+//! }
+//! println!("{}", digests.digest());
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "zip")]
+use std::io::Seek;
+use std::io::{self, Read};
+#[cfg(feature = "tar")]
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "zip")]
+use zip::ZipArchive;
+
+#[cfg(feature = "tar")]
+use crate::MD5;
+use crate::{chksum_reader, Digest, Result};
+
+#[cfg(feature = "zip")]
+/// Computes digests of the decompressed content of every file stored in the given ZIP archive.
+///
+/// Directory entries are skipped. The entries are returned in the order of the archive's central directory.
+pub fn zip_entries(reader: impl Read + Seek) -> Result<Vec<(String, Digest)>> {
+    let mut archive = ZipArchive::new(reader).map_err(io::Error::from)?;
+    let mut entries = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let file = archive.by_index(index).map_err(io::Error::from)?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_owned();
+        let digest = chksum_reader(file)?;
+        entries.push((name, digest));
+    }
+    Ok(entries)
+}
+
+/// Digests of a TAR archive produced by the [`tar_entries`] function.
+#[cfg(feature = "tar")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TarDigests {
+    entries: Vec<(PathBuf, Digest)>,
+    digest: Digest,
+}
+
+#[cfg(feature = "tar")]
+impl TarDigests {
+    /// Returns the paths and digests of the regular files stored in the archive, in the archive order.
+    #[must_use]
+    pub fn entries(&self) -> &[(PathBuf, Digest)] {
+        &self.entries
+    }
+
+    /// Returns the digest of the whole archive stream.
+    #[must_use]
+    pub const fn digest(&self) -> &Digest {
+        &self.digest
+    }
+
+    /// Consumes the digests, returning the entries.
+    #[must_use]
+    pub fn into_entries(self) -> Vec<(PathBuf, Digest)> {
+        self.entries
+    }
+}
+
+/// Computes digests of every regular file stored in the given TAR archive, together with the digest of the whole archive stream.
+///
+/// Directories, links and other special entries are skipped.
+#[cfg(feature = "tar")]
+pub fn tar_entries(reader: impl Read) -> Result<TarDigests> {
+    let reader = HashingReader {
+        inner: reader,
+        hash: MD5::new(),
+    };
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path().map(|path| Path::to_path_buf(&path))?;
+        let digest = chksum_reader(entry)?;
+        entries.push((path, digest));
+    }
+    // consume the end-of-archive padding which is not read by the archive itself
+    let mut reader = archive.into_inner();
+    io::copy(&mut reader, &mut io::sink())?;
+    let digest = reader.hash.digest();
+    Ok(TarDigests { entries, digest })
+}
+
+#[cfg(feature = "tar")]
+struct HashingReader<R> {
+    inner: R,
+    hash: MD5,
+}
+
+#[cfg(feature = "tar")]
+impl<R> Read for HashingReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let length = self.inner.read(buffer)?;
+        self.hash.update(&buffer[..length]);
+        Ok(length)
+    }
+}
@@ -0,0 +1,143 @@
+//! `Content-MD5` values of block blobs uploaded to Azure Blob Storage.
+//!
+//! Blobs uploaded in blocks are checked in two places: every `Put Block` request carries the `Content-MD5` header of the block, and the `Put Block List` request sets the `x-ms-blob-content-md5` property of the blob, the `Content-MD5` value of the whole contents. Both are base64 encoded digests, see the [`Digest::to_content_md5`] method.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use chksum_md5::Result;
+//! use chksum_md5::azure;
+//!
+//! # fn wrapper() -> Result<()> {
+//! let values = azure::content_md5s(&b"example data"[..], 8)?;
+//! assert_eq!(
+//!     values.blocks(),
+//!     ["tEAZEqJT+mmG/S0gQzrXeg==", "jXd/OF09/siBXSD3SWAm3A=="]
+//! );
+//! assert_eq!(values.blob(), "XHHbsodjDWXKk3ZMNNmqDQ==");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+use crate::error::{Context, Operation};
+use crate::{block, Digest, Error, Result, MD5};
+
+/// The `Content-MD5` values of the blocks and of the whole blob, calculated by the [`content_md5s`] function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlobContentMd5 {
+    blocks: Vec<String>,
+    blob: String,
+}
+
+impl BlobContentMd5 {
+    /// Returns the values of the `Content-MD5` header of the `Put Block` requests, in order.
+    #[must_use]
+    pub fn blocks(&self) -> &[String] {
+        &self.blocks
+    }
+
+    /// Returns the value of the `x-ms-blob-content-md5` header of the `Put Block List` request.
+    #[must_use]
+    pub fn blob(&self) -> &str {
+        &self.blob
+    }
+}
+
+/// Calculates the `Content-MD5` values of the data read from the given reader until EOF, staged in blocks of the given size.
+///
+/// The last block is shorter than the block size when the length of the data is not its multiple, and empty data has no blocks.
+///
+/// # Panics
+///
+/// Panics if the block size is zero.
+///
+/// # Errors
+///
+/// Returns an error if reading fails.
+pub fn content_md5s(reader: impl Read, block_size: usize) -> Result<BlobContentMd5> {
+    let digests = block::digests(reader, block_size)?;
+    let blocks = digests.blocks().iter().map(Digest::to_content_md5).collect();
+    let blob = digests.digest().to_content_md5();
+    Ok(BlobContentMd5 { blocks, blob })
+}
+
+/// Checks whether the given file matches the values reported by the service.
+///
+/// The blob-level value is the `Content-MD5` property of the blob. The blocks are the sizes reported by `Get Block List` with the `Content-MD5` values sent with the blocks, in the committed order; they can be empty to check the blob-level value only. Digests are compared in constant time.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, or [`Error::InvalidContentMd5`] if any value is malformed.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5::azure;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let blocks = [
+///     (8, "tEAZEqJT+mmG/S0gQzrXeg=="),
+///     (4, "jXd/OF09/siBXSD3SWAm3A=="),
+/// ];
+/// assert!(azure::verify(path, "XHHbsodjDWXKk3ZMNNmqDQ==", &blocks)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify(path: impl AsRef<Path>, blob_content_md5: &str, blocks: &[(u64, &str)]) -> Result<bool> {
+    let path = path.as_ref();
+    let expected = Digest::from_content_md5(blob_content_md5)?;
+    let blocks = blocks
+        .iter()
+        .map(|&(size, content_md5)| Ok((size, Digest::from_content_md5(content_md5)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let metadata = fs::metadata(path).context(path, Operation::Metadata)?;
+    if metadata.is_dir() {
+        let path = path.to_path_buf();
+        return Err(Error::IsADirectory { path });
+    }
+    let total = blocks
+        .iter()
+        .try_fold(0u64, |total, &(size, _)| total.checked_add(size));
+    if !blocks.is_empty() && total != Some(metadata.len()) {
+        return Ok(false);
+    }
+
+    let file = File::open(path).context(path, Operation::Open)?;
+    let mut reader = BufReader::new(file);
+    let mut hash = MD5::new();
+    let mut matches = true;
+    for (size, expected) in blocks {
+        let mut block = MD5::new();
+        let mut remaining = size;
+        while remaining > 0 {
+            let buffer = reader.fill_buf().context(path, Operation::Read)?;
+            if buffer.is_empty() {
+                // the file was truncated while reading
+                return Ok(false);
+            }
+            let length = buffer.len().min(usize::try_from(remaining).unwrap_or(usize::MAX));
+            block.update(&buffer[..length]);
+            hash.update(&buffer[..length]);
+            reader.consume(length);
+            remaining -= length as u64;
+        }
+        matches &= crate::verify::eq(&block.digest(), &expected);
+    }
+    loop {
+        let buffer = reader.fill_buf().context(path, Operation::Read)?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(length);
+    }
+    Ok(matches & crate::verify::eq(&hash.digest(), &expected))
+}
@@ -0,0 +1,56 @@
+//! The standard base64 encoding with padding, as defined by RFC 4648.
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes the data with padding.
+pub(crate) fn encode(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let block = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (block >> (18 - 6 * index)) & 0x3F;
+                encoded.push(char::from(ALPHABET[sextet as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Decodes padded data, returning `None` if it is not in the canonical encoding.
+///
+/// Non-zero bits in the last character before the padding are rejected, so every input has exactly one encoding.
+pub(crate) fn decode(encoded: &str) -> Option<Vec<u8>> {
+    let encoded = encoded.as_bytes();
+    if !encoded.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut decoded = Vec::with_capacity(encoded.len() / 4 * 3);
+    let blocks = encoded.len() / 4;
+    for (index, chunk) in encoded.chunks(4).enumerate() {
+        let padding = chunk.iter().rev().take_while(|&&byte| byte == b'=').count();
+        if padding > 2 || (padding > 0 && index + 1 != blocks) {
+            return None;
+        }
+        let mut block = 0u32;
+        for &byte in &chunk[..4 - padding] {
+            let sextet = ALPHABET.iter().position(|&symbol| symbol == byte)?;
+            block = block << 6 | sextet as u32;
+        }
+        block <<= 6 * padding;
+        let [_, bytes @ ..] = block.to_be_bytes();
+        let length = 3 - padding;
+        if bytes[length..].iter().any(|&byte| byte != 0) {
+            return None;
+        }
+        decoded.extend_from_slice(&bytes[..length]);
+    }
+    Some(decoded)
+}
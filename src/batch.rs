@@ -0,0 +1,155 @@
+//! Interleaved hashing of multiple inputs, which processes the blocks of several inputs at once.
+//!
+//! Every operation of the compression function is applied to an array of lanes, one input in each, so the compiler can implement the rounds with SIMD instructions while a single stream has to be hashed step by step.
+
+#[cfg(all(feature = "unsafe-simd", target_arch = "x86_64"))]
+mod avx2;
+
+use crate::compress::{f, g, h, i, BLOCK_LENGTH, CONSTANTS, SHIFTS, STATE};
+use crate::hash::DIGEST_LENGTH_BYTES;
+use crate::Digest;
+
+/// The number of inputs hashed at once, which fills two 256-bit vectors of 32-bit words.
+const LANES: usize = 16;
+
+/// A word of every lane.
+type Words = [u32; LANES];
+
+/// Computes the digests of the inputs.
+pub(crate) fn hash(inputs: &[&[u8]]) -> Vec<Digest> {
+    let mut digests = vec![Digest::new([0; DIGEST_LENGTH_BYTES]); inputs.len()];
+    // inputs of similar lengths share lanes, so few lanes idle while the longest input is hashed
+    let mut order: Vec<usize> = (0..inputs.len()).collect();
+    order.sort_by_key(|&index| blocks(inputs[index].len()));
+    for group in order.chunks(LANES) {
+        let mut lanes = [&[][..]; LANES];
+        for (lane, &index) in lanes.iter_mut().zip(group) {
+            *lane = inputs[index];
+        }
+        for (&index, digest) in group.iter().zip(hash_lanes(&lanes)) {
+            digests[index] = digest;
+        }
+    }
+    digests
+}
+
+/// Computes the digests of the inputs of all lanes, including empty ones.
+fn hash_lanes(inputs: &[&[u8]; LANES]) -> [Digest; LANES] {
+    let counts = inputs.map(|input| blocks(input.len()));
+    #[cfg(all(feature = "unsafe-simd", target_arch = "x86_64"))]
+    let compress: fn(&[Words; 4], &[Words; 16]) -> [Words; 4] =
+        if avx2::available() { avx2::compress } else { compress };
+    let mut state = STATE.map(|word| [word; LANES]);
+    let mut words = [[0; LANES]; 16];
+    for block in 0..counts.iter().copied().max().unwrap_or_default() {
+        for (lane, input) in inputs.iter().enumerate() {
+            let start = block * BLOCK_LENGTH;
+            let data = match input.get(start..start + BLOCK_LENGTH) {
+                Some(data) => data.try_into().expect("block has 64 bytes"),
+                None => padded_block(input, block),
+            };
+            for (word, bytes) in words.iter_mut().zip(data.chunks_exact(4)) {
+                word[lane] = u32::from_le_bytes(bytes.try_into().expect("chunk has 4 bytes"));
+            }
+        }
+        let next = compress(&state, &words);
+        // lanes of finished inputs keep their final state
+        for (lane, &count) in counts.iter().enumerate() {
+            if block < count {
+                for (word, next) in state.iter_mut().zip(&next) {
+                    word[lane] = next[lane];
+                }
+            }
+        }
+    }
+    let mut digests = [Digest::new([0; DIGEST_LENGTH_BYTES]); LANES];
+    for (lane, digest) in digests.iter_mut().enumerate() {
+        let mut bytes = [0; DIGEST_LENGTH_BYTES];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(&state) {
+            chunk.copy_from_slice(&word[lane].to_le_bytes());
+        }
+        *digest = Digest::new(bytes);
+    }
+    digests
+}
+
+/// Applies the compression function to the state and block of every lane.
+fn compress(state: &[Words; 4], words: &[Words; 16]) -> [Words; 4] {
+    let [mut a, mut b, mut c, mut d] = *state;
+    for n in (0..16).step_by(4) {
+        step(&mut a, &b, &c, &d, &words[n], n, f);
+        step(&mut d, &a, &b, &c, &words[n + 1], n + 1, f);
+        step(&mut c, &d, &a, &b, &words[n + 2], n + 2, f);
+        step(&mut b, &c, &d, &a, &words[n + 3], n + 3, f);
+    }
+    for n in (16..32).step_by(4) {
+        step(&mut a, &b, &c, &d, &words[(5 * n + 1) % 16], n, g);
+        step(&mut d, &a, &b, &c, &words[(5 * n + 6) % 16], n + 1, g);
+        step(&mut c, &d, &a, &b, &words[(5 * n + 11) % 16], n + 2, g);
+        step(&mut b, &c, &d, &a, &words[(5 * n + 16) % 16], n + 3, g);
+    }
+    for n in (32..48).step_by(4) {
+        step(&mut a, &b, &c, &d, &words[(3 * n + 5) % 16], n, h);
+        step(&mut d, &a, &b, &c, &words[(3 * n + 8) % 16], n + 1, h);
+        step(&mut c, &d, &a, &b, &words[(3 * n + 11) % 16], n + 2, h);
+        step(&mut b, &c, &d, &a, &words[(3 * n + 14) % 16], n + 3, h);
+    }
+    for n in (48..64).step_by(4) {
+        step(&mut a, &b, &c, &d, &words[(7 * n) % 16], n, i);
+        step(&mut d, &a, &b, &c, &words[(7 * n + 7) % 16], n + 1, i);
+        step(&mut c, &d, &a, &b, &words[(7 * n + 14) % 16], n + 2, i);
+        step(&mut b, &c, &d, &a, &words[(7 * n + 21) % 16], n + 3, i);
+    }
+    let mut result = *state;
+    for (result, word) in result.iter_mut().zip([a, b, c, d]) {
+        for lane in 0..LANES {
+            result[lane] = result[lane].wrapping_add(word[lane]);
+        }
+    }
+    result
+}
+
+/// Applies the step of the compression function to the words of every lane.
+// the loop is vectorized only in a separate function, inlined steps are compiled to scalar code
+#[inline(never)]
+fn step(
+    a: &mut Words,
+    b: &Words,
+    c: &Words,
+    d: &Words,
+    word: &Words,
+    step: usize,
+    function: impl Fn(u32, u32, u32) -> u32,
+) {
+    for lane in 0..LANES {
+        let sum = a[lane]
+            .wrapping_add(function(b[lane], c[lane], d[lane]))
+            .wrapping_add(CONSTANTS[step])
+            .wrapping_add(word[lane]);
+        a[lane] = b[lane].wrapping_add(sum.rotate_left(SHIFTS[step]));
+    }
+}
+
+/// Returns the number of blocks of the padded input of the given length.
+fn blocks(length: usize) -> usize {
+    // the padding takes at least a byte and the 8-byte length
+    (length + 8) / BLOCK_LENGTH + 1
+}
+
+/// Returns the block of the padded input, which is empty past its blocks, for the blocks which are not entirely within the input.
+fn padded_block(input: &[u8], block: usize) -> [u8; BLOCK_LENGTH] {
+    let mut data = [0; BLOCK_LENGTH];
+    let start = block * BLOCK_LENGTH;
+    if let Some(rest) = input.get(start..) {
+        let length = rest.len().min(BLOCK_LENGTH);
+        data[..length].copy_from_slice(&rest[..length]);
+        if length < BLOCK_LENGTH {
+            data[length] = 0x80;
+        }
+    }
+    if block + 1 == blocks(input.len()) {
+        let bits = (input.len() as u64).wrapping_mul(8);
+        data[BLOCK_LENGTH - 8..].copy_from_slice(&bits.to_le_bytes());
+    }
+    data
+}
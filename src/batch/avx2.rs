@@ -0,0 +1,156 @@
+//! The compression function of the interleaved hashing implemented with AVX2 intrinsics.
+//!
+//! The 16 lanes are processed as two 256-bit vectors of eight 32-bit words. This is the only place of the batch hashing with unsafe code, which is limited to calling the intrinsics on CPUs detected to support them.
+
+#![allow(unsafe_code)]
+
+use std::arch::x86_64::{
+    __m256i,
+    _mm256_add_epi32,
+    _mm256_and_si256,
+    _mm256_andnot_si256,
+    _mm256_loadu_si256,
+    _mm256_or_si256,
+    _mm256_set1_epi32,
+    _mm256_setzero_si256,
+    _mm256_sll_epi32,
+    _mm256_srl_epi32,
+    _mm256_storeu_si256,
+    _mm256_xor_si256,
+    _mm_cvtsi32_si128,
+};
+
+use super::{Words, LANES};
+use crate::compress::{CONSTANTS, SHIFTS};
+
+/// The number of lanes of a vector.
+const VECTOR_LANES: usize = 8;
+
+/// Returns `true` if the CPU supports AVX2.
+pub(super) fn available() -> bool {
+    is_x86_feature_detected!("avx2")
+}
+
+/// Applies the compression function to the state and block of every lane.
+///
+/// # Panics
+///
+/// Panics if the CPU doesn't support AVX2.
+pub(super) fn compress(state: &[Words; 4], words: &[Words; 16]) -> [Words; 4] {
+    assert!(available(), "CPU must support AVX2");
+
+    let mut result = *state;
+    for offset in (0..LANES).step_by(VECTOR_LANES) {
+        // SAFETY: the CPU supports AVX2
+        unsafe { compress_vectors(&mut result, words, offset) };
+    }
+    result
+}
+
+/// Applies the step of the compression function to the vectors of the lanes.
+macro_rules! step {
+    ($a:ident, $b:ident, $c:ident, $d:ident, $function:ident, $word:expr, $step:expr) => {
+        let sum = add(
+            add($a, $function($b, $c, $d)),
+            add(_mm256_set1_epi32(CONSTANTS[$step] as i32), $word),
+        );
+        $a = add($b, rotate(sum, SHIFTS[$step]));
+    };
+}
+
+/// Applies the compression function to the eight lanes starting at the given offset.
+#[target_feature(enable = "avx2")]
+unsafe fn compress_vectors(state: &mut [Words; 4], words: &[Words; 16], offset: usize) {
+    // closures would not inherit the target feature, so the vectors are loaded in loops
+    let mut vectors = [_mm256_setzero_si256(); 16];
+    for (vector, word) in vectors.iter_mut().zip(words) {
+        *vector = load(word, offset);
+    }
+    let words = vectors;
+    let mut a = load(&state[0], offset);
+    let mut b = load(&state[1], offset);
+    let mut c = load(&state[2], offset);
+    let mut d = load(&state[3], offset);
+    for n in (0..16).step_by(4) {
+        step!(a, b, c, d, f, words[n], n);
+        step!(d, a, b, c, f, words[n + 1], n + 1);
+        step!(c, d, a, b, f, words[n + 2], n + 2);
+        step!(b, c, d, a, f, words[n + 3], n + 3);
+    }
+    for n in (16..32).step_by(4) {
+        step!(a, b, c, d, g, words[(5 * n + 1) % 16], n);
+        step!(d, a, b, c, g, words[(5 * n + 6) % 16], n + 1);
+        step!(c, d, a, b, g, words[(5 * n + 11) % 16], n + 2);
+        step!(b, c, d, a, g, words[(5 * n + 16) % 16], n + 3);
+    }
+    for n in (32..48).step_by(4) {
+        step!(a, b, c, d, h, words[(3 * n + 5) % 16], n);
+        step!(d, a, b, c, h, words[(3 * n + 8) % 16], n + 1);
+        step!(c, d, a, b, h, words[(3 * n + 11) % 16], n + 2);
+        step!(b, c, d, a, h, words[(3 * n + 14) % 16], n + 3);
+    }
+    for n in (48..64).step_by(4) {
+        step!(a, b, c, d, i, words[(7 * n) % 16], n);
+        step!(d, a, b, c, i, words[(7 * n + 7) % 16], n + 1);
+        step!(c, d, a, b, i, words[(7 * n + 14) % 16], n + 2);
+        step!(b, c, d, a, i, words[(7 * n + 21) % 16], n + 3);
+    }
+    for (word, vector) in state.iter_mut().zip([a, b, c, d]) {
+        let vector = add(load(word, offset), vector);
+        let lanes = &mut word[offset..offset + VECTOR_LANES];
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast(), vector);
+    }
+}
+
+/// Loads the vector of the eight lanes starting at the given offset.
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn load(words: &Words, offset: usize) -> __m256i {
+    let lanes = &words[offset..offset + VECTOR_LANES];
+    _mm256_loadu_si256(lanes.as_ptr().cast())
+}
+
+/// Adds the words of the vectors, wrapping around on overflow.
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn add(a: __m256i, b: __m256i) -> __m256i {
+    _mm256_add_epi32(a, b)
+}
+
+/// Rotates the words of the vector to the left.
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn rotate(x: __m256i, shift: u32) -> __m256i {
+    let left = _mm_cvtsi32_si128(shift as i32);
+    let right = _mm_cvtsi32_si128(32 - shift as i32);
+    _mm256_or_si256(_mm256_sll_epi32(x, left), _mm256_srl_epi32(x, right))
+}
+
+/// The auxiliary function of the first round.
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn f(b: __m256i, c: __m256i, d: __m256i) -> __m256i {
+    _mm256_or_si256(_mm256_and_si256(b, c), _mm256_andnot_si256(b, d))
+}
+
+/// The auxiliary function of the second round.
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn g(b: __m256i, c: __m256i, d: __m256i) -> __m256i {
+    _mm256_or_si256(_mm256_and_si256(d, b), _mm256_andnot_si256(d, c))
+}
+
+/// The auxiliary function of the third round.
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn h(b: __m256i, c: __m256i, d: __m256i) -> __m256i {
+    _mm256_xor_si256(_mm256_xor_si256(b, c), d)
+}
+
+/// The auxiliary function of the fourth round.
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn i(b: __m256i, c: __m256i, d: __m256i) -> __m256i {
+    let not_d = _mm256_xor_si256(d, _mm256_set1_epi32(-1));
+    _mm256_xor_si256(c, _mm256_or_si256(b, not_d))
+}
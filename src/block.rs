@@ -0,0 +1,102 @@
+//! Per-block digests of data, as used by delta-sync protocols and chunk-level deduplication.
+
+use std::io::{self, Read};
+
+use crate::{Digest, Result, MD5};
+
+/// Digests of fixed-size blocks of data produced by the [`block_digests`](crate::block_digests) function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockDigests {
+    block_size: usize,
+    blocks: Vec<Digest>,
+    digest: Digest,
+}
+
+impl BlockDigests {
+    /// Returns the size of the blocks.
+    #[must_use]
+    pub const fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the digests of the blocks, in order; the last block can be shorter than the block size.
+    #[must_use]
+    pub fn blocks(&self) -> &[Digest] {
+        &self.blocks
+    }
+
+    /// Returns the digest of the whole data.
+    #[must_use]
+    pub const fn digest(&self) -> &Digest {
+        &self.digest
+    }
+
+    /// Returns indices of blocks which differ from the blocks of the other digests, including blocks present in only one of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the block sizes of the digests differ.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Vec<usize> {
+        assert_eq!(self.block_size, other.block_size, "block sizes must be equal");
+
+        let length = self.blocks.len().max(other.blocks.len());
+        (0..length)
+            .filter(|&index| self.blocks.get(index) != other.blocks.get(index))
+            .collect()
+    }
+
+    /// Consumes the digests, returning the digests of the blocks.
+    #[must_use]
+    pub fn into_blocks(self) -> Vec<Digest> {
+        self.blocks
+    }
+}
+
+/// Computes digests of every block of the given size read from the reader, together with the digest of the whole data.
+pub(crate) fn digests(reader: impl Read, block_size: usize) -> Result<BlockDigests> {
+    let mut hash = MD5::new();
+    let mut blocks = Vec::new();
+    for_each(reader, block_size, |block| {
+        hash.update(block);
+        blocks.push(MD5::hash(block));
+    })?;
+    let digest = hash.digest();
+    Ok(BlockDigests {
+        block_size,
+        blocks,
+        digest,
+    })
+}
+
+/// Calls the given function with every block of the given size read from the reader; the last block can be shorter.
+pub(crate) fn for_each(mut reader: impl Read, block_size: usize, mut f: impl FnMut(&[u8])) -> io::Result<()> {
+    assert!(block_size > 0, "block size must be non-zero");
+
+    let mut buffer = vec![0; block_size];
+    loop {
+        let length = read_full(&mut reader, &mut buffer)?;
+        if length == 0 {
+            break;
+        }
+        f(&buffer[..length]);
+        if length < block_size {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Reads until the buffer is full or EOF is reached, returning the length of the data.
+pub(crate) fn read_full(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut length = 0;
+    while length < buffer.len() {
+        match reader.read(&mut buffer[length..]) {
+            Ok(0) => break,
+            Ok(read) => length += read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {},
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(length)
+}
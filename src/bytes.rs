@@ -0,0 +1,53 @@
+//! This module is optional and can be enabled using the `bytes` Cargo feature.
+//!
+//! The [`hash`] function calculates the digest of any [`Buf`] implementation, such as [`Bytes`](::bytes::Bytes), [`BytesMut`](::bytes::BytesMut) or chained buffers, walking through its chunks without copying them.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `bytes` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["bytes"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features bytes
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use bytes::{Buf, Bytes};
+//! use chksum_md5 as md5;
+//!
+//! let data = Bytes::from_static(b"example").chain(Bytes::from_static(b" data"));
+//! let digest = md5::bytes::hash(data);
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! ```
+
+use bytes::Buf;
+
+use crate::{Digest, MD5};
+
+/// Computes the hash of the given buffer.
+pub fn hash(data: impl Buf) -> Digest {
+    let mut hash = MD5::new();
+    hash_with(data, &mut hash);
+    hash.digest()
+}
+
+/// Updates the given hash instance with the remaining bytes of the buffer.
+pub fn hash_with(mut data: impl Buf, hash: &mut MD5) {
+    while data.has_remaining() {
+        let chunk = data.chunk();
+        let length = chunk.len();
+        hash.update(chunk);
+        data.advance(length);
+    }
+}
@@ -0,0 +1,239 @@
+//! Caching of file digests between checksum calculations.
+//!
+//! A [`DigestCache`] maps files to their sizes, modification times and digests. When it is set with the [`Options::cache`](crate::Options::cache) option, files whose size and modification time did not change since they were cached are not read again. The cache can be saved to and loaded from the disk, so repeated audits of large trees read only modified files.
+//!
+//! The cache is consulted wherever a digest of a single file is calculated: by the [`chksum_with`](crate::chksum_with) function for files, by the [`manifest`](crate::manifest) functions, and during directory traversal with the [`Options::dedup_hardlinks`](crate::Options::dedup_hardlinks) option. The digest of a directory calculated without that option is computed over the contents of its files, so every file is still read.
+//!
+//! Files modified within the last two seconds are not cached, since further changes made within the timestamp granularity of the filesystem would not change their modification times.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//! use md5::cache::DigestCache;
+//! use md5::Options;
+//!
+//! # fn wrapper(path: &Path, cache_path: &Path) -> Result<()> {
+//! let cache = DigestCache::load(cache_path)?;
+//! let options = Options::new().cache(cache.clone());
+//! let digest = md5::chksum_with(path, &options)?;
+//! cache.save(cache_path)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::fs::{self, File, Metadata};
+use std::io::{self, BufReader, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::manifest::{path_from_bytes, path_to_bytes};
+use crate::sidecar::write_atomically;
+use crate::{Digest, Result};
+
+/// The header of cache files, followed by the format version.
+const MAGIC: &[u8; 16] = b"chksum-md5 cache";
+
+/// The version of the cache file format.
+const VERSION: u8 = 1;
+
+/// The time after which a modified file can be cached.
+const SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// A shared cache of file digests.
+///
+/// Clones of a cache share the same entries, so a cache given to [`Options::cache`](crate::Options::cache) can be saved after the calculation.
+#[derive(Clone, Default)]
+pub struct DigestCache {
+    entries: Arc<Mutex<HashMap<PathBuf, Record>>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Record {
+    size: u64,
+    modified: (i64, u32),
+    digest: [u8; 16],
+}
+
+impl DigestCache {
+    /// Creates a new empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a cache saved by the [`save`](Self::save) function, returning an empty cache when the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Self::new()),
+            Err(error) => return Err(error.into()),
+        };
+        let mut reader = BufReader::new(file);
+
+        let mut header = [0; 17];
+        reader.read_exact(&mut header).map_err(invalid)?;
+        if header[..16] != MAGIC[..] || header[16] != VERSION {
+            return Err(io::Error::new(ErrorKind::InvalidData, "invalid cache file").into());
+        }
+
+        let mut entries = HashMap::new();
+        let mut length = [0; 4];
+        loop {
+            match reader.read_exact(&mut length) {
+                Ok(()) => {},
+                Err(error) if error.kind() == ErrorKind::UnexpectedEof => break,
+                Err(error) => return Err(error.into()),
+            }
+            // the buffer grows with the data read, so a corrupted length can't allocate gigabytes up front
+            let length = u64::from(u32::from_le_bytes(length));
+            let mut path = Vec::new();
+            reader.by_ref().take(length).read_to_end(&mut path)?;
+            if path.len() as u64 != length {
+                return Err(io::Error::new(ErrorKind::InvalidData, "invalid cache file").into());
+            }
+            let path =
+                path_from_bytes(path).map_err(|_| io::Error::new(ErrorKind::InvalidData, "invalid cache file"))?;
+            let mut record = [0; 36];
+            reader.read_exact(&mut record).map_err(invalid)?;
+            let (size, record) = record.split_at(8);
+            let (secs, record) = record.split_at(8);
+            let (nanos, digest) = record.split_at(4);
+            let record = Record {
+                size: u64::from_le_bytes(size.try_into().expect("size has 8 bytes")),
+                modified: (
+                    i64::from_le_bytes(secs.try_into().expect("seconds have 8 bytes")),
+                    u32::from_le_bytes(nanos.try_into().expect("nanoseconds have 4 bytes")),
+                ),
+                digest: digest.try_into().expect("digest has 16 bytes"),
+            };
+            entries.insert(path, record);
+        }
+        let entries = Arc::new(Mutex::new(entries));
+        Ok(Self { entries })
+    }
+
+    /// Saves the cache to the given file.
+    ///
+    /// The cache is written to a uniquely named temporary file next to the given one, which is then renamed, so an interrupted save does not corrupt an existing cache and concurrent saves don't interfere.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        write_atomically(path.as_ref(), |writer| {
+            writer.write_all(MAGIC)?;
+            writer.write_all(&[VERSION])?;
+            for (path, record) in self.lock().iter() {
+                let path = path_to_bytes(path)?;
+                let length = u32::try_from(path.len())
+                    .map_err(|_| io::Error::new(ErrorKind::InvalidData, "path is too long"))?;
+                writer.write_all(&length.to_le_bytes())?;
+                writer.write_all(path)?;
+                writer.write_all(&record.size.to_le_bytes())?;
+                writer.write_all(&record.modified.0.to_le_bytes())?;
+                writer.write_all(&record.modified.1.to_le_bytes())?;
+                writer.write_all(&record.digest)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns the number of cached files.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no file is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Removes all cached files.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Returns the cached digest of the given file, if its size and modification time did not change.
+    pub(crate) fn get(&self, path: &Path, metadata: &Metadata) -> Option<Digest> {
+        let path = fs::canonicalize(path).ok()?;
+        let modified = modified(metadata)?;
+        let entries = self.lock();
+        let record = entries.get(&path)?;
+        (record.size == metadata.len() && record.modified == modified).then(|| Digest::new(record.digest))
+    }
+
+    /// Caches the digest of the given file, unless it was modified too recently.
+    pub(crate) fn insert(&self, path: &Path, metadata: &Metadata, digest: Digest) {
+        let Ok(path) = fs::canonicalize(path) else {
+            return;
+        };
+        let Some(modified) = modified(metadata) else {
+            return;
+        };
+        let settled = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|elapsed| elapsed >= SETTLE_TIME);
+        let mut entries = self.lock();
+        if settled {
+            let record = Record {
+                size: metadata.len(),
+                modified,
+                digest: digest.into_inner(),
+            };
+            entries.insert(path, record);
+        } else {
+            entries.remove(&path);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, Record>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Debug for DigestCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DigestCache").field("len", &self.len()).finish()
+    }
+}
+
+/// Compares caches by identity, so options sharing the same cache are equal.
+impl PartialEq for DigestCache {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.entries, &other.entries)
+    }
+}
+
+impl Eq for DigestCache {}
+
+/// Returns the modification time of the file as seconds and nanoseconds relative to the Unix epoch.
+fn modified(metadata: &Metadata) -> Option<(i64, u32)> {
+    let modified = metadata.modified().ok()?;
+    match modified.duration_since(UNIX_EPOCH) {
+        Ok(duration) => Some((i64::try_from(duration.as_secs()).ok()?, duration.subsec_nanos())),
+        Err(error) => {
+            // times before the epoch are stored with a negative number of seconds and a non-negative fraction
+            let duration = error.duration();
+            let secs = i64::try_from(duration.as_secs()).ok()?;
+            match duration.subsec_nanos() {
+                0 => Some((-secs, 0)),
+                nanos => Some((-secs - 1, 1_000_000_000 - nanos)),
+            }
+        },
+    }
+}
+
+/// Maps truncated cache files to an error of invalid data.
+fn invalid(error: io::Error) -> io::Error {
+    if error.kind() == ErrorKind::UnexpectedEof {
+        io::Error::new(ErrorKind::InvalidData, "invalid cache file")
+    } else {
+        error
+    }
+}
@@ -0,0 +1,106 @@
+//! C interface to the [`MD5`] hash, for components written in other languages.
+//!
+//! The functions operate on an opaque `chksum_md5` handle created by [`chksum_md5_new`] and released by [`chksum_md5_free`]. Like the methods of [`MD5`], taking the digest doesn't change the hash state, so the digest of a stream can be taken at any point and the stream continued afterwards.
+//!
+//! The declarations are provided by the `include/chksum_md5.h` header. To build a shared library, pass the crate type to `cargo rustc`:
+//!
+//! ```shell
+//! cargo rustc --release --features capi --crate-type cdylib
+//! ```
+//!
+//! # Example
+//!
+//! ```c
+//! #include "chksum_md5.h"
+//!
+//! chksum_md5 *hash = chksum_md5_new();
+//! chksum_md5_update(hash, (const uint8_t *)"example data", 12);
+//! char hex[CHKSUM_MD5_HEX_LENGTH];
+//! chksum_md5_hex(hash, hex);
+//! chksum_md5_free(hash);
+//! ```
+
+#![allow(unsafe_code)]
+
+use std::ffi::c_char;
+use std::slice;
+
+use crate::hash::DIGEST_LENGTH_BYTES;
+use crate::MD5;
+
+/// Creates a new hash, which has to be released with [`chksum_md5_free`].
+#[no_mangle]
+pub extern "C" fn chksum_md5_new() -> *mut MD5 {
+    Box::into_raw(Box::new(MD5::new()))
+}
+
+/// Updates the hash state with `length` bytes of data.
+///
+/// # Safety
+///
+/// The hash has to be created by [`chksum_md5_new`] and not released. The data has to point to `length` readable bytes; it can be null if the length is zero.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_md5_update(hash: *mut MD5, data: *const u8, length: usize) {
+    if length == 0 {
+        return;
+    }
+    // SAFETY: the caller guarantees that the hash is valid and that the data points to `length` readable bytes
+    let (hash, data) = unsafe { (&mut *hash, slice::from_raw_parts(data, length)) };
+    hash.update(data);
+}
+
+/// Resets the hash state to its initial state.
+///
+/// # Safety
+///
+/// The hash has to be created by [`chksum_md5_new`] and not released.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_md5_reset(hash: *mut MD5) {
+    // SAFETY: the caller guarantees that the hash is valid
+    let hash = unsafe { &mut *hash };
+    hash.reset();
+}
+
+/// Writes the 16 bytes of the digest, without changing the hash state.
+///
+/// # Safety
+///
+/// The hash has to be created by [`chksum_md5_new`] and not released. The digest has to point to 16 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_md5_finalize(hash: *const MD5, digest: *mut u8) {
+    // SAFETY: the caller guarantees that the hash is valid and that the digest points to 16 writable bytes
+    let (hash, digest) = unsafe { (&*hash, slice::from_raw_parts_mut(digest, DIGEST_LENGTH_BYTES)) };
+    digest.copy_from_slice(hash.digest().as_bytes());
+}
+
+/// Writes the digest in the lowercase hexadecimal representation as a NUL-terminated string of 33 bytes, without changing the hash state.
+///
+/// # Safety
+///
+/// The hash has to be created by [`chksum_md5_new`] and not released. The string has to point to 33 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_md5_hex(hash: *const MD5, hex: *mut c_char) {
+    // SAFETY: the caller guarantees that the hash is valid and that the string points to 33 writable bytes
+    let (hash, hex) = unsafe {
+        (
+            &*hash,
+            slice::from_raw_parts_mut(hex.cast::<u8>(), DIGEST_LENGTH_BYTES * 2 + 1),
+        )
+    };
+    let (digits, terminator) = hex.split_at_mut(DIGEST_LENGTH_BYTES * 2);
+    digits.copy_from_slice(hash.digest().to_hex_lowercase().as_bytes());
+    terminator[0] = 0;
+}
+
+/// Releases the hash.
+///
+/// # Safety
+///
+/// The hash has to be created by [`chksum_md5_new`] and not released before; it can be null, in which case nothing happens.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_md5_free(hash: *mut MD5) {
+    if !hash.is_null() {
+        // SAFETY: the caller guarantees that the hash was created by `chksum_md5_new` and not released
+        drop(unsafe { Box::from_raw(hash) });
+    }
+}
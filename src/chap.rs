@@ -0,0 +1,36 @@
+//! The Challenge-Handshake Authentication Protocol with MD5, as specified in [RFC 1994](https://www.rfc-editor.org/rfc/rfc1994).
+//!
+//! The response to a challenge is the MD5 digest of the identifier of the `Challenge` packet, followed by the shared secret and the challenge value. It is used by PPP and by iSCSI.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_md5::chap;
+//!
+//! let challenge = [0x5A; 16];
+//! let response = chap::response(1, b"secret", &challenge);
+//! assert!(chap::verify(1, b"secret", &challenge, response.as_bytes()));
+//! ```
+
+use crate::{Digest, MD5};
+
+/// Calculates the response to the challenge, `MD5(identifier || secret || challenge)`.
+#[must_use]
+pub fn response(id: u8, secret: &[u8], challenge: &[u8]) -> Digest {
+    let mut hash = MD5::new();
+    hash.update([id]);
+    hash.update(secret);
+    hash.update(challenge);
+    hash.digest()
+}
+
+/// Checks whether the value of a `Response` packet answers the challenge.
+///
+/// Values which are not 16 bytes long never match. Digests are compared in constant time.
+#[must_use]
+pub fn verify(id: u8, secret: &[u8], challenge: &[u8], response: &[u8]) -> bool {
+    let Ok(received) = <[u8; 16]>::try_from(response) else {
+        return false;
+    };
+    crate::verify::eq(&self::response(id, secret, challenge), &Digest::new(received))
+}
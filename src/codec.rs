@@ -0,0 +1,137 @@
+//! This module is optional and can be enabled using the `codec` Cargo feature.
+//!
+//! The [`FrameCodec`] is a [`tokio-util`](tokio_util::codec) codec of length-prefixed frames followed by the MD5 digest of their payload, giving lightweight integrity checks of messages on internal links where TLS is unavailable. Each frame is the length of the payload as a 32-bit big-endian integer, the payload, and its 16-byte digest.
+//!
+//! The digest detects corruption only; it doesn't protect against tampering, since anyone able to modify a frame can recompute its digest.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `codec` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["codec"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features codec
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! # use chksum_md5::Result;
+//! use bytes::{Bytes, BytesMut};
+//! use chksum_md5::codec::FrameCodec;
+//! use tokio_util::codec::{Decoder, Encoder};
+//!
+//! # fn wrapper() -> Result<()> {
+//! let mut codec = FrameCodec::new();
+//! let mut buffer = BytesMut::new();
+//! codec.encode(Bytes::from("example data"), &mut buffer)?;
+//! assert_eq!(buffer.len(), 4 + 12 + 16);
+//!
+//! let frame = codec.decode(&mut buffer)?;
+//! assert_eq!(frame.as_deref(), Some(&b"example data"[..]));
+//! # Ok(())
+//! # }
+//! ```
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::hash::DIGEST_LENGTH_BYTES;
+use crate::{Digest, Error};
+
+/// The length of the length prefix of frames.
+const PREFIX_LENGTH: usize = 4;
+
+/// The default maximum length of payloads, 8 MiB.
+const MAX_FRAME_LENGTH: usize = 8 * 1024 * 1024;
+
+/// A codec of length-prefixed frames followed by the digest of their payload.
+///
+/// Decoding a frame whose digest doesn't match its payload fails with [`Error::FrameDigestMismatch`], and encoding or decoding a payload longer than the maximum frame length fails with [`Error::FrameTooLong`].
+#[derive(Clone, Copy, Debug)]
+pub struct FrameCodec {
+    max_frame_length: usize,
+}
+
+impl FrameCodec {
+    /// Creates a codec with the maximum frame length of 8 MiB.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum length of payloads, which bounds the memory used to buffer frames.
+    ///
+    /// Payloads can't be longer than [`u32::MAX`] bytes, the limit of the length prefix, regardless of this setting.
+    #[must_use]
+    pub fn max_frame_length(mut self, max_frame_length: usize) -> Self {
+        self.max_frame_length = max_frame_length;
+        self
+    }
+
+    /// Returns the maximum length of payloads.
+    #[must_use]
+    pub fn get_max_frame_length(&self) -> usize {
+        self.max_frame_length
+    }
+}
+
+impl Default for FrameCodec {
+    fn default() -> Self {
+        let max_frame_length = MAX_FRAME_LENGTH;
+        Self { max_frame_length }
+    }
+}
+
+impl Decoder for FrameCodec {
+    type Error = Error;
+    type Item = BytesMut;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let Some(prefix) = src.get(..PREFIX_LENGTH) else {
+            return Ok(None);
+        };
+        let length = u32::from_be_bytes(prefix.try_into().expect("prefix has 4 bytes"));
+        let length = usize::try_from(length).unwrap_or(usize::MAX);
+        if length > self.max_frame_length {
+            return Err(Error::FrameTooLong { length });
+        }
+        let frame_length = PREFIX_LENGTH + length + DIGEST_LENGTH_BYTES;
+        if src.len() < frame_length {
+            src.reserve(frame_length - src.len());
+            return Ok(None);
+        }
+
+        src.advance(PREFIX_LENGTH);
+        let payload = src.split_to(length);
+        let mut digest = [0; DIGEST_LENGTH_BYTES];
+        src.copy_to_slice(&mut digest);
+        if !crate::verify::eq(&crate::hash(&payload), &Digest::new(digest)) {
+            return Err(Error::FrameDigestMismatch);
+        }
+        Ok(Some(payload))
+    }
+}
+
+impl Encoder<Bytes> for FrameCodec {
+    type Error = Error;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let length = item.len();
+        let prefix = u32::try_from(length)
+            .ok()
+            .filter(|_| length <= self.max_frame_length)
+            .ok_or(Error::FrameTooLong { length })?;
+        dst.reserve(PREFIX_LENGTH + length + DIGEST_LENGTH_BYTES);
+        dst.put_u32(prefix);
+        dst.put_slice(&item);
+        dst.put_slice(crate::hash(&item).as_bytes());
+        Ok(())
+    }
+}
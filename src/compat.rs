@@ -0,0 +1,145 @@
+//! A drop-in replacement of the interface of the [`md5`](https://crates.io/crates/md5) crate.
+//!
+//! The [`compute`] function, the [`Digest`] tuple struct and the [`Context`] struct behave like their counterparts from the `md5` crate, including the formatting of digests, so projects can migrate by changing a single import. Digests convert into [`crate::Digest`] and back for use with the rest of this crate.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_md5::compat as md5;
+//!
+//! let digest = md5::compute(b"example data");
+//! assert_eq!(format!("{digest:x}"), "5c71dbb287630d65ca93764c34d9aa0d");
+//! assert_eq!(format!("{digest:?}"), "5c71dbb287630d65ca93764c34d9aa0d");
+//!
+//! let mut context = md5::Context::new();
+//! context.consume(b"example ");
+//! context.consume(b"data");
+//! assert_eq!(context.compute(), digest);
+//! ```
+
+use std::fmt::{self, Debug, Formatter, LowerHex, UpperHex};
+use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+
+use crate::hash::DIGEST_LENGTH_BYTES;
+use crate::MD5;
+
+/// Calculates the digest of the data.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5::compat as md5;
+///
+/// let digest = md5::compute("example data");
+/// assert_eq!(format!("{digest:X}"), "5C71DBB287630D65CA93764C34D9AA0D");
+/// ```
+pub fn compute(data: impl AsRef<[u8]>) -> Digest {
+    crate::hash(data).into()
+}
+
+/// A digest formatted like the digests of the `md5` crate.
+///
+/// Both the [`Debug`] and the [`LowerHex`] formats are lowercase hexadecimal, with the `0x` prefix in the alternate form.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Digest(pub [u8; DIGEST_LENGTH_BYTES]);
+
+impl Debug for Digest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(self, f)
+    }
+}
+
+impl LowerHex for Digest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl UpperHex for Digest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0X")?;
+        }
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02X}"))
+    }
+}
+
+impl Deref for Digest {
+    type Target = [u8; DIGEST_LENGTH_BYTES];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Digest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Digest> for [u8; DIGEST_LENGTH_BYTES] {
+    fn from(digest: Digest) -> Self {
+        digest.0
+    }
+}
+
+impl From<crate::Digest> for Digest {
+    fn from(digest: crate::Digest) -> Self {
+        Self(digest.into_inner())
+    }
+}
+
+impl From<Digest> for crate::Digest {
+    fn from(digest: Digest) -> Self {
+        Self::new(digest.0)
+    }
+}
+
+/// An incremental hash with the interface of the context of the `md5` crate.
+///
+/// Data is consumed with the [`consume`](Context::consume) method or written through the [`Write`] implementation.
+#[derive(Clone, Debug, Default)]
+pub struct Context {
+    inner: MD5,
+}
+
+impl Context {
+    /// Creates a new context.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consumes the data.
+    pub fn consume(&mut self, data: impl AsRef<[u8]>) {
+        self.inner.update(data);
+    }
+
+    /// Consumes the context, returning the digest of the consumed data.
+    #[must_use]
+    pub fn compute(self) -> Digest {
+        self.inner.digest().into()
+    }
+}
+
+impl From<Context> for Digest {
+    fn from(context: Context) -> Self {
+        context.compute()
+    }
+}
+
+impl Write for Context {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.consume(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
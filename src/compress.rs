@@ -0,0 +1,81 @@
+//! The constants, auxiliary functions and scalar compression function of MD5, as specified in RFC 1321.
+//!
+//! They are shared by the implementations processing blocks themselves: the resumable hashing of files, which needs access to the intermediate state, and the interleaved hashing of batches.
+
+/// The length of blocks.
+pub(crate) const BLOCK_LENGTH: usize = 64;
+
+/// The initial state.
+pub(crate) const STATE: [u32; 4] = [0x6745_2301, 0xEFCD_AB89, 0x98BA_DCFE, 0x1032_5476];
+
+/// The shift amounts of the steps.
+#[rustfmt::skip]
+pub(crate) const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+    5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20,
+    4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+    6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// The constants of the steps, the integer parts of the sines of integers.
+#[rustfmt::skip]
+pub(crate) const CONSTANTS: [u32; 64] = [
+    0xD76A_A478, 0xE8C7_B756, 0x2420_70DB, 0xC1BD_CEEE, 0xF57C_0FAF, 0x4787_C62A, 0xA830_4613, 0xFD46_9501,
+    0x6980_98D8, 0x8B44_F7AF, 0xFFFF_5BB1, 0x895C_D7BE, 0x6B90_1122, 0xFD98_7193, 0xA679_438E, 0x49B4_0821,
+    0xF61E_2562, 0xC040_B340, 0x265E_5A51, 0xE9B6_C7AA, 0xD62F_105D, 0x0244_1453, 0xD8A1_E681, 0xE7D3_FBC8,
+    0x21E1_CDE6, 0xC337_07D6, 0xF4D5_0D87, 0x455A_14ED, 0xA9E3_E905, 0xFCEF_A3F8, 0x676F_02D9, 0x8D2A_4C8A,
+    0xFFFA_3942, 0x8771_F681, 0x6D9D_6122, 0xFDE5_380C, 0xA4BE_EA44, 0x4BDE_CFA9, 0xF6BB_4B60, 0xBEBF_BC70,
+    0x289B_7EC6, 0xEAA1_27FA, 0xD4EF_3085, 0x0488_1D05, 0xD9D4_D039, 0xE6DB_99E5, 0x1FA2_7CF8, 0xC4AC_5665,
+    0xF429_2244, 0x432A_FF97, 0xAB94_23A7, 0xFC93_A039, 0x655B_59C3, 0x8F0C_CC92, 0xFFEF_F47D, 0x8584_5DD1,
+    0x6FA8_7E4F, 0xFE2C_E6E0, 0xA301_4314, 0x4E08_11A1, 0xF753_7E82, 0xBD3A_F235, 0x2AD7_D2BB, 0xEB86_D391,
+];
+
+/// Applies the compression function to the state and the block of [`BLOCK_LENGTH`] bytes.
+pub(crate) fn compress(state: &mut [u32; 4], block: &[u8]) {
+    let mut words = [0u32; 16];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().expect("chunk has four bytes"));
+    }
+
+    let [mut a, mut b, mut c, mut d] = *state;
+    for index in 0..64 {
+        let (f, g) = match index / 16 {
+            0 => (f(b, c, d), index),
+            1 => (g(b, c, d), (5 * index + 1) % 16),
+            2 => (h(b, c, d), (3 * index + 5) % 16),
+            _ => (i(b, c, d), (7 * index) % 16),
+        };
+        let f = f.wrapping_add(a).wrapping_add(CONSTANTS[index]).wrapping_add(words[g]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(f.rotate_left(SHIFTS[index]));
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+/// The auxiliary function of the first round.
+#[inline(always)]
+pub(crate) fn f(b: u32, c: u32, d: u32) -> u32 {
+    (b & c) | (!b & d)
+}
+
+/// The auxiliary function of the second round.
+#[inline(always)]
+pub(crate) fn g(b: u32, c: u32, d: u32) -> u32 {
+    (d & b) | (!d & c)
+}
+
+/// The auxiliary function of the third round.
+#[inline(always)]
+pub(crate) fn h(b: u32, c: u32, d: u32) -> u32 {
+    b ^ c ^ d
+}
+
+/// The auxiliary function of the fourth round.
+#[inline(always)]
+pub(crate) fn i(b: u32, c: u32, d: u32) -> u32 {
+    c ^ (b | !d)
+}
@@ -0,0 +1,67 @@
+//! This module is optional and can be enabled using the `gzip` or `zstd` Cargo features.
+//!
+//! The [`Compression`] enum selects the format used by the [`chksum_decompressed`](crate::chksum_decompressed) function, which calculates digest of the uncompressed content in one streaming pass.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `gzip` and `zstd` features:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["gzip", "zstd"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features gzip,zstd
+//! ```
+//!
+//! # Example
+#![cfg_attr(feature = "gzip", doc = "```rust")]
+#![cfg_attr(not(feature = "gzip"), doc = "```rust,ignore")]
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//! use md5::Compression;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let file = File::open(path)?;
+//! let digest = md5::chksum_decompressed(file, Compression::Gzip)?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{self, Read};
+
+/// A compression format of the input data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Compression {
+    /// The gzip format, including multi-member files.
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// The Zstandard format.
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Compression {
+    /// Wraps the given reader with a decoder of the compression format.
+    ///
+    /// The decoder can be used with other functions of this crate, e.g. to process compressed TAR archives.
+    pub fn decoder<'a>(self, reader: impl Read + 'a) -> io::Result<Box<dyn Read + 'a>> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => Ok(Box::new(flate2::read::MultiGzDecoder::new(reader))),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => Ok(Box::new(zstd::stream::read::Decoder::new(reader)?)),
+        }
+    }
+}
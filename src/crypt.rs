@@ -0,0 +1,216 @@
+//! Password hashing with the MD5-based `crypt` schemes: the `$1$` scheme of glibc and BSD systems, and the `$apr1$` variant of Apache `htpasswd` files.
+//!
+//! Both schemes are the same algorithm with a different prefix. They are considered weak and should only be used for compatibility with existing password files; hashes are formatted as `$1$<salt>$<hash>`, with a salt of up to eight characters.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use chksum_md5::Result;
+//! use chksum_md5::crypt::{self, Scheme};
+//!
+//! # fn wrapper() -> Result<()> {
+//! let hash = crypt::hash_with_salt("password", "saltsalt", Scheme::Apr1)?;
+//! assert_eq!(hash, "$apr1$saltsalt$yAAkm4libquA.ZWLHbSBq/");
+//! assert!(crypt::verify("password", &hash)?);
+//!
+//! let hash = crypt::hash("password", Scheme::Md5);
+//! assert!(crypt::verify("password", &hash)?);
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::collections::hash_map::RandomState;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::hash::{BuildHasher, Hasher};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{Digest, Error, Result, MD5};
+
+/// The characters of salts and hashes, in the order of their values.
+const ALPHABET: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// The maximum length of salts; longer salts are truncated.
+const SALT_LENGTH: usize = 8;
+
+/// The length of the encoded hash following the salt.
+const HASH_LENGTH: usize = 22;
+
+/// The number of rounds of the algorithm.
+const ROUNDS: usize = 1000;
+
+/// The MD5-based `crypt` scheme, distinguished by the prefix of hashes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scheme {
+    /// The `$1$` scheme of glibc and BSD systems.
+    Md5,
+    /// The `$apr1$` scheme of the Apache Portable Runtime, used by `htpasswd`.
+    Apr1,
+}
+
+impl Scheme {
+    /// Returns the prefix of hashes of the scheme.
+    #[must_use]
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::Md5 => "$1$",
+            Self::Apr1 => "$apr1$",
+        }
+    }
+}
+
+/// Hashes the password with a randomly generated salt.
+///
+/// Not available on the `wasm32-unknown-unknown` target, see [`generate_salt`].
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+#[must_use]
+pub fn hash(password: impl AsRef<[u8]>, scheme: Scheme) -> String {
+    let salt = generate_salt();
+    format(password.as_ref(), salt.as_bytes(), scheme)
+}
+
+/// Hashes the password with the given salt.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidSalt`] if the salt is longer than eight characters or contains a character other than letters, digits, `.` and `/`.
+pub fn hash_with_salt(password: impl AsRef<[u8]>, salt: &str, scheme: Scheme) -> Result<String> {
+    if salt.len() > SALT_LENGTH || !salt.bytes().all(|byte| ALPHABET.contains(&byte)) {
+        let salt = salt.to_owned();
+        return Err(Error::InvalidSalt { salt });
+    }
+    Ok(format(password.as_ref(), salt.as_bytes(), scheme))
+}
+
+/// Checks the password against a hash of either scheme.
+///
+/// Salts longer than eight characters are truncated, as by glibc. Hashes are compared in constant time.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidPasswordHash`] if the hash is not in the format of either scheme.
+pub fn verify(password: impl AsRef<[u8]>, hash: &str) -> Result<bool> {
+    let invalid = || {
+        let hash = hash.to_owned();
+        Error::InvalidPasswordHash { hash }
+    };
+    let (scheme, rest) = [Scheme::Md5, Scheme::Apr1]
+        .into_iter()
+        .find_map(|scheme| hash.strip_prefix(scheme.prefix()).map(|rest| (scheme, rest)))
+        .ok_or_else(invalid)?;
+    let (salt, encoded) = rest.rsplit_once('$').ok_or_else(invalid)?;
+    if salt.contains('$') || encoded.len() != HASH_LENGTH || !encoded.bytes().all(|byte| ALPHABET.contains(&byte)) {
+        return Err(invalid());
+    }
+    let salt = &salt.as_bytes()[..salt.len().min(SALT_LENGTH)];
+    let expected = format(password.as_ref(), salt, scheme);
+    let expected = &expected.as_bytes()[expected.len() - HASH_LENGTH..];
+    let difference = expected
+        .iter()
+        .zip(encoded.as_bytes())
+        .fold(0, |difference, (a, b)| difference | (a ^ b));
+    Ok(std::hint::black_box(difference) == 0)
+}
+
+/// Generates a random salt of eight characters.
+///
+/// Salts have to be unique rather than secret, so they are derived from the randomly seeded hasher of the standard library, the current time and a counter, without a dependency on a random number generator.
+///
+/// Not available on the `wasm32-unknown-unknown` target, which has neither a randomly seeded hasher nor a clock; use [`hash_with_salt`] with a salt from a random source of the environment, such as `crypto.getRandomValues` in browsers.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+#[must_use]
+pub fn generate_salt() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(elapsed.as_nanos());
+    }
+    let mut value = hasher.finish();
+    (0..SALT_LENGTH)
+        .map(|_| {
+            let character = char::from(ALPHABET[(value & 0x3F) as usize]);
+            value >>= 6;
+            character
+        })
+        .collect()
+}
+
+/// Formats the hash of the password with the given salt of at most eight bytes.
+fn format(password: &[u8], salt: &[u8], scheme: Scheme) -> String {
+    let digest = digest(password, salt, scheme.prefix().as_bytes());
+    let bytes = digest.as_bytes();
+
+    let mut hash = String::with_capacity(scheme.prefix().len() + salt.len() + 1 + HASH_LENGTH);
+    hash.push_str(scheme.prefix());
+    hash.extend(salt.iter().copied().map(char::from));
+    hash.push('$');
+    for [a, b, c] in [[0, 6, 12], [1, 7, 13], [2, 8, 14], [3, 9, 15], [4, 10, 5]] {
+        let value = u32::from(bytes[a]) << 16 | u32::from(bytes[b]) << 8 | u32::from(bytes[c]);
+        encode(&mut hash, value, 4);
+    }
+    encode(&mut hash, u32::from(bytes[11]), 2);
+    hash
+}
+
+/// Calculates the digest of the password after the rounds of the algorithm.
+fn digest(password: &[u8], salt: &[u8], prefix: &[u8]) -> Digest {
+    let mut alternate = MD5::new();
+    alternate.update(password);
+    alternate.update(salt);
+    alternate.update(password);
+    let alternate = alternate.digest();
+
+    let mut hash = MD5::new();
+    hash.update(password);
+    hash.update(prefix);
+    hash.update(salt);
+    for chunk in password.chunks(alternate.as_bytes().len()) {
+        hash.update(&alternate.as_bytes()[..chunk.len()]);
+    }
+    // the bits of the length select between a zero byte and the first byte of the password
+    let mut length = password.len();
+    while length > 0 {
+        if length & 1 == 1 {
+            hash.update([0]);
+        } else {
+            hash.update(&password[..1]);
+        }
+        length >>= 1;
+    }
+    let mut digest = hash.digest();
+
+    for round in 0..ROUNDS {
+        let mut hash = MD5::new();
+        if round % 2 == 1 {
+            hash.update(password);
+        } else {
+            hash.update(digest);
+        }
+        if round % 3 != 0 {
+            hash.update(salt);
+        }
+        if round % 7 != 0 {
+            hash.update(password);
+        }
+        if round % 2 == 1 {
+            hash.update(digest);
+        } else {
+            hash.update(password);
+        }
+        digest = hash.digest();
+    }
+    digest
+}
+
+/// Appends the given number of characters encoding the value, least significant bits first.
+fn encode(hash: &mut String, mut value: u32, characters: usize) {
+    for _ in 0..characters {
+        hash.push(char::from(ALPHABET[(value & 0x3F) as usize]));
+        value >>= 6;
+    }
+}
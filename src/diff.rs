@@ -0,0 +1,82 @@
+//! Comparison of directory trees.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::{chksum_with, walk, Options, Result};
+
+/// The difference between two directory trees produced by the [`compare_trees`](crate::compare_trees) function.
+///
+/// All paths are relative to the roots of the compared trees and sorted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TreeDiff {
+    pub(crate) only_in_a: Vec<PathBuf>,
+    pub(crate) only_in_b: Vec<PathBuf>,
+    pub(crate) differs: Vec<PathBuf>,
+    pub(crate) identical: Vec<PathBuf>,
+}
+
+impl TreeDiff {
+    /// Returns files which exist only in the first tree.
+    #[must_use]
+    pub fn only_in_a(&self) -> &[PathBuf] {
+        &self.only_in_a
+    }
+
+    /// Returns files which exist only in the second tree.
+    #[must_use]
+    pub fn only_in_b(&self) -> &[PathBuf] {
+        &self.only_in_b
+    }
+
+    /// Returns files which exist in both trees with different contents.
+    #[must_use]
+    pub fn differs(&self) -> &[PathBuf] {
+        &self.differs
+    }
+
+    /// Returns files which exist in both trees with identical contents.
+    #[must_use]
+    pub fn identical(&self) -> &[PathBuf] {
+        &self.identical
+    }
+
+    /// Returns `true` if both trees contain the same files with identical contents.
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.differs.is_empty()
+    }
+}
+
+/// Compares files of two trees, hashing only files of equal size.
+pub(crate) fn compare(a: &Path, b: &Path, options: &Options) -> Result<TreeDiff> {
+    let files = |root: &Path| -> Result<BTreeMap<PathBuf, u64>> {
+        let files = walk::files(root, options)?
+            .into_iter()
+            .map(|(path, size)| {
+                let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+                (relative, size)
+            })
+            .collect();
+        Ok(files)
+    };
+    let files_a = files(a)?;
+    let mut files_b = files(b)?;
+
+    let mut diff = TreeDiff::default();
+    for (path, size_a) in files_a {
+        match files_b.remove(&path) {
+            None => diff.only_in_a.push(path),
+            Some(size_b) if size_a != size_b => diff.differs.push(path),
+            Some(_) => {
+                if chksum_with(a.join(&path), options)? == chksum_with(b.join(&path), options)? {
+                    diff.identical.push(path);
+                } else {
+                    diff.differs.push(path);
+                }
+            },
+        }
+    }
+    diff.only_in_b = files_b.into_keys().collect();
+    Ok(diff)
+}
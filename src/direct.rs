@@ -0,0 +1,52 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+/// The alignment of buffers, lengths and offsets of reads, which covers the logical block sizes of common devices.
+const ALIGNMENT: usize = 4096;
+
+/// Hashes the file with reads bypassing the page cache.
+///
+/// Returns the number of bytes read, or `None` when the filesystem doesn't support direct I/O, in which case nothing is hashed.
+pub(crate) fn hash(path: &Path, buffer_size: usize, update: &mut impl FnMut(&[u8])) -> io::Result<Option<u64>> {
+    let mut file = match OpenOptions::new().read(true).custom_flags(libc::O_DIRECT).open(path) {
+        Ok(file) => file,
+        Err(error) if error.raw_os_error() == Some(libc::EINVAL) => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    // the buffer is a slice of a larger allocation, which starts at an aligned address
+    let buffer_size = buffer_size.max(1).div_ceil(ALIGNMENT) * ALIGNMENT;
+    let mut allocation = vec![0; buffer_size + ALIGNMENT];
+    let offset = allocation.as_ptr().align_offset(ALIGNMENT);
+    let buffer = &mut allocation[offset..offset + buffer_size];
+    let mut position = 0;
+    loop {
+        let read = match file.read(buffer) {
+            Ok(0) => return Ok(Some(position)),
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        update(&buffer[..read]);
+        position += read as u64;
+        if read % ALIGNMENT != 0 {
+            break;
+        }
+    }
+
+    // reads after a short one are unaligned, which direct I/O rejects, so the rest of a file which has grown is read in the usual way
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(position))?;
+    let buffer = &mut allocation[..buffer_size];
+    loop {
+        let read = match file.read(buffer) {
+            Ok(0) => return Ok(Some(position)),
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        update(&buffer[..read]);
+        position += read as u64;
+    }
+}
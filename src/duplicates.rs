@@ -0,0 +1,30 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::{walk, Digest, Options, Result};
+
+/// Groups identical files under the given path, hashing only files which share their size with another file.
+pub(crate) fn find<F>(path: &Path, options: &Options, hash: F) -> Result<Vec<Vec<PathBuf>>>
+where
+    F: FnOnce(&[PathBuf]) -> Vec<Result<Digest>>,
+{
+    let mut sizes: BTreeMap<u64, Vec<PathBuf>> = BTreeMap::new();
+    for (path, size) in walk::files(path, options)? {
+        sizes.entry(size).or_default().push(path);
+    }
+
+    let (sizes, candidates): (Vec<u64>, Vec<PathBuf>) = sizes
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .flat_map(|(size, paths)| paths.into_iter().map(move |path| (size, path)))
+        .unzip();
+    let digests = hash(&candidates);
+
+    let mut groups: BTreeMap<(u64, [u8; 16]), Vec<PathBuf>> = BTreeMap::new();
+    for ((size, path), digest) in sizes.into_iter().zip(candidates).zip(digests) {
+        groups.entry((size, digest?.into_inner())).or_default().push(path);
+    }
+    let mut groups: Vec<Vec<PathBuf>> = groups.into_values().filter(|paths| paths.len() > 1).collect();
+    groups.sort();
+    Ok(groups)
+}
@@ -0,0 +1,85 @@
+//! Object-safe hashing, for applications which select the hash function at runtime.
+//!
+//! The [`Hash`](crate::Hash) trait is not object safe, since its methods are generic over the input. The [`DynHash`] trait exposes the same operations with byte slices and boxed digests, and it is implemented for every cloneable [`Hash`](crate::Hash), including [`MD5`](crate::MD5), [`Hmac`](crate::hmac::Hmac) and the hashes of the other `chksum` crates, so they can be held as `Box<dyn DynHash>` side by side.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_md5 as md5;
+//! use md5::dyn_hash::DynHash;
+//!
+//! fn hasher(name: &str) -> Option<Box<dyn DynHash>> {
+//!     match name {
+//!         "md5" => Some(Box::new(md5::new())),
+//!         _ => None,
+//!     }
+//! }
+//!
+//! let mut hash = hasher("md5").unwrap();
+//! hash.update(b"example data");
+//! assert_eq!(hash.output_size(), 16);
+//! assert_eq!(&*hash.digest(), md5::hash(b"example data").as_bytes());
+//! ```
+
+use chksum_core as core;
+
+/// An object-safe hash function, implemented for every cloneable [`Hash`](crate::Hash).
+pub trait DynHash {
+    /// Updates the hash state with an input data.
+    fn update(&mut self, data: &[u8]);
+
+    /// Resets the hash state to its initial state.
+    fn reset(&mut self);
+
+    /// Produces the bytes of the hash digest, without changing the hash state.
+    #[must_use]
+    fn digest(&self) -> Box<[u8]>;
+
+    /// Produces the bytes of the hash digest and resets the hash state.
+    #[must_use]
+    fn digest_reset(&mut self) -> Box<[u8]> {
+        let digest = self.digest();
+        self.reset();
+        digest
+    }
+
+    /// Returns the length of digests in bytes.
+    #[must_use]
+    fn output_size(&self) -> usize;
+
+    /// Clones the hash state into a box.
+    #[must_use]
+    fn box_clone(&self) -> Box<dyn DynHash>;
+}
+
+impl<T> DynHash for T
+where
+    T: core::Hash + Clone + 'static,
+    T::Digest: AsRef<[u8]>,
+{
+    fn update(&mut self, data: &[u8]) {
+        core::Hash::update(self, data);
+    }
+
+    fn reset(&mut self) {
+        core::Hash::reset(self);
+    }
+
+    fn digest(&self) -> Box<[u8]> {
+        Box::from(core::Hash::digest(self).as_ref())
+    }
+
+    fn output_size(&self) -> usize {
+        core::Hash::digest(&T::default()).as_ref().len()
+    }
+
+    fn box_clone(&self) -> Box<dyn DynHash> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn DynHash> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
@@ -0,0 +1,72 @@
+//! The EAP-MD5 authentication method of the Extensible Authentication Protocol, as specified in [RFC 3748](https://www.rfc-editor.org/rfc/rfc3748#section-5.4).
+//!
+//! EAP-MD5 is CHAP carried in EAP packets, as used by 802.1X: the response value is the MD5 digest of the identifier of the request, followed by the password and the challenge value, see the [`chap`] module.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use chksum_md5::Result;
+//! use chksum_md5::eap_md5;
+//!
+//! # fn wrapper(packet: &[u8]) -> Result<()> {
+//! let challenge = eap_md5::parse_challenge(packet)?;
+//! let response = eap_md5::response(challenge.identifier, b"password", challenge.value);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{chap, Digest, Error, Result};
+
+/// The code of EAP `Request` packets.
+const REQUEST: u8 = 1;
+
+/// The type of EAP-MD5 `Request` and `Response` packets.
+const MD5_CHALLENGE: u8 = 4;
+
+/// The MD5-Challenge of an EAP `Request` packet, parsed by the [`parse_challenge`] function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Challenge<'a> {
+    /// The identifier of the packet, which the response has to match.
+    pub identifier: u8,
+    /// The challenge value.
+    pub value: &'a [u8],
+    /// The name of the authenticator, which can be empty.
+    pub name: &'a [u8],
+}
+
+/// Parses the MD5-Challenge of an EAP `Request` packet, starting with the code of the packet.
+///
+/// Bytes following the length given in the header, such as the padding of Ethernet frames, are ignored.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidEapPacket`] if the packet is not an EAP-MD5 `Request`, its length is inconsistent, or the challenge value is empty.
+pub fn parse_challenge(packet: &[u8]) -> Result<Challenge<'_>> {
+    let [code, identifier, length_high, length_low, kind, data @ ..] = packet else {
+        return Err(Error::InvalidEapPacket);
+    };
+    let length = usize::from(u16::from_be_bytes([*length_high, *length_low]));
+    if *code != REQUEST || *kind != MD5_CHALLENGE || length < 6 || length > packet.len() {
+        return Err(Error::InvalidEapPacket);
+    }
+    let [value_size, data @ ..] = &data[..length - 5] else {
+        return Err(Error::InvalidEapPacket);
+    };
+    let value_size = usize::from(*value_size);
+    if value_size == 0 || value_size > data.len() {
+        return Err(Error::InvalidEapPacket);
+    }
+    let (value, name) = data.split_at(value_size);
+    Ok(Challenge {
+        identifier: *identifier,
+        value,
+        name,
+    })
+}
+
+/// Calculates the value of the EAP-MD5 `Response` packet, `MD5(identifier || password || challenge)`.
+#[must_use]
+pub fn response(identifier: u8, password: &[u8], challenge: &[u8]) -> Digest {
+    chap::response(identifier, password, challenge)
+}
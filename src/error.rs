@@ -0,0 +1,198 @@
+//! Errors of checksum-based operations.
+
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::{io, result};
+
+use chksum_core as core;
+use chksum_hash_md5 as hash;
+
+use crate::manifest::ParseError;
+
+/// The error type for checksum-based operations.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input is an interactive terminal.
+    #[error("cannot process terminal input")]
+    IsTerminal,
+    /// The I/O error occured.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The I/O error occured while processing the given path.
+    #[error("cannot {operation} {}", path.display())]
+    Path {
+        /// The path which caused the error.
+        path: PathBuf,
+        /// The operation which failed.
+        operation: Operation,
+        /// The underlying I/O error.
+        #[source]
+        source: io::Error,
+    },
+    /// The path is a directory, while only files are accepted.
+    #[error("{} is a directory", path.display())]
+    IsADirectory {
+        /// The path of the directory.
+        path: PathBuf,
+    },
+    /// The source and the destination of a copy are the same file, e.g. the same path or hard links.
+    #[error("{} and {} are the same file", path.display(), destination.display())]
+    SameFile {
+        /// The path of the source.
+        path: PathBuf,
+        /// The path of the destination.
+        destination: PathBuf,
+    },
+    /// The path is a special file, like a FIFO or a device, rejected by the [`SpecialFiles::Error`](crate::options::SpecialFiles::Error) policy.
+    #[error("{} is not a regular file", path.display())]
+    SpecialFile {
+        /// The path of the special file.
+        path: PathBuf,
+    },
+    /// The file was changed since the resume state was saved.
+    #[error("{} was changed since the resume state was saved", path.display())]
+    ResumeMismatch {
+        /// The path of the file.
+        path: PathBuf,
+    },
+    /// The directory is its own ancestor through a symbolic link.
+    #[error("symbolic link cycle detected at {}", path.display())]
+    SymlinkCycle {
+        /// The path of the directory which closes the cycle.
+        path: PathBuf,
+    },
+    /// The line of a checksum file is malformed.
+    #[error("invalid line {line} of checksum file")]
+    InvalidLine {
+        /// The number of the line, starting from one.
+        line: usize,
+        /// The reason why the line is malformed.
+        #[source]
+        error: ParseError,
+    },
+    /// The file is not listed in its sidecar checksum file with multiple entries.
+    #[error("{} is not listed in its sidecar file", path.display())]
+    NotInSidecar {
+        /// The path of the file.
+        path: PathBuf,
+    },
+    /// The ETag is neither a hexadecimal digest nor a digest followed by a dash and the number of parts.
+    #[error("invalid ETag {etag}")]
+    InvalidEtag {
+        /// The malformed ETag.
+        etag: String,
+    },
+    /// The value of the `Content-MD5` header is not a base64 encoded digest.
+    #[error("invalid Content-MD5 value {value}")]
+    InvalidContentMd5 {
+        /// The malformed value.
+        value: String,
+    },
+    /// The salt of a password hash has more than eight characters or a character outside of the `crypt` alphabet.
+    #[error("invalid salt {salt}")]
+    InvalidSalt {
+        /// The malformed salt.
+        salt: String,
+    },
+    /// The password hash is not in the format of the MD5-based `crypt` schemes.
+    #[error("invalid password hash {hash}")]
+    InvalidPasswordHash {
+        /// The malformed hash.
+        hash: String,
+    },
+    /// The greeting of a POP3 server does not contain a valid `APOP` timestamp.
+    #[error("no APOP timestamp in {banner}")]
+    InvalidApopTimestamp {
+        /// The greeting of the server.
+        banner: String,
+    },
+    /// The `User-Password` attribute of a RADIUS request has an invalid length.
+    #[error("invalid length {length} of RADIUS User-Password")]
+    InvalidRadiusPassword {
+        /// The length of the password or of the hidden attribute.
+        length: usize,
+    },
+    /// The requested key is longer than the key derivation function can derive.
+    #[error("cannot derive key of {length} bytes")]
+    InvalidKeyLength {
+        /// The requested length of the key.
+        length: usize,
+    },
+    /// The packet is not a well-formed EAP-MD5 `Request`.
+    #[error("invalid EAP-MD5 challenge packet")]
+    InvalidEapPacket,
+    /// The frame is longer than the maximum frame length of the codec.
+    #[error("frame of {length} bytes exceeds maximum frame length")]
+    FrameTooLong {
+        /// The length of the frame.
+        length: usize,
+    },
+    /// The digest of the received frame does not match its payload.
+    #[error("frame digest mismatch")]
+    FrameDigestMismatch,
+    /// The expected digest is not a valid hexadecimal representation.
+    #[error("invalid digest")]
+    InvalidDigest(#[from] hash::digest::FormatError),
+    /// The value cannot be serialized.
+    #[cfg(feature = "serde")]
+    #[error("cannot serialize value")]
+    Serialize(#[from] bincode::Error),
+}
+
+impl From<core::Error> for Error {
+    fn from(error: core::Error) -> Self {
+        match error {
+            core::Error::IsTerminal => Self::IsTerminal,
+            core::Error::Io(error) => Self::Io(error),
+        }
+    }
+}
+
+/// A specialized [`Result`](std::result::Result) type for checksum-based operations.
+///
+/// This typedef is generally used to avoid writing out [`Error`] directly and is otherwise a direct mapping to [`Result`](std::result::Result).
+pub type Result<T> = result::Result<T, Error>;
+
+/// The operation on a path which caused an [`Error::Path`] error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Operation {
+    /// Querying metadata of the path.
+    Metadata,
+    /// Reading entries of a directory.
+    ReadDir,
+    /// Opening a file.
+    Open,
+    /// Reading contents of a file.
+    Read,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let operation = match self {
+            Self::Metadata => "read metadata of",
+            Self::ReadDir => "read directory",
+            Self::Open => "open",
+            Self::Read => "read",
+        };
+        f.write_str(operation)
+    }
+}
+
+/// Attaches the path and the operation to I/O errors.
+pub(crate) trait Context<T> {
+    fn context(self, path: &Path, operation: Operation) -> Result<T>;
+}
+
+impl<T> Context<T> for io::Result<T> {
+    fn context(self, path: &Path, operation: Operation) -> Result<T> {
+        self.map_err(|source| {
+            Error::Path {
+                path: path.to_path_buf(),
+                operation,
+                source,
+            }
+        })
+    }
+}
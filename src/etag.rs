@@ -0,0 +1,202 @@
+//! Entity tags of objects uploaded to Amazon S3 and compatible object stores.
+//!
+//! The ETag of an object uploaded in a single request is the hexadecimal MD5 digest of its contents. The ETag of an object uploaded in multiple parts is the MD5 digest of the concatenated binary digests of the parts, followed by a dash and the number of parts, e.g. `ebe97f2a4738800fe71edbe389c000a6-2`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use chksum_md5::Result;
+//! use chksum_md5::etag;
+//!
+//! # fn wrapper() -> Result<()> {
+//! let data = vec![0; 12 * 1024 * 1024];
+//! let etag = etag::s3_multipart(&data[..], 8 * 1024 * 1024)?;
+//! assert_eq!(etag, "ebe97f2a4738800fe71edbe389c000a6-2");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read};
+use std::mem;
+use std::path::Path;
+
+use crate::error::{Context, Operation};
+use crate::{chksum_with, Digest, Error, ExpectedDigest, Options, Result, MD5};
+
+/// One mebibyte, the unit of part sizes used by most S3 clients.
+const MIB: u64 = 1024 * 1024;
+
+/// Part sizes used by popular S3 clients, tried by the [`verify`] function besides powers of two.
+const COMMON_PART_SIZES: [u64; 2] = [5 * MIB, 15 * MIB];
+
+/// Calculates the ETag of the data read from the given reader until EOF, as if it was uploaded in parts of the given size.
+///
+/// Empty data is treated as a single empty part.
+///
+/// # Panics
+///
+/// Panics if the part size is zero.
+///
+/// # Errors
+///
+/// Returns an error if reading fails.
+pub fn s3_multipart(reader: impl Read, part_size: u64) -> Result<String> {
+    assert!(part_size > 0, "part size must be non-zero");
+
+    let mut parts = Parts::new(part_size);
+    let mut reader = BufReader::new(reader);
+    loop {
+        let buffer = reader.fill_buf()?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        parts.update(buffer);
+        reader.consume(length);
+    }
+    let (digest, count) = parts.finish();
+    Ok(format!("{digest:x}-{count}"))
+}
+
+/// Checks whether the given file matches the ETag, in either the single-part or the multipart form.
+///
+/// Surrounding quotes of the ETag are ignored. The part size of multipart ETags is not recorded, so the part sizes which are consistent with the size of the file and the number of parts are tried: the smallest such size, rounded up to a multiple of a mebibyte and as it is, powers of two mebibytes, and the 5 MiB and 15 MiB sizes. All candidates are checked while reading the file once.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, or [`Error::InvalidEtag`] if the ETag is malformed.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5::etag;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// assert!(etag::verify(path, "\"5c71dbb287630d65ca93764c34d9aa0d\"")?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify(path: impl AsRef<Path>, etag: &str) -> Result<bool> {
+    let path = path.as_ref();
+    let invalid = || {
+        let etag = etag.to_owned();
+        Error::InvalidEtag { etag }
+    };
+    let trimmed = etag.trim_matches('"');
+    let (expected, count) = match trimmed.split_once('-') {
+        None => {
+            let expected = trimmed.to_digest().map_err(|_| invalid())?;
+            let digest = chksum_with(path, &Options::new().file_only(true))?;
+            return Ok(crate::verify::eq(&digest, &expected));
+        },
+        Some((digest, count)) => {
+            let digest = digest.to_digest().map_err(|_| invalid())?;
+            if !count.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            match count.parse() {
+                Ok(0) | Err(_) => return Err(invalid()),
+                Ok(count) => (digest, count),
+            }
+        },
+    };
+
+    let metadata = fs::metadata(path).context(path, Operation::Metadata)?;
+    if metadata.is_dir() {
+        let path = path.to_path_buf();
+        return Err(Error::IsADirectory { path });
+    }
+    let mut candidates: Vec<_> = part_sizes(metadata.len(), count).into_iter().map(Parts::new).collect();
+    if candidates.is_empty() {
+        return Ok(false);
+    }
+
+    let file = File::open(path).context(path, Operation::Open)?;
+    let mut reader = BufReader::new(file);
+    loop {
+        let buffer = reader.fill_buf().context(path, Operation::Read)?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        candidates.iter_mut().for_each(|parts| parts.update(buffer));
+        reader.consume(length);
+    }
+    Ok(candidates.into_iter().any(|parts| {
+        let (digest, parts) = parts.finish();
+        parts == count && crate::verify::eq(&digest, &expected)
+    }))
+}
+
+/// Returns the candidate part sizes which split data of the given length into the given number of parts.
+fn part_sizes(length: u64, count: u64) -> Vec<u64> {
+    // every part but the last one is full and the last one is non-empty, unless the data is empty
+    let feasible = |size: u64| {
+        if count == 1 {
+            length <= size
+        } else {
+            (count - 1).checked_mul(size).is_some_and(|full| full < length)
+                && count.checked_mul(size).is_none_or(|total| length <= total)
+        }
+    };
+
+    let smallest = length.div_ceil(count).max(1);
+    let mut sizes = vec![smallest, smallest.div_ceil(MIB) * MIB];
+    sizes.extend(COMMON_PART_SIZES);
+    sizes.extend((0..u64::BITS - MIB.trailing_zeros()).map(|shift| MIB << shift));
+    sizes.retain(|&size| feasible(size));
+    sizes.sort_unstable();
+    sizes.dedup();
+    sizes
+}
+
+/// The digests of consecutive parts of the data.
+struct Parts {
+    part_size: u64,
+    hash: MD5,
+    filled: u64,
+    digests: MD5,
+    count: u64,
+}
+
+impl Parts {
+    fn new(part_size: u64) -> Self {
+        Self {
+            part_size,
+            hash: MD5::new(),
+            filled: 0,
+            digests: MD5::new(),
+            count: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.filled == self.part_size {
+                self.finish_part();
+            }
+            let remaining = usize::try_from(self.part_size - self.filled).unwrap_or(usize::MAX);
+            let (part, rest) = data.split_at(remaining.min(data.len()));
+            self.hash.update(part);
+            self.filled += part.len() as u64;
+            data = rest;
+        }
+    }
+
+    fn finish_part(&mut self) {
+        let hash = mem::take(&mut self.hash);
+        self.digests.update(hash.digest().as_bytes());
+        self.filled = 0;
+        self.count += 1;
+    }
+
+    /// Returns the digest of the part digests and the number of parts.
+    fn finish(mut self) -> (Digest, u64) {
+        // the last part is finished even if it is empty, so empty data has a single part
+        self.finish_part();
+        (self.digests.digest(), self.count)
+    }
+}
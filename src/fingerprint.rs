@@ -0,0 +1,183 @@
+//! Stable fingerprints of structured values.
+//!
+//! The [`Fingerprint`] trait feeds a value to the hash in a platform-independent encoding: integers are encoded as little-endian bytes, with `usize` and `isize` widened to 64 bits, and variable-length values like strings and vectors are prefixed with their lengths, so adjacent fields can never be confused. With the `derive` feature, the trait can be derived for structs with the [`Hashable`](macro@crate::Hashable) macro, which feeds fields in declaration order.
+//!
+//! # Example
+#![cfg_attr(feature = "derive", doc = "```rust")]
+#![cfg_attr(not(feature = "derive"), doc = "```rust,ignore")]
+//! use chksum_md5 as md5;
+//! use md5::fingerprint::Fingerprint;
+//!
+//! #[derive(md5::Hashable)]
+//! struct Entry {
+//!     name: String,
+//!     size: u64,
+//! }
+//!
+//! let entry = Entry {
+//!     name: String::from("example"),
+//!     size: 4,
+//! };
+//! assert_eq!(entry.fingerprint(), ("example", 4u64).fingerprint());
+//! ```
+
+use crate::{Digest, MD5};
+
+/// A value with a stable fingerprint.
+pub trait Fingerprint {
+    /// Updates the given hash instance with the encoding of this value.
+    fn fingerprint_with(&self, hash: &mut MD5);
+
+    /// Computes the fingerprint of this value.
+    #[must_use]
+    fn fingerprint(&self) -> Digest {
+        let mut hash = MD5::new();
+        self.fingerprint_with(&mut hash);
+        hash.digest()
+    }
+}
+
+/// Feeds the length prefix of a variable-length value to the hash.
+fn length(length: usize, hash: &mut MD5) {
+    hash.update((length as u64).to_le_bytes());
+}
+
+macro_rules! impl_fingerprint_for_number {
+    ($($t:ty),+) => {
+        $(
+            impl Fingerprint for $t {
+                fn fingerprint_with(&self, hash: &mut MD5) {
+                    hash.update(self.to_le_bytes());
+                }
+            }
+        )+
+    };
+}
+
+impl_fingerprint_for_number!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl Fingerprint for usize {
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        (*self as u64).fingerprint_with(hash);
+    }
+}
+
+impl Fingerprint for isize {
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        (*self as i64).fingerprint_with(hash);
+    }
+}
+
+impl Fingerprint for bool {
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        u8::from(*self).fingerprint_with(hash);
+    }
+}
+
+impl Fingerprint for char {
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        u32::from(*self).fingerprint_with(hash);
+    }
+}
+
+impl Fingerprint for () {
+    fn fingerprint_with(&self, _hash: &mut MD5) {}
+}
+
+impl Fingerprint for str {
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        length(self.len(), hash);
+        hash.update(self);
+    }
+}
+
+impl Fingerprint for String {
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        self.as_str().fingerprint_with(hash);
+    }
+}
+
+impl<T> Fingerprint for [T]
+where
+    T: Fingerprint,
+{
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        length(self.len(), hash);
+        self.iter().for_each(|value| value.fingerprint_with(hash));
+    }
+}
+
+impl<T, const N: usize> Fingerprint for [T; N]
+where
+    T: Fingerprint,
+{
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        self.as_slice().fingerprint_with(hash);
+    }
+}
+
+impl<T> Fingerprint for Vec<T>
+where
+    T: Fingerprint,
+{
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        self.as_slice().fingerprint_with(hash);
+    }
+}
+
+impl<T> Fingerprint for Option<T>
+where
+    T: Fingerprint,
+{
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        match self {
+            None => false.fingerprint_with(hash),
+            Some(value) => {
+                true.fingerprint_with(hash);
+                value.fingerprint_with(hash);
+            },
+        }
+    }
+}
+
+impl<T> Fingerprint for Box<T>
+where
+    T: Fingerprint + ?Sized,
+{
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        (**self).fingerprint_with(hash);
+    }
+}
+
+impl<T> Fingerprint for &T
+where
+    T: Fingerprint + ?Sized,
+{
+    fn fingerprint_with(&self, hash: &mut MD5) {
+        (**self).fingerprint_with(hash);
+    }
+}
+
+macro_rules! impl_fingerprint_for_tuple {
+    ($($t:ident),+) => {
+        impl<$($t),+> Fingerprint for ($($t,)+)
+        where
+            $($t: Fingerprint),+
+        {
+            #[allow(non_snake_case)]
+            fn fingerprint_with(&self, hash: &mut MD5) {
+                let ($($t,)+) = self;
+                $($t.fingerprint_with(hash);)+
+            }
+        }
+    };
+}
+
+impl_fingerprint_for_tuple!(A);
+impl_fingerprint_for_tuple!(A, B);
+impl_fingerprint_for_tuple!(A, B, C);
+impl_fingerprint_for_tuple!(A, B, C, D);
+impl_fingerprint_for_tuple!(A, B, C, D, E);
+impl_fingerprint_for_tuple!(A, B, C, D, E, F);
+impl_fingerprint_for_tuple!(A, B, C, D, E, F, G);
+impl_fingerprint_for_tuple!(A, B, C, D, E, F, G, H);
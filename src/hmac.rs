@@ -0,0 +1,151 @@
+//! Keyed-hash message authentication codes with the MD5 hash function, as specified in [RFC 2104](https://www.rfc-editor.org/rfc/rfc2104).
+//!
+//! The [`Hmac`] type calculates the authentication code of data fed incrementally, like the [`MD5`] type calculates the digest. With the `reader` and `writer` Cargo features, streams can be authenticated on the fly with the [`reader`] and [`writer`] modules.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use chksum_md5::Result;
+//! use chksum_md5::hmac::Hmac;
+//!
+//! # fn wrapper() -> Result<()> {
+//! let mut hmac = Hmac::new(b"key");
+//! hmac.update(b"example ");
+//! hmac.update(b"data");
+//! assert!(hmac.verify("3202f5c8a216915a01f5ff40692e5a28")?);
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "reader")]
+pub mod reader;
+#[cfg(feature = "writer")]
+pub mod writer;
+
+use std::fmt::{self, Debug, Formatter};
+
+use chksum_core as core;
+
+use crate::{Digest, ExpectedDigest, Result, MD5};
+
+/// The length of blocks processed by the MD5 hash function.
+const BLOCK_LENGTH: usize = crate::hash::BLOCK_LENGTH_BYTES;
+
+/// The HMAC-MD5 message authentication code.
+///
+/// The [`Default`] implementation uses an empty key, which is required by the generic readers and writers; use [`Hmac::new`] to provide the key.
+///
+/// The states derived from the key are as sensitive as the key itself, so the [`Debug`] implementation doesn't print them, and codes are not comparable with `==`; compare their digests with [`Hmac::verify`] instead.
+#[derive(Clone)]
+pub struct Hmac {
+    inner: MD5,
+    outer: MD5,
+    initial: MD5,
+}
+
+impl Hmac {
+    /// Creates a new authentication code with the given key.
+    ///
+    /// Keys longer than the block size of 64 bytes are hashed first, as specified by RFC 2104.
+    #[must_use]
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        let key = key.as_ref();
+        let mut block = [0; BLOCK_LENGTH];
+        if key.len() > BLOCK_LENGTH {
+            block[..crate::hash::DIGEST_LENGTH_BYTES].copy_from_slice(MD5::hash(key).as_bytes());
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = MD5::new();
+        inner.update(block.map(|byte| byte ^ 0x36));
+        let mut outer = MD5::new();
+        outer.update(block.map(|byte| byte ^ 0x5C));
+        Self {
+            initial: inner.clone(),
+            inner,
+            outer,
+        }
+    }
+
+    /// Updates the authentication code with the given data.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.inner.update(data);
+    }
+
+    /// Resets the authentication code to its initial state, keeping the key.
+    pub fn reset(&mut self) {
+        self.inner = self.initial.clone();
+    }
+
+    /// Produces the authentication code of the data.
+    #[must_use]
+    pub fn digest(&self) -> Digest {
+        let mut outer = self.outer.clone();
+        outer.update(self.inner.digest().as_bytes());
+        outer.digest()
+    }
+
+    /// Checks whether the authentication code of the data matches the expected one.
+    ///
+    /// The codes are compared in constant time, so the comparison doesn't reveal how many leading bytes match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidDigest`](crate::Error::InvalidDigest) error if the expected code is not a valid hexadecimal representation.
+    pub fn verify(&self, expected: impl ExpectedDigest) -> Result<bool> {
+        let expected = expected.to_digest()?;
+        Ok(crate::verify::eq(&self.digest(), &expected))
+    }
+}
+
+impl Debug for Hmac {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hmac").finish_non_exhaustive()
+    }
+}
+
+impl Default for Hmac {
+    fn default() -> Self {
+        Self::new([])
+    }
+}
+
+impl core::Hash for Hmac {
+    type Digest = Digest;
+
+    fn update<T>(&mut self, data: T)
+    where
+        T: AsRef<[u8]>,
+    {
+        self.update(data);
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn digest(&self) -> Self::Digest {
+        self.digest()
+    }
+}
+
+/// Calculates the authentication code of the given data with the given key.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5::hmac;
+///
+/// let digest = hmac::hash(b"Jefe", b"what do ya want for nothing?");
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "750c783e6ab0b503eaa86e310a5db738"
+/// );
+/// ```
+#[must_use]
+pub fn hash(key: impl AsRef<[u8]>, data: impl AsRef<[u8]>) -> Digest {
+    let mut hmac = Hmac::new(key);
+    hmac.update(data);
+    hmac.digest()
+}
@@ -0,0 +1,67 @@
+//! This module is optional and can be enabled using the `reader` Cargo feature.
+//!
+//! The [`Reader`] allows on-the-fly calculation of the authentication code while reading the data.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//! use std::io::Read; // required by reader
+//!
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let file = File::open(path)?;
+//! let mut reader = md5::hmac::reader::new(file, b"secret");
+//!
+//! let mut buffer = Vec::new();
+//! reader.read_to_end(&mut buffer)?;
+//! assert_eq!(buffer, b"example data");
+//!
+//! let digest = reader.digest();
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "e096114728a7f2caa9650419ccd91ba9"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::Read;
+
+use chksum_reader as reader;
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::AsyncRead;
+
+use crate::hmac::Hmac;
+
+/// A specialized [`Reader`](reader::Reader) type with the [`Hmac`] authentication code.
+pub type Reader<R> = reader::Reader<R, Hmac>;
+
+#[cfg(feature = "async-runtime-tokio")]
+/// A specialized [`AsyncReader`](reader::AsyncReader) type with the [`Hmac`] authentication code.
+pub type AsyncReader<R> = reader::AsyncReader<R, Hmac>;
+
+/// Creates new [`Reader`] with the given key.
+pub fn new(inner: impl Read, key: impl AsRef<[u8]>) -> Reader<impl Read> {
+    reader::with_hash(inner, Hmac::new(key))
+}
+
+/// Creates new [`Reader`] with provided authentication code.
+pub fn with_hmac(inner: impl Read, hmac: Hmac) -> Reader<impl Read> {
+    reader::with_hash(inner, hmac)
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Creates new [`AsyncReader`] with the given key.
+pub fn async_new(inner: impl AsyncRead, key: impl AsRef<[u8]>) -> AsyncReader<impl AsyncRead> {
+    reader::async_with_hash(inner, Hmac::new(key))
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Creates new [`AsyncReader`] with provided authentication code.
+pub fn async_with_hmac(inner: impl AsyncRead, hmac: Hmac) -> AsyncReader<impl AsyncRead> {
+    reader::async_with_hash(inner, hmac)
+}
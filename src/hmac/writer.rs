@@ -0,0 +1,65 @@
+//! This module is optional and can be enabled using the `writer` Cargo feature.
+//!
+//! The [`Writer`] allows on-the-fly calculation of the authentication code while writing the data.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//! use std::io::Write; // required by writer
+//!
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let file = File::create(path)?;
+//! let mut writer = md5::hmac::writer::new(file, b"secret");
+//!
+//! writer.write_all(b"example data")?;
+//!
+//! let digest = writer.digest();
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "e096114728a7f2caa9650419ccd91ba9"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::Write;
+
+use chksum_writer as writer;
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::AsyncWrite;
+
+use crate::hmac::Hmac;
+
+/// A specialized [`Writer`](writer::Writer) type with the [`Hmac`] authentication code.
+pub type Writer<W> = writer::Writer<W, Hmac>;
+
+#[cfg(feature = "async-runtime-tokio")]
+/// A specialized [`AsyncWriter`](writer::AsyncWriter) type with the [`Hmac`] authentication code.
+pub type AsyncWriter<R> = writer::AsyncWriter<R, Hmac>;
+
+/// Creates new [`Writer`] with the given key.
+pub fn new(inner: impl Write, key: impl AsRef<[u8]>) -> Writer<impl Write> {
+    writer::with_hash(inner, Hmac::new(key))
+}
+
+/// Creates new [`Writer`] with provided authentication code.
+pub fn with_hmac(inner: impl Write, hmac: Hmac) -> Writer<impl Write> {
+    writer::with_hash(inner, hmac)
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Creates new [`AsyncWriter`] with the given key.
+pub fn async_new(inner: impl AsyncWrite, key: impl AsRef<[u8]>) -> AsyncWriter<impl AsyncWrite> {
+    writer::async_with_hash(inner, Hmac::new(key))
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+/// Creates new [`AsyncWriter`] with provided authentication code.
+pub fn async_with_hmac(inner: impl AsyncWrite, hmac: Hmac) -> AsyncWriter<impl AsyncWrite> {
+    writer::async_with_hash(inner, hmac)
+}
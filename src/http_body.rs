@@ -0,0 +1,152 @@
+//! This module is optional and can be enabled using the `http-body` Cargo feature.
+//!
+//! The [`HashedBody`] adaptor wraps any [`Body`] implementation, like the bodies of `hyper` requests and responses, and hashes its data frames as they are polled. The frames are passed on unchanged, so proxies can record digests of the payloads they forward without copying them.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `http-body` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["http-body"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features http-body
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use bytes::Bytes;
+//! use chksum_md5::http_body::HashedBody;
+//! use http_body_util::{BodyExt, Full};
+//!
+//! # async fn wrapper() -> Result<(), std::convert::Infallible> {
+//! let body = HashedBody::new(Full::new(Bytes::from("example data"))).on_end(|digest| {
+//!     println!("forwarded payload with digest {digest:x}");
+//! });
+//! let data = body.collect().await?.to_bytes();
+//! assert_eq!(data, "example data");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Debug, Formatter};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+use crate::{Digest, MD5};
+
+pin_project! {
+    /// A [`Body`] hashing the data frames of the inner body as they are polled.
+    ///
+    /// The digest is available from the [`digest`](HashedBody::digest) method and passed to the [`on_end`](HashedBody::on_end) callback once the inner body reaches the end of the stream. Bodies failing with an error have no digest.
+    pub struct HashedBody<B> {
+        #[pin]
+        inner: B,
+        hash: Option<MD5>,
+        digest: Option<Digest>,
+        on_end: Option<Box<dyn FnOnce(Digest) + Send>>,
+    }
+}
+
+impl<B> HashedBody<B> {
+    /// Wraps the body.
+    #[must_use]
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            hash: Some(MD5::new()),
+            digest: None,
+            on_end: None,
+        }
+    }
+
+    /// Sets the callback receiving the digest at the end of the stream, like recording it in a log of a proxy.
+    #[must_use]
+    pub fn on_end(mut self, on_end: impl FnOnce(Digest) + Send + 'static) -> Self {
+        self.on_end = Some(Box::new(on_end));
+        self
+    }
+
+    /// Returns the digest of the data, or [`None`] until the end of the stream is reached.
+    #[must_use]
+    pub fn digest(&self) -> Option<Digest> {
+        self.digest
+    }
+
+    /// Returns a reference to the inner body.
+    #[must_use]
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Consumes the adaptor, returning the inner body.
+    #[must_use]
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> Body for HashedBody<B>
+where
+    B: Body,
+    B::Data: AsRef<[u8]>,
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let frame = ready!(this.inner.as_mut().poll_frame(cx));
+        let finished = match &frame {
+            Some(Ok(frame)) => {
+                if let (Some(data), Some(hash)) = (frame.data_ref(), this.hash.as_mut()) {
+                    hash.update(data);
+                }
+                // consumers don't poll bodies which report the end of the stream
+                this.inner.is_end_stream()
+            },
+            Some(Err(_)) => {
+                // the digest of incomplete data is meaningless
+                this.hash.take();
+                false
+            },
+            None => true,
+        };
+        if finished {
+            if let Some(hash) = this.hash.take() {
+                let digest = hash.digest();
+                *this.digest = Some(digest);
+                if let Some(on_end) = this.on_end.take() {
+                    on_end(digest);
+                }
+            }
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        // empty bodies are polled once to finish the hash
+        self.hash.is_none() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B: Debug> Debug for HashedBody<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashedBody")
+            .field("inner", &self.inner)
+            .field("digest", &self.digest)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,104 @@
+//! HTTP Digest access authentication with the `MD5` and `MD5-sess` algorithms, as specified in [RFC 7616](https://www.rfc-editor.org/rfc/rfc7616).
+//!
+//! The `response` parameter of the `Authorization` header is calculated in three steps: [`ha1`] hashes the credentials, optionally bound to the nonces of the session by [`session_ha1`] for the `MD5-sess` algorithm, [`ha2`] hashes the request, and [`response`] combines both with the nonces and the quality of protection. All values are lowercase hexadecimal digests.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_md5::http_digest::{self, Qop};
+//!
+//! let ha1 = http_digest::ha1("Mufasa", "http-auth@example.org", "Circle of Life");
+//! let ha2 = http_digest::ha2("GET", "/dir/index.html");
+//! let response = http_digest::response(
+//!     &ha1,
+//!     "7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v",
+//!     Some((Qop::Auth, 1, "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ")),
+//!     &ha2,
+//! );
+//! assert_eq!(response, "8ca523f5e9506fed4657c9700eebdbec");
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::MD5;
+
+/// The quality of protection requested by the server in the `qop` parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Qop {
+    /// Authentication of the request line.
+    Auth,
+    /// Authentication of the request line and the body.
+    AuthInt,
+}
+
+impl Qop {
+    /// Returns the value of the `qop` parameter.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Auth => "auth",
+            Self::AuthInt => "auth-int",
+        }
+    }
+}
+
+impl Display for Qop {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Calculates the hash of the credentials, `H(username:realm:password)`.
+///
+/// This is the `HA1` value of the `MD5` algorithm; for `MD5-sess` it has to be passed to [`session_ha1`].
+#[must_use]
+pub fn ha1(username: &str, realm: &str, password: impl AsRef<[u8]>) -> String {
+    hash([username.as_bytes(), realm.as_bytes(), password.as_ref()])
+}
+
+/// Calculates the `HA1` value of the `MD5-sess` algorithm, `H(H(username:realm:password):nonce:cnonce)`, from the result of [`ha1`].
+///
+/// The value is calculated once, for the first nonce and client nonce of the session.
+#[must_use]
+pub fn session_ha1(ha1: &str, nonce: &str, cnonce: &str) -> String {
+    hash([ha1, nonce, cnonce].map(str::as_bytes))
+}
+
+/// Calculates the hash of the request with the `auth` quality of protection or without it, `H(method:uri)`.
+#[must_use]
+pub fn ha2(method: &str, uri: &str) -> String {
+    hash([method, uri].map(str::as_bytes))
+}
+
+/// Calculates the hash of the request with the `auth-int` quality of protection, `H(method:uri:H(body))`.
+#[must_use]
+pub fn ha2_auth_int(method: &str, uri: &str, body: impl AsRef<[u8]>) -> String {
+    let body = crate::hash(body).to_hex_lowercase();
+    hash([method, uri, &body].map(str::as_bytes))
+}
+
+/// Calculates the `response` parameter from the results of [`ha1`] or [`session_ha1`] and [`ha2`] or [`ha2_auth_int`].
+///
+/// With a quality of protection, the nonce count and the client nonce are included as `H(HA1:nonce:nc:cnonce:qop:HA2)`, with the nonce count formatted as eight hexadecimal digits. Without it, the response is calculated as `H(HA1:nonce:HA2)` for the servers of RFC 2069.
+#[must_use]
+pub fn response(ha1: &str, nonce: &str, qop: Option<(Qop, u32, &str)>, ha2: &str) -> String {
+    match qop {
+        Some((qop, nc, cnonce)) => {
+            let nc = format!("{nc:08x}");
+            hash([ha1, nonce, &nc, cnonce, qop.as_str(), ha2].map(str::as_bytes))
+        },
+        None => hash([ha1, nonce, ha2].map(str::as_bytes)),
+    }
+}
+
+/// Hashes the values joined by colons.
+fn hash<const N: usize>(values: [&[u8]; N]) -> String {
+    let mut hash = MD5::new();
+    for (index, value) in values.into_iter().enumerate() {
+        if index > 0 {
+            hash.update(b":");
+        }
+        hash.update(value);
+    }
+    hash.digest().to_hex_lowercase()
+}
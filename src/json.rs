@@ -0,0 +1,108 @@
+use std::fmt::Write as _;
+
+use serde_json::Value;
+
+use crate::MD5;
+
+/// Feeds the canonical form of the value to the hash, following the JSON Canonicalization Scheme from RFC 8785.
+pub(crate) fn write(value: &Value, hash: &mut MD5) {
+    match value {
+        Value::Null => hash.update(b"null"),
+        Value::Bool(true) => hash.update(b"true"),
+        Value::Bool(false) => hash.update(b"false"),
+        // numbers are IEEE 754 doubles, so values without an exact representation are rounded like in ECMAScript
+        Value::Number(number) => hash.update(format_number(number.as_f64().unwrap_or_default())),
+        Value::String(string) => write_string(string, hash),
+        Value::Array(values) => {
+            hash.update(b"[");
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    hash.update(b",");
+                }
+                write(value, hash);
+            }
+            hash.update(b"]");
+        },
+        Value::Object(map) => {
+            // keys are sorted by their UTF-16 code units, which differs from the byte order for some characters
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            hash.update(b"{");
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    hash.update(b",");
+                }
+                write_string(key, hash);
+                hash.update(b":");
+                write(value, hash);
+            }
+            hash.update(b"}");
+        },
+    }
+}
+
+/// Feeds the string to the hash, escaping only the characters which must be escaped.
+fn write_string(string: &str, hash: &mut MD5) {
+    let mut escaped = String::with_capacity(string.len() + 2);
+    escaped.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\u{8}' => escaped.push_str("\\b"),
+            '\u{c}' => escaped.push_str("\\f"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\0'..='\u{1f}' => {
+                let _ = write!(escaped, "\\u{:04x}", u32::from(character));
+            },
+            _ => escaped.push(character),
+        }
+    }
+    escaped.push('"');
+    hash.update(escaped);
+}
+
+/// Formats the number like the ECMAScript `Number.prototype.toString` method.
+fn format_number(number: f64) -> String {
+    if number == 0.0 {
+        return String::from("0");
+    }
+
+    // the shortest digits which round-trip, with the exponent of the first digit
+    let scientific = format!("{:e}", number.abs());
+    let (mantissa, exponent) = scientific.split_once('e').expect("scientific notation has an exponent");
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().expect("exponent is an integer");
+    let length = digits.len() as i32;
+    let position = exponent + 1;
+
+    let mut formatted = String::new();
+    if number < 0.0 {
+        formatted.push('-');
+    }
+    if length <= position && position <= 21 {
+        formatted.push_str(&digits);
+        formatted.extend((length..position).map(|_| '0'));
+    } else if 0 < position && position <= 21 {
+        let (integer, fraction) = digits.split_at(position as usize);
+        formatted.push_str(integer);
+        formatted.push('.');
+        formatted.push_str(fraction);
+    } else if -6 < position && position <= 0 {
+        formatted.push_str("0.");
+        formatted.extend((position..0).map(|_| '0'));
+        formatted.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        formatted.push_str(first);
+        if !rest.is_empty() {
+            formatted.push('.');
+            formatted.push_str(rest);
+        }
+        let sign = if exponent < 0 { '-' } else { '+' };
+        let _ = write!(formatted, "e{sign}{}", exponent.unsigned_abs());
+    }
+    formatted
+}
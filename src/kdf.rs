@@ -0,0 +1,130 @@
+//! Legacy key derivation functions based on MD5.
+//!
+//! These functions exist to decrypt data produced by old software. They are not suitable for deriving keys from passwords for new data; use a modern password hashing function such as Argon2, scrypt or PBKDF2 with a strong hash function instead.
+
+use crate::hmac::Hmac;
+use crate::{Digest, Error, Result, MD5};
+
+/// The length of MD5 digests.
+const DIGEST_LENGTH: usize = crate::hash::DIGEST_LENGTH_BYTES;
+
+/// Derives a key with PBKDF1 of PKCS #5 v1.5, as specified in [RFC 8018](https://www.rfc-editor.org/rfc/rfc8018#section-5.1).
+///
+/// The key is the first `dk_len` bytes of the digest of the password followed by the salt, hashed `iterations` times. PKCS #5 specifies salts of eight bytes, but salts of any length are accepted for compatibility.
+///
+/// **Legacy only:** PBKDF1 derives at most 16 bytes and MD5 is cheap to compute, so the derived keys are weak against brute-force attacks.
+///
+/// # Panics
+///
+/// Panics if the number of iterations is zero.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidKeyLength`] if the requested key is longer than 16 bytes.
+///
+/// # Example
+///
+/// ```rust
+/// # use chksum_md5::Result;
+/// use chksum_md5::kdf;
+///
+/// # fn wrapper() -> Result<()> {
+/// let salt = [0x78, 0x57, 0x8E, 0x5A, 0x5D, 0x63, 0xCB, 0x06];
+/// let key = kdf::pbkdf1(b"password", &salt, 1000, 16)?;
+/// assert_eq!(key.len(), 16);
+/// # Ok(())
+/// # }
+/// ```
+pub fn pbkdf1(password: impl AsRef<[u8]>, salt: &[u8], iterations: u32, dk_len: usize) -> Result<Vec<u8>> {
+    assert!(iterations > 0, "number of iterations must be non-zero");
+    if dk_len > DIGEST_LENGTH {
+        let length = dk_len;
+        return Err(Error::InvalidKeyLength { length });
+    }
+
+    let mut hash = MD5::new();
+    hash.update(password);
+    hash.update(salt);
+    let mut digest = hash.digest();
+    for _ in 1..iterations {
+        digest = crate::hash(digest);
+    }
+    Ok(digest.as_bytes()[..dk_len].to_vec())
+}
+
+/// Derives a key and an initialization vector like the `EVP_BytesToKey` function of OpenSSL with the MD5 digest and a single iteration.
+///
+/// This is the derivation of `openssl enc` without the `-pbkdf2` option, the default before OpenSSL 1.1.0. Data encrypted with a salt starts with the `Salted__` string followed by the eight bytes of the salt. The derived bytes are the concatenation of the digests `D_i = MD5(D_(i-1) || password || salt)`, split into the key and the initialization vector.
+///
+/// **Legacy only:** the derivation is a single fast hash of the password, so keys derived from passwords are weak against brute-force attacks.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5::kdf;
+///
+/// let salt = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+/// // openssl enc -aes-256-cbc -md md5 -pass pass:password -S 0102030405060708
+/// let (key, iv) = kdf::evp_bytes_to_key(b"password", Some(&salt), 32, 16);
+/// assert_eq!((key.len(), iv.len()), (32, 16));
+/// ```
+#[must_use]
+pub fn evp_bytes_to_key(
+    password: impl AsRef<[u8]>,
+    salt: Option<&[u8; 8]>,
+    key_len: usize,
+    iv_len: usize,
+) -> (Vec<u8>, Vec<u8>) {
+    let password = password.as_ref();
+    let mut derived = Vec::with_capacity((key_len + iv_len).div_ceil(DIGEST_LENGTH) * DIGEST_LENGTH);
+    let mut previous: Option<Digest> = None;
+    while derived.len() < key_len + iv_len {
+        let mut hash = MD5::new();
+        if let Some(previous) = previous {
+            hash.update(previous);
+        }
+        hash.update(password);
+        if let Some(salt) = salt {
+            hash.update(salt);
+        }
+        let digest = hash.digest();
+        derived.extend_from_slice(digest.as_bytes());
+        previous = Some(digest);
+    }
+    derived.truncate(key_len + iv_len);
+    let iv = derived.split_off(key_len);
+    (derived, iv)
+}
+
+/// Expands the secret and the seed with the `P_MD5` function, the MD5 half of the pseudorandom function of TLS 1.0 and 1.1, as specified in [RFC 2246](https://www.rfc-editor.org/rfc/rfc2246#section-5).
+///
+/// The output is the concatenation of `HMAC_MD5(secret, A(i) + seed)`, where `A(0)` is the seed and `A(i) = HMAC_MD5(secret, A(i-1))`. The PRF of TLS 1.0 and 1.1 combines it with `P_SHA1`: the MD5 half takes the first half of the secret, rounded up, and the seed is the label followed by the seed of the handshake.
+///
+/// **Legacy only:** TLS 1.0 and 1.1 are deprecated; this function is meant for reproducing old handshakes in analyzers and test harnesses.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5::kdf;
+///
+/// let output = kdf::p_md5(b"secret", b"labelseed", 40);
+/// assert_eq!(output.len(), 40);
+/// ```
+#[must_use]
+pub fn p_md5(secret: impl AsRef<[u8]>, seed: &[u8], length: usize) -> Vec<u8> {
+    let mut hmac = Hmac::new(secret);
+    let mut output = Vec::with_capacity(length.div_ceil(DIGEST_LENGTH) * DIGEST_LENGTH);
+    hmac.update(seed);
+    let mut a = hmac.digest();
+    while output.len() < length {
+        hmac.reset();
+        hmac.update(a);
+        hmac.update(seed);
+        output.extend_from_slice(hmac.digest().as_bytes());
+        hmac.reset();
+        hmac.update(a);
+        a = hmac.digest();
+    }
+    output.truncate(length);
+    output
+}
@@ -23,7 +23,7 @@
 //!
 //! ```rust
 //! # use std::path::Path;
-//! use std::fs::File;
+//! use std::fs::{self, File};
 //!
 //! # use chksum_md5::Result;
 //! use chksum_md5 as md5;
@@ -67,6 +67,8 @@
 //!
 //! ## Bytes
 //!
+//! Byte arrays, vectors and slices are hashed directly from memory, without going through a reader or allocating an intermediate buffer.
+//!
 //! ### Array
 //!
 //! ```rust
@@ -118,6 +120,23 @@
 //! # }
 //! ```
 //!
+//! ### Smart Pointers
+//!
+//! Byte buffers held behind [`Box`], [`Rc`](std::rc::Rc), [`Arc`](std::sync::Arc) or [`Cow`](std::borrow::Cow) can be passed to the [`hash`](fn@hash) function directly.
+//!
+//! ```rust
+//! use std::sync::Arc;
+//!
+//! use chksum_md5 as md5;
+//!
+//! let data: Arc<[u8]> = Arc::from(&b"example data"[..]);
+//! let digest = md5::hash(data);
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! ```
+//!
 //! ## Strings
 //!
 //! ### str
@@ -233,12 +252,150 @@
 //! # }
 //! ```
 //!
+//! ## Reader
+//!
+//! Use the [`chksum_reader`](fn@chksum_reader) function to calculate digest of any reader, including trait objects like `&mut dyn Read` or `Box<dyn Read + Send>`.
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//! use std::io::Read;
+//!
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let reader: Box<dyn Read + Send> = Box::new(File::open(path)?);
+//! let digest = md5::chksum_reader(reader)?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The [`async_chksum_reader`] function accepts asynchronous readers, including boxed trait objects like `Box<dyn AsyncRead + Unpin + Send>` and `Pin<Box<dyn AsyncRead + Send>>`, so dynamic pipelines don't need to know the concrete reader type.
+//!
+//! ## Child Process
+//!
+//! The output streams of a child process are readers as well, so a subprocess can be piped straight into the [`chksum_reader`](fn@chksum_reader) function.
+//!
+//! ```rust
+//! use std::process::{Command, Stdio};
+//!
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//!
+//! # fn wrapper() -> Result<()> {
+//! let mut child = Command::new("generator").stdout(Stdio::piped()).spawn()?;
+//! if let Some(stdout) = child.stdout.take() {
+//!     let digest = md5::chksum_reader(stdout)?;
+//!     assert_eq!(
+//!         digest.to_hex_lowercase(),
+//!         "5c71dbb287630d65ca93764c34d9aa0d"
+//!     );
+//! }
+//! child.wait()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The same applies to [`ChildStderr`](std::process::ChildStderr) and, with the [`async_chksum_reader`] function, to the Tokio equivalents.
+//!
+//! ## Network Stream
+//!
+//! Use the [`net`] module to calculate digest of data received from TCP and Unix streams, with an optional read timeout.
+//!
+//! ## References
+//!
+//! Inputs don't have to be moved into the [`chksum`] function: paths, files, directory entries and standard input are accepted by reference as well, and the [`chksum_reader`](fn@chksum_reader) function takes any mutable reference to a reader.
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let mut file = File::open(path)?;
+//! let digest = md5::chksum(&mut file)?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! let metadata = file.metadata()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Options
+//!
+//! Use the [`chksum_with`] function to calculate digest of file or directory with custom [`Options`], like the read buffer size.
+//!
+//! ```rust
+//! # use std::path::Path;
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//! use md5::options::BufferSize;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let options = md5::Options::new().buffer_size(BufferSize::Adaptive);
+//! let digest = md5::chksum_with(path, &options)?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Migrating from the `md5` crate
+//!
+//! The [`compat`] module mirrors the interface of the `md5` crate, so replacing its import is enough to migrate.
+//!
+//! ```rust
+//! use chksum_md5::compat as md5;
+//!
+//! let digest = md5::compute(b"example data");
+//! assert_eq!(format!("{digest:x}"), "5c71dbb287630d65ca93764c34d9aa0d");
+//! ```
+//!
 //! # Features
 //!
 //! Cargo features are utilized to enable extra options.
 //!
+//! * `actix-web` enables the [`actix_web`](mod@actix_web) module with an `actix-web` middleware setting and validating the `Content-MD5` header of HTTP messages.
+//! * `bytes` enables the [`bytes`] module with support for [`Buf`](::bytes::Buf) implementations.
+//! * `capi` enables the [`capi`] module with a C interface to the [`MD5`] hash.
+//! * `cli` builds the `chksum-md5` binary with a `md5sum`-compatible interface, including the `--check`, `--tag` and `--zero` options, and hashing of directories.
+//! * `codec` enables the [`codec`] module with a `tokio-util` codec of length-prefixed frames with MD5 digests.
+//! * `derive` enables the [`Hashable`](macro@Hashable) derive macro of the [`Fingerprint`](fingerprint::Fingerprint) trait.
+//! * `direct-io` enables the [`Options::direct_io`] option with reading of files bypassing the page cache on Linux.
+//! * `gzip` enables the [`compression`] module with hashing of gzip-compressed content.
+//! * `http-body` enables the [`http_body`](mod@http_body) module with hashing of [`Body`](::http_body::Body) implementations as they are polled, like bodies of `hyper`.
+//! * `indicatif` enables the [`Options::progress`] option with reporting of the progress of hashing files to `indicatif` progress bars.
+//! * `io-uring` enables the [`Options::io_uring`] option with reading of files through `io_uring` on Linux.
+//! * `json` enables the [`hash_json_canonical`] function for hashing canonicalized JSON documents.
+//! * `md-5` enables conversions between [`Digest`] and the output of the `Md5` hash of the RustCrypto [`md-5`](::md5) crate.
+//! * `metrics` enables the [`metrics`](mod@metrics) module with counters and histograms of hashing operations recorded through the `metrics` facade.
+//! * `mmap` enables the [`mmap`] module with memory-mapped hashing of large files.
+//! * `object-store` enables the [`object_store`](mod@object_store) module with hashing of objects from cloud and local object stores.
+//! * `rayon` enables parallel processing, like the [`par_chksum_many`] function, and the [`pool`] module with thread pools provided by the caller.
 //! * `reader` enables the [`reader`] module with the [`Reader`] struct.
+//! * `reqwest` enables the [`async_chksum_url`] function for hashing remote content.
+//! * `serde` enables the [`hash_serialize`] function for hashing serializable values.
+//! * `sparse` enables the [`Options::sparse`] option with hole detection in sparse files.
+//! * `tar` enables the [`archive`] module with hashing of TAR archive entries.
+//! * `tower` enables the [`tower`](mod@tower) module with a middleware setting and validating the `Content-MD5` header of HTTP messages.
+//! * `unicode-normalization` enables the [`Options::normalize_unicode`] option with NFC normalization of entry names.
+//! * `unsafe-simd` enables an AVX2 kernel of the [`hash_batch`] function on x86-64 CPUs supporting it, with unsafe code limited to the intrinsics.
+//! * `watch` enables the [`watch`](mod@watch) module with re-hashing of files on change.
+//! * `web` enables the `web` module with hashing of browser blobs and streams on the `wasm32` targets.
 //! * `writer` enables the [`writer`] module with the [`Writer`] struct.
+//! * `zip` enables the [`archive`] module with hashing of ZIP archive entries.
+//! * `zstd` enables the [`compression`] module with hashing of Zstandard-compressed content.
 //!
 //! By default, neither of these features is enabled.
 //!
@@ -261,6 +418,20 @@
 //!
 //! By default, neither of these features is enabled.
 //!
+//! ## WebAssembly
+//!
+//! The crate supports the `wasm32-unknown-unknown` target with all features but `actix-web`, `async-runtime-tokio`, `reqwest` and `zstd`. The target has no filesystem, so functions accessing files and directories return errors there; hash data in memory or from readers instead. The `web` feature adds the `web` module, which hashes browser blobs and streams, e.g. to verify uploads on the client side.
+//!
+//! ## `no_std`
+//!
+//! The crate requires the standard library, including its core [`MD5`] and [`Digest`] types: the underlying `chksum-core` and `chksum-hash-md5` crates depend on `std`, so there is no `std` feature to disable.
+//!
+//! # Performance
+//!
+//! The MD5 compression function is inherently sequential: every step of a block depends on the result of the previous one, and every block depends on the state left by the previous block. A single stream therefore can't be spread across SIMD lanes, and its compression function is implemented by the [`chksum_hash_md5`] crate. To increase the throughput of many inputs, hash them in parallel, e.g. with the [`par_chksum_many`] function of the `rayon` feature.
+//!
+//! The interleaved implementation of the [`hash_batch`] function is safe code left to the vectorizer of the compiler. The `unsafe-simd` feature adds a kernel written with AVX2 intrinsics, used on x86-64 CPUs detected to support AVX2, and it is the only feature besides the ones binding to system interfaces which lifts the `forbid(unsafe_code)` of the crate.
+//!
 //! # Disclaimer
 //!
 //! The MD5 hash function should be used only for backward compatibility due to security issues.
@@ -272,30 +443,150 @@
 //! This crate is licensed under the MIT License.
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
-#![forbid(unsafe_code)]
+#![cfg_attr(
+    not(any(feature = "capi", feature = "io-uring", feature = "mmap", feature = "unsafe-simd")),
+    forbid(unsafe_code)
+)]
+#![cfg_attr(
+    any(feature = "capi", feature = "io-uring", feature = "mmap", feature = "unsafe-simd"),
+    deny(unsafe_code)
+)]
 
+#[cfg(feature = "actix-web")]
+pub mod actix_web;
+#[cfg(any(feature = "tar", feature = "zip"))]
+pub mod archive;
+pub mod azure;
+mod base64;
+mod batch;
+pub mod block;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+pub mod cache;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod chap;
+#[cfg(feature = "codec")]
+pub mod codec;
+pub mod compat;
+mod compress;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+pub mod compression;
+pub mod crypt;
+pub mod diff;
+#[cfg(all(feature = "direct-io", target_os = "linux"))]
+mod direct;
+mod duplicates;
+pub mod dyn_hash;
+pub mod eap_md5;
+pub mod error;
+pub mod etag;
+pub mod fingerprint;
+pub mod hmac;
+#[cfg(feature = "http-body")]
+pub mod http_body;
+pub mod http_digest;
+#[cfg(feature = "json")]
+mod json;
+pub mod kdf;
+pub mod manifest;
+pub mod merkle;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod net;
+#[cfg(feature = "object-store")]
+pub mod object_store;
+pub mod options;
+#[cfg(feature = "rayon")]
+pub mod pool;
+#[cfg(feature = "indicatif")]
+pub mod progress;
+pub mod radius;
+mod read_ahead;
 #[cfg(feature = "reader")]
 pub mod reader;
+pub mod report;
+pub mod resume;
+pub mod ring;
+pub mod seq;
+pub mod sidecar;
+#[cfg(all(
+    feature = "sparse",
+    any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd"
+    )
+))]
+mod sparse;
+#[cfg(feature = "tower")]
+pub mod tower;
+mod tuning;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+mod uring;
+pub mod uuid;
+mod vectored;
+pub mod verify;
+mod walk;
+#[cfg(feature = "watch")]
+pub mod watch;
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+pub mod web;
 #[cfg(feature = "writer")]
 pub mod writer;
 
+use std::ffi::{CStr, OsStr};
 use std::fmt::{self, Display, Formatter, LowerHex, UpperHex};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
 
 use chksum_core as core;
 #[cfg(feature = "async-runtime-tokio")]
 #[doc(no_inline)]
 pub use chksum_core::AsyncChksumable;
 #[doc(no_inline)]
-pub use chksum_core::{Chksumable, Error, Hash, Hashable, Result};
+pub use chksum_core::{Chksumable, Hash, Hashable};
 #[doc(no_inline)]
 pub use chksum_hash_md5 as hash;
+#[cfg(feature = "derive")]
+pub use chksum_md5_derive::Hashable;
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader as AsyncBufReader};
 
+#[doc(inline)]
+pub use crate::block::BlockDigests;
+#[doc(inline)]
+pub use crate::cache::DigestCache;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+#[doc(inline)]
+pub use crate::compression::Compression;
+#[doc(inline)]
+pub use crate::diff::TreeDiff;
+use crate::error::{Context, Operation};
+#[doc(inline)]
+pub use crate::error::{Error, Result};
+#[doc(inline)]
+pub use crate::options::Options;
 #[cfg(all(feature = "reader", feature = "async-runtime-tokio"))]
 #[doc(inline)]
 pub use crate::reader::AsyncReader;
 #[cfg(feature = "reader")]
 #[doc(inline)]
 pub use crate::reader::Reader;
+#[doc(inline)]
+pub use crate::report::Report;
+#[doc(inline)]
+pub use crate::seq::ChksumableSeq;
+#[doc(inline)]
+pub use crate::sidecar::SidecarVerification;
+#[doc(inline)]
+pub use crate::verify::{ExpectedDigest, VerifiedCopy};
 #[cfg(all(feature = "writer", feature = "async-runtime-tokio"))]
 #[doc(inline)]
 pub use crate::writer::AsyncWriter;
@@ -357,12 +648,250 @@ pub fn default() -> MD5 {
 ///     "5c71dbb287630d65ca93764c34d9aa0d"
 /// );
 /// ```
-pub fn hash(data: impl core::Hashable) -> Digest {
-    core::hash::<MD5>(data)
+pub fn hash(data: impl AsRef<[u8]>) -> Digest {
+    MD5::hash(data)
+}
+
+/// Computes the hash of the given input, returning it in the lowercase hexadecimal representation.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5 as md5;
+///
+/// let data = b"example data";
+/// let digest = md5::hash_hex(data);
+/// assert_eq!(digest, "5c71dbb287630d65ca93764c34d9aa0d");
+/// ```
+#[must_use]
+pub fn hash_hex(data: impl AsRef<[u8]>) -> String {
+    hash(data).to_hex_lowercase()
+}
+
+/// Computes the hash of the remaining data of the given cursor.
+///
+/// The underlying buffer is hashed directly from the current position, without going through the [`Read`] machinery. Afterwards, the cursor is positioned at the end of the buffer, exactly as if it had been read until EOF.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Cursor;
+///
+/// use chksum_md5 as md5;
+///
+/// let mut cursor = Cursor::new(b"some example data".to_vec());
+/// cursor.set_position(5);
+/// let digest = md5::hash_cursor(&mut cursor);
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// assert_eq!(cursor.position(), 17);
+/// ```
+pub fn hash_cursor<T>(cursor: &mut Cursor<T>) -> Digest
+where
+    T: AsRef<[u8]>,
+{
+    let data = cursor.get_ref().as_ref();
+    let length = data.len();
+    let position = usize::try_from(cursor.position()).map_or(length, |position| position.min(length));
+    let digest = hash(&data[position..]);
+    cursor.set_position(length as u64);
+    digest
+}
+
+/// Computes the hash of the given platform string.
+///
+/// The bytes fed to the hash depend on the platform:
+///
+/// * on Unix, the raw bytes of the string are used as they are,
+/// * on Windows, the UTF-16 string is converted to [WTF-8](https://simonsapin.github.io/wtf-8/), so valid Unicode is hashed as UTF-8 and unpaired surrogates are encoded as 3-byte sequences,
+/// * on other platforms, the [encoded bytes](OsStr::as_encoded_bytes) of the string are used.
+///
+/// Thereby, strings which are valid Unicode produce the same digest on every platform.
+///
+/// # Example
+///
+/// ```rust
+/// use std::ffi::OsString;
+///
+/// use chksum_md5 as md5;
+///
+/// let data = OsString::from("example data");
+/// let digest = md5::hash_os_str(data);
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// ```
+pub fn hash_os_str(data: impl AsRef<OsStr>) -> Digest {
+    let data = data.as_ref();
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        hash(data.as_bytes())
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+
+        let mut hash = MD5::new();
+        let mut buffer = [0; 4];
+        for unit in char::decode_utf16(data.encode_wide()) {
+            match unit {
+                Ok(char) => hash.update(char.encode_utf8(&mut buffer)),
+                Err(error) => {
+                    // generalized UTF-8 encoding of the unpaired surrogate
+                    let surrogate = error.unpaired_surrogate();
+                    #[allow(clippy::cast_possible_truncation)]
+                    let bytes = [
+                        0xE0 | (surrogate >> 12) as u8,
+                        0x80 | ((surrogate >> 6) & 0x3F) as u8,
+                        0x80 | (surrogate & 0x3F) as u8,
+                    ];
+                    hash.update(bytes);
+                },
+            }
+        }
+        hash.digest()
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        hash(data.as_encoded_bytes())
+    }
+}
+
+/// Computes the hash of the given C string.
+///
+/// The trailing nul byte is not included in the hashed data.
+///
+/// # Example
+///
+/// ```rust
+/// use std::ffi::CString;
+///
+/// use chksum_md5 as md5;
+///
+/// let data = CString::new("example data").unwrap();
+/// let digest = md5::hash_c_str(data);
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// ```
+pub fn hash_c_str(data: impl AsRef<CStr>) -> Digest {
+    hash(data.as_ref().to_bytes())
+}
+
+/// Computes the hash of the given chunks as if they were a single contiguous input.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5 as md5;
+///
+/// let chunks = vec![b"example".to_vec(), b" ".to_vec(), b"data".to_vec()];
+/// let digest = md5::hash_chunks(chunks);
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// ```
+pub fn hash_chunks<T>(chunks: impl IntoIterator<Item = T>) -> Digest
+where
+    T: AsRef<[u8]>,
+{
+    let mut hash = MD5::new();
+    chunks.into_iter().for_each(|chunk| hash.update(chunk));
+    hash.digest()
+}
+
+/// Computes the hashes of the given inputs, in their order.
+///
+/// The inputs are hashed sixteen at a time with an interleaved implementation, which lets the compiler process them with SIMD instructions. Batches of many short inputs, like log lines or keys, are hashed about two to three times faster than one input after another, depending on the vector instructions of the target.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5 as md5;
+///
+/// let digests = md5::hash_batch(&[&b"example"[..], b"data"]);
+/// assert_eq!(digests, [md5::hash(b"example"), md5::hash(b"data")]);
+/// ```
+pub fn hash_batch<T>(inputs: &[T]) -> Vec<Digest>
+where
+    T: AsRef<[u8]>,
+{
+    let inputs: Vec<&[u8]> = inputs.iter().map(AsRef::as_ref).collect();
+    batch::hash(&inputs)
+}
+
+/// Computes the hash of the given value serialized with [`bincode`] using a fixed configuration.
+///
+/// The configuration uses fixed-width little-endian integers, so equal values produce equal digests regardless of the platform, which makes this function suitable for fingerprinting values for caching.
+///
+/// # Example
+///
+/// ```rust
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper() -> Result<()> {
+/// let value = (1u32, String::from("example"));
+/// let digest = md5::hash_serialize(&value)?;
+/// assert_eq!(digest, md5::hash_serialize(&(1u32, "example"))?);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "serde")]
+pub fn hash_serialize<T>(value: &T) -> Result<Digest>
+where
+    T: serde::Serialize + ?Sized,
+{
+    use bincode::Options as _;
+
+    let data = bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_little_endian()
+        .serialize(value)?;
+    Ok(hash(data))
+}
+
+/// Computes the hash of the given JSON document in its canonical form.
+///
+/// The document is canonicalized following the JSON Canonicalization Scheme from [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785): object keys are sorted, numbers are formatted like in ECMAScript, and whitespace is dropped. This way, equivalent documents produced by different serializers have equal digests.
+///
+/// The feature enables the `float_roundtrip` feature of [`serde_json`], so parsed numbers are rounded correctly before they are formatted again.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5 as md5;
+/// use serde_json::json;
+///
+/// let a = json!({ "name": "example", "size": 4.50 });
+/// let b = serde_json::from_str(r#"{"size":4.5e0,"name":"example"}"#).unwrap();
+/// assert_eq!(md5::hash_json_canonical(&a), md5::hash_json_canonical(&b));
+/// assert_eq!(
+///     md5::hash_json_canonical(&a),
+///     md5::hash(r#"{"name":"example","size":4.5}"#)
+/// );
+/// ```
+#[cfg(feature = "json")]
+#[must_use]
+pub fn hash_json_canonical(value: &serde_json::Value) -> Digest {
+    let mut hash = MD5::new();
+    json::write(value, &mut hash);
+    hash.digest()
 }
 
 /// Computes the hash of the given input.
 ///
+/// In-memory inputs like byte arrays, vectors and slices are fed to the hash directly, with no extra copies, so calling this function with them is as cheap as calling the [`hash`](fn@hash) function.
+///
+/// Files and directories are read with a fixed buffer; use the [`chksum_with`] function to choose the read buffer size, or change the default one of all options with the [`options::set_default_buffer_size`] function.
+///
 /// # Example
 ///
 /// ```rust
@@ -377,45 +906,1147 @@ pub fn hash(data: impl core::Hashable) -> Digest {
 /// }
 /// ```
 pub fn chksum(data: impl core::Chksumable) -> Result<Digest> {
-    core::chksum::<MD5>(data)
+    let result = core::chksum::<MD5>(data).map_err(Error::from);
+    #[cfg(feature = "metrics")]
+    if let Err(error) = &result {
+        metrics::error(error);
+    }
+    result
 }
 
-/// Computes the hash of the given input.
+/// Computes the hash of the given input, returning it in the lowercase hexadecimal representation.
 ///
 /// # Example
 ///
 /// ```rust
 /// use chksum_md5 as md5;
 ///
-/// # async fn wrapper() {
 /// let data = b"example data";
-/// if let Ok(digest) = md5::async_chksum(data).await {
-///     assert_eq!(
-///         digest.to_hex_lowercase(),
-///         "5c71dbb287630d65ca93764c34d9aa0d"
-///     );
+/// if let Ok(digest) = md5::chksum_hex(data) {
+///     assert_eq!(digest, "5c71dbb287630d65ca93764c34d9aa0d");
 /// }
-/// # }
 /// ```
-#[cfg(feature = "async-runtime-tokio")]
-pub async fn async_chksum(data: impl core::AsyncChksumable) -> Result<Digest> {
-    core::async_chksum::<MD5>(data).await
+pub fn chksum_hex(data: impl core::Chksumable) -> Result<String> {
+    chksum(data).map(|digest| digest.to_hex_lowercase())
 }
 
-/// The MD5 hash instance.
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
-pub struct MD5 {
-    inner: hash::Update,
+/// Computes the hash of the given input, returning it as the value of the `Content-MD5` header defined by RFC 1864.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5 as md5;
+///
+/// let data = b"example data";
+/// if let Ok(value) = md5::content_md5(data) {
+///     assert_eq!(value, "XHHbsodjDWXKk3ZMNNmqDQ==");
+/// }
+/// ```
+pub fn content_md5(data: impl core::Chksumable) -> Result<String> {
+    chksum(data).map(|digest| digest.to_content_md5())
 }
 
-impl MD5 {
-    /// Calculates the hash digest of an input data.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use chksum_md5::MD5;
-    ///
+/// Computes the digest of the POP3 `APOP` command defined by RFC 1939, the lowercase hexadecimal digest of the timestamp of the server greeting followed by the shared secret.
+///
+/// The timestamp is the first `<...>` string of the greeting, which can be given as a whole, e.g. `+OK POP3 server ready <1896.697170952@dbc.mtview.ca.us>`.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidApopTimestamp`] if the greeting does not contain a timestamp, or the timestamp contains whitespace or control characters.
+///
+/// # Example
+///
+/// ```rust
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper() -> Result<()> {
+/// let banner = "+OK POP3 server ready <1896.697170952@dbc.mtview.ca.us>";
+/// let digest = md5::apop(banner, "tanstaaf")?;
+/// assert_eq!(digest, "c4c9334bac560ecc979e58001b3e22fb");
+/// # Ok(())
+/// # }
+/// ```
+pub fn apop(timestamp_banner: &str, password: impl AsRef<[u8]>) -> Result<String> {
+    let timestamp = timestamp_banner
+        .find('<')
+        .and_then(|start| {
+            let end = start + timestamp_banner[start..].find('>')?;
+            Some(&timestamp_banner[start..=end])
+        })
+        .filter(|timestamp| {
+            let inner = &timestamp[1..timestamp.len() - 1];
+            !inner.is_empty() && !inner.contains(|c: char| c == '<' || c.is_whitespace() || c.is_control())
+        })
+        .ok_or_else(|| {
+            let banner = timestamp_banner.to_owned();
+            Error::InvalidApopTimestamp { banner }
+        })?;
+    let mut hash = MD5::new();
+    hash.update(timestamp);
+    hash.update(password);
+    Ok(hash.digest().to_hex_lowercase())
+}
+
+/// Generates the name-based UUID of version 3 of the name in the namespace, as specified in RFC 4122.
+///
+/// The UUID is returned as its 16 bytes in the network byte order. Well-known namespaces are defined in the [`uuid`] module.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5 as md5;
+/// use md5::uuid::NAMESPACE_DNS;
+///
+/// let uuid = md5::uuid_v3(NAMESPACE_DNS, b"python.org");
+/// #[rustfmt::skip]
+/// assert_eq!(uuid, [
+///     0x6F, 0xA4, 0x59, 0xEA, 0xEE, 0x8A, 0x3C, 0xA4,
+///     0x89, 0x4E, 0xDB, 0x77, 0xE1, 0x60, 0x35, 0x5E,
+/// ]);
+/// ```
+#[must_use]
+pub fn uuid_v3(namespace: [u8; 16], name: &[u8]) -> [u8; 16] {
+    uuid::v3(namespace, name)
+}
+
+/// Computes the Gravatar hash of the email address, the lowercase hexadecimal digest of the address with surrounding whitespace removed and converted to lowercase.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5 as md5;
+///
+/// let hash = md5::gravatar_hash(" MyEmailAddress@example.com ");
+/// assert_eq!(hash, "0bc83cb571cd1c50ba6f3e8a78ef1346");
+/// ```
+#[must_use]
+pub fn gravatar_hash(email: &str) -> String {
+    hash(email.trim().to_lowercase()).to_hex_lowercase()
+}
+
+/// Returns the URL of the Gravatar avatar of the email address, see the [`gravatar_hash`] function.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5 as md5;
+///
+/// let url = md5::gravatar_url("MyEmailAddress@example.com");
+/// assert_eq!(
+///     url,
+///     "https://www.gravatar.com/avatar/0bc83cb571cd1c50ba6f3e8a78ef1346"
+/// );
+/// ```
+#[must_use]
+pub fn gravatar_url(email: &str) -> String {
+    format!("https://www.gravatar.com/avatar/{}", gravatar_hash(email))
+}
+
+/// Computes the hash of the given input and checks whether it matches the expected digest.
+///
+/// The expected digest can be given as a [`Digest`], as raw bytes, or in the hexadecimal representation, see the [`ExpectedDigest`] trait. Digests are compared in constant time.
+///
+/// # Example
+///
+/// ```rust
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper() -> Result<()> {
+/// let data = b"example data";
+/// assert!(md5::verify(data, "5c71dbb287630d65ca93764c34d9aa0d")?);
+/// assert!(!md5::verify(data, md5::hash(b"other data"))?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify(data: impl core::Chksumable, expected: impl ExpectedDigest) -> Result<bool> {
+    let expected = expected.to_digest()?;
+    let digest = chksum(data)?;
+    Ok(verify::eq(&digest, &expected))
+}
+
+/// Checks the given file against the digest listed in its sidecar, the file with the `.md5` extension appended to its name.
+///
+/// The sidecar can hold a bare hexadecimal digest or a checksum file in the GNU or BSD format. A checksum file with multiple entries has to list the file by its name. See the [`sidecar`] module for details.
+///
+/// # Errors
+///
+/// Returns an error if the file or its sidecar cannot be read, if the sidecar is malformed, or [`Error::NotInSidecar`] if the file is not listed in it.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// // reads the digest from `<path>.md5`
+/// let verification = md5::verify_sidecar(path)?;
+/// assert!(verification.is_ok());
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify_sidecar(path: impl AsRef<Path>) -> Result<SidecarVerification> {
+    sidecar::verify(path.as_ref())
+}
+
+/// Writes the data read from the given reader until EOF to the file, computing its hash, and writes the matching sidecar, the file with the `.md5` extension appended to its name.
+///
+/// The data is streamed to a temporary file in the same directory, which is synced to the disk and atomically renamed over the given path, so readers never observe a partially written file. The sidecar is written in the GNU format in the same way afterwards, listing the file by its name.
+///
+/// # Errors
+///
+/// Returns an error if reading or writing fails; the temporary file is removed then.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let data = b"example data";
+/// let digest = md5::write_with_sidecar(path, &data[..])?;
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// assert!(md5::verify_sidecar(path)?.is_ok());
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_with_sidecar(path: impl AsRef<Path>, reader: impl Read) -> Result<Digest> {
+    sidecar::write(path.as_ref(), reader)
+}
+
+/// Computes the hash of the given input.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5 as md5;
+///
+/// # async fn wrapper() {
+/// let data = b"example data";
+/// if let Ok(digest) = md5::async_chksum(data).await {
+///     assert_eq!(
+///         digest.to_hex_lowercase(),
+///         "5c71dbb287630d65ca93764c34d9aa0d"
+///     );
+/// }
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(data: impl core::AsyncChksumable) -> Result<Digest> {
+    let result = core::async_chksum::<MD5>(data).await.map_err(Error::from);
+    #[cfg(feature = "metrics")]
+    if let Err(error) = &result {
+        metrics::error(error);
+    }
+    result
+}
+
+/// Computes the hash of the given input, returning it in the lowercase hexadecimal representation.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5 as md5;
+///
+/// # async fn wrapper() {
+/// let data = b"example data";
+/// if let Ok(digest) = md5::async_chksum_hex(data).await {
+///     assert_eq!(digest, "5c71dbb287630d65ca93764c34d9aa0d");
+/// }
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_hex(data: impl core::AsyncChksumable) -> Result<String> {
+    async_chksum(data).await.map(|digest| digest.to_hex_lowercase())
+}
+
+/// Copies the entire contents of a reader into a writer, computing the hash of the transferred data.
+///
+/// Returns the number of bytes copied together with the digest.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(source: &Path, destination: &Path) -> Result<()> {
+/// let reader = File::open(source)?;
+/// let writer = File::create(destination)?;
+/// let (length, digest) = md5::copy(reader, writer)?;
+/// assert_eq!(length, 12);
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn copy(reader: impl Read, mut writer: impl Write) -> Result<(u64, Digest)> {
+    let mut hash = MD5::new();
+    let mut reader = BufReader::new(reader);
+    let mut copied = 0;
+    loop {
+        let buffer = reader.fill_buf()?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        writer.write_all(buffer)?;
+        hash.update(buffer);
+        reader.consume(length);
+        copied += length as u64;
+    }
+    writer.flush()?;
+    Ok((copied, hash.digest()))
+}
+
+/// Copies the source file to the destination, computing the hash of the source while copying.
+///
+/// The destination is created or truncated, and synced to the disk after copying. With `reread` enabled, the destination is read again and hashed, so [`VerifiedCopy::is_verified`] confirms that the data was written intact; the data may still be read from the cache of the operating system rather than from the disk.
+///
+/// # Errors
+///
+/// Returns an error if either file cannot be opened, read or written, [`Error::IsADirectory`] if the source is a directory, or [`Error::SameFile`] if the destination is the source itself, e.g. a hard link to it.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(source: &Path, destination: &Path) -> Result<()> {
+/// let copy = md5::copy_verified(source, destination, true)?;
+/// assert!(copy.is_verified());
+/// assert_eq!(
+///     copy.source.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn copy_verified(source: impl AsRef<Path>, destination: impl AsRef<Path>, reread: bool) -> Result<VerifiedCopy> {
+    let (source, destination) = (source.as_ref(), destination.as_ref());
+    // directories cannot be opened on every platform, so they are detected first
+    if fs::metadata(source).context(source, Operation::Metadata)?.is_dir() {
+        let path = source.to_path_buf();
+        return Err(Error::IsADirectory { path });
+    }
+    // creating the destination truncates it, which would destroy the source if it is the same file
+    if same_file(source, destination)? {
+        let (path, destination) = (source.to_path_buf(), destination.to_path_buf());
+        return Err(Error::SameFile { path, destination });
+    }
+    let reader = File::open(source).context(source, Operation::Open)?;
+    // the writer is closed at the end of the block, before the destination is reread
+    let (length, digest) = {
+        let mut writer = File::create(destination).context(destination, Operation::Open)?;
+        let copied = copy(reader, &mut writer)?;
+        writer.sync_all()?;
+        copied
+    };
+    let destination = if reread {
+        Some(chksum_with(destination, &Options::new().file_only(true))?)
+    } else {
+        None
+    };
+    Ok(VerifiedCopy {
+        length,
+        source: digest,
+        destination,
+    })
+}
+
+/// Returns `true` if the destination exists and is the same file as the source, comparing the device and inode numbers on Unix and the canonical paths elsewhere.
+fn same_file(source: &Path, destination: &Path) -> Result<bool> {
+    // a destination which can't be accessed is either missing or reported when it is created
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let Ok(destination) = fs::metadata(destination) else {
+            return Ok(false);
+        };
+        let source = fs::metadata(source).context(source, Operation::Metadata)?;
+        Ok(source.dev() == destination.dev() && source.ino() == destination.ino())
+    }
+    #[cfg(not(unix))]
+    {
+        let Ok(destination) = fs::canonicalize(destination) else {
+            return Ok(false);
+        };
+        let source = fs::canonicalize(source).context(source, Operation::Metadata)?;
+        Ok(source == destination)
+    }
+}
+
+/// Copies the entire contents of a reader into a writer, computing the hash of the transferred data.
+///
+/// Returns the number of bytes copied together with the digest.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+/// use tokio::fs::File;
+///
+/// # async fn wrapper(source: &Path, destination: &Path) -> Result<()> {
+/// let reader = File::open(source).await?;
+/// let writer = File::create(destination).await?;
+/// let (length, digest) = md5::async_copy(reader, writer).await?;
+/// assert_eq!(length, 12);
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_copy(reader: impl AsyncRead + Unpin, mut writer: impl AsyncWrite + Unpin) -> Result<(u64, Digest)> {
+    let mut hash = MD5::new();
+    let mut reader = AsyncBufReader::new(reader);
+    let mut copied = 0;
+    loop {
+        let buffer = reader.fill_buf().await?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        writer.write_all(buffer).await?;
+        hash.update(buffer);
+        reader.consume(length);
+        copied += length as u64;
+    }
+    writer.flush().await?;
+    Ok((copied, hash.digest()))
+}
+
+/// Computes the hash of the content downloaded from the given URL.
+///
+/// The response body is streamed through the hash chunk by chunk, without buffering the whole body. Returns the number of downloaded bytes together with the digest. Responses with client or server error status are treated as errors.
+///
+/// Only plain HTTP is supported out of the box; enable one of the TLS features of the [`reqwest`] crate in your `Cargo.toml` file to download over HTTPS.
+///
+/// # Example
+///
+/// ```rust
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # async fn wrapper() -> Result<()> {
+/// let (length, digest) = md5::async_chksum_url("http://example.com/file.txt").await?;
+/// assert_eq!(length, 12);
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "reqwest")]
+pub async fn async_chksum_url(url: impl reqwest::IntoUrl) -> Result<(u64, Digest)> {
+    let response = reqwest::get(url).await.map_err(std::io::Error::other)?;
+    async_chksum_response(response).await
+}
+
+/// Computes the hash of the body of the given response.
+///
+/// Returns the number of received bytes together with the digest. Responses with client or server error status are treated as errors.
+#[cfg(feature = "reqwest")]
+pub async fn async_chksum_response(response: reqwest::Response) -> Result<(u64, Digest)> {
+    let mut response = response.error_for_status().map_err(std::io::Error::other)?;
+    let mut hash = MD5::new();
+    let mut length = 0;
+    while let Some(chunk) = response.chunk().await.map_err(std::io::Error::other)? {
+        hash.update(&chunk);
+        length += chunk.len() as u64;
+    }
+    Ok((length, hash.digest()))
+}
+
+/// Computes the hash of the uncompressed content of the given reader.
+///
+/// The data is decompressed and hashed in one streaming pass, without storing the uncompressed content.
+///
+/// # Example
+#[cfg_attr(feature = "zstd", doc = "```rust")]
+#[cfg_attr(not(feature = "zstd"), doc = "```rust,ignore")]
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+/// use md5::Compression;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let file = File::open(path)?;
+/// let digest = md5::chksum_decompressed(file, Compression::Zstd)?;
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+pub fn chksum_decompressed(reader: impl Read, compression: Compression) -> Result<Digest> {
+    let reader = compression.decoder(reader)?;
+    chksum_reader(reader)
+}
+
+/// Computes the hash of the given inputs hashed in sequence, as if they were concatenated.
+///
+/// Any [`ChksumableSeq`] is accepted, like a tuple of inputs of different types.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let file = File::open(path)?;
+/// let digest = md5::chksum_seq((b"example ", file))?;
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_seq(mut inputs: impl ChksumableSeq) -> Result<Digest> {
+    let mut hash = MD5::new();
+    inputs.chksum_seq_with(&mut hash)?;
+    Ok(hash.digest())
+}
+
+/// Computes the hashes of the given inputs, one by one.
+///
+/// A failure of a single input does not stop processing of the remaining ones.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(paths: Vec<PathBuf>) -> Result<()> {
+/// for digest in md5::chksum_many(paths) {
+///     assert_eq!(
+///         digest?.to_hex_lowercase(),
+///         "5c71dbb287630d65ca93764c34d9aa0d"
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_many<T>(inputs: impl IntoIterator<Item = T>) -> Vec<Result<Digest>>
+where
+    T: core::Chksumable,
+{
+    inputs.into_iter().map(chksum).collect()
+}
+
+/// Computes the hashes of the given inputs in parallel, using the global [`rayon`] thread pool.
+///
+/// The results are returned in the same order as the inputs.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(paths: Vec<PathBuf>) -> Result<()> {
+/// for digest in md5::par_chksum_many(paths) {
+///     assert_eq!(
+///         digest?.to_hex_lowercase(),
+///         "5c71dbb287630d65ca93764c34d9aa0d"
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_chksum_many<T>(inputs: impl IntoIterator<Item = T>) -> Vec<Result<Digest>>
+where
+    T: core::Chksumable + Send,
+{
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+    let inputs: Vec<T> = inputs.into_iter().collect();
+    inputs.into_par_iter().map(chksum).collect()
+}
+
+/// Computes the hashes of the given inputs in parallel, using the given thread [`Pool`](pool::Pool).
+///
+/// The results are returned in the same order as the inputs.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+/// use md5::pool::Pool;
+/// use rayon::ThreadPoolBuilder;
+///
+/// # fn wrapper(paths: Vec<PathBuf>) -> Result<()> {
+/// let pool = Pool::from(
+///     ThreadPoolBuilder::new()
+///         .num_threads(2)
+///         .build()
+///         .expect("pool is built"),
+/// );
+/// for digest in md5::par_chksum_many_in(&pool, paths) {
+///     assert_eq!(
+///         digest?.to_hex_lowercase(),
+///         "5c71dbb287630d65ca93764c34d9aa0d"
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_chksum_many_in<T>(pool: &pool::Pool, inputs: impl IntoIterator<Item = T>) -> Vec<Result<Digest>>
+where
+    T: core::Chksumable + Send,
+{
+    let inputs: Vec<T> = inputs.into_iter().collect();
+    pool.install(|| par_chksum_many(inputs))
+}
+
+/// Computes the hash of the given path with the provided options.
+///
+/// Directories are traversed recursively and the contents of their files are hashed in the same order as by the [`chksum`] function, so both functions produce the same digest for default options.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let options = md5::Options::new();
+/// let digest = md5::chksum_with(path, &options)?;
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_with(path: impl AsRef<Path>, options: &Options) -> Result<Digest> {
+    chksum_with_report(path, options).map(Report::into_digest)
+}
+
+/// Computes the hash of the given path with the provided options, returning a [`Report`] with statistics of the calculation.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let options = md5::Options::new();
+/// let report = md5::chksum_with_report(path, &options)?;
+/// println!("buffer size: {}", report.statistics().buffer_size);
+/// assert_eq!(
+///     report.digest().to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_with_report(path: impl AsRef<Path>, options: &Options) -> Result<Report> {
+    walk::chksum(path.as_ref(), options)
+}
+
+/// Computes the hash of the given path with the provided options on the blocking thread pool of Tokio.
+///
+/// Reading and hashing run in [`spawn_blocking`](tokio::task::spawn_blocking), so hashing large files or trees does not keep a runtime worker busy and the reactor stays responsive. The digest is the same as calculated by the [`chksum_with`] function.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # async fn wrapper(path: &Path) -> Result<()> {
+/// let options = md5::Options::new();
+/// let digest = md5::async_chksum_with(path, &options).await?;
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_with(path: impl AsRef<Path>, options: &Options) -> Result<Digest> {
+    async_chksum_with_report(path, options).await.map(Report::into_digest)
+}
+
+/// Computes the hash of the given path with the provided options on the blocking thread pool of Tokio, returning a [`Report`] with statistics of the calculation.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # async fn wrapper(path: &Path) -> Result<()> {
+/// let options = md5::Options::new();
+/// let report = md5::async_chksum_with_report(path, &options).await?;
+/// assert_eq!(
+///     report.digest().to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_with_report(path: impl AsRef<Path>, options: &Options) -> Result<Report> {
+    let path = path.as_ref().to_path_buf();
+    let options = options.clone();
+    match tokio::task::spawn_blocking(move || walk::chksum(&path, &options)).await {
+        Ok(report) => report,
+        Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+        Err(error) => Err(std::io::Error::other(error).into()),
+    }
+}
+
+/// Finds groups of identical files under the given path.
+///
+/// Only files of equal size are hashed, with the provided options. Groups are sorted by their first path and paths within a group follow the traversal order of the [`chksum`] function.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let options = md5::Options::new();
+/// for group in md5::find_duplicates(path, &options)? {
+///     println!("{group:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn find_duplicates(root: impl AsRef<Path>, options: &Options) -> Result<Vec<Vec<PathBuf>>> {
+    duplicates::find(root.as_ref(), options, |paths| {
+        paths.iter().map(|path| chksum_with(path, options)).collect()
+    })
+}
+
+/// Finds groups of identical files under the given path, hashing files in parallel using the global [`rayon`] thread pool.
+///
+/// The result is the same as of the [`find_duplicates`] function.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let options = md5::Options::new();
+/// for group in md5::par_find_duplicates(path, &options)? {
+///     println!("{group:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_find_duplicates(root: impl AsRef<Path>, options: &Options) -> Result<Vec<Vec<PathBuf>>> {
+    use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+    duplicates::find(root.as_ref(), options, |paths| {
+        paths.par_iter().map(|path| chksum_with(path, options)).collect()
+    })
+}
+
+/// Finds groups of identical files under the given path, hashing files in parallel using the given thread [`Pool`](pool::Pool).
+///
+/// The result is the same as of the [`find_duplicates`] function. The traversal runs on the calling thread.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+/// use md5::pool::Pool;
+/// use rayon::ThreadPoolBuilder;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let pool = Pool::from(
+///     ThreadPoolBuilder::new()
+///         .num_threads(2)
+///         .build()
+///         .expect("pool is built"),
+/// );
+/// let options = md5::Options::new();
+/// for group in md5::par_find_duplicates_in(&pool, path, &options)? {
+///     println!("{group:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_find_duplicates_in(
+    pool: &pool::Pool,
+    root: impl AsRef<Path>,
+    options: &Options,
+) -> Result<Vec<Vec<PathBuf>>> {
+    use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+    duplicates::find(root.as_ref(), options, |paths| {
+        pool.install(|| paths.par_iter().map(|path| chksum_with(path, options)).collect())
+    })
+}
+
+/// Compares two directory trees by the digests of their files.
+///
+/// Files are matched by their paths relative to the given roots and only files of equal size are hashed, with the provided options.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(a: &Path, b: &Path) -> Result<()> {
+/// let options = md5::Options::new();
+/// let diff = md5::compare_trees(a, b, &options)?;
+/// for path in diff.differs() {
+///     println!("{}", path.display());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn compare_trees(a: impl AsRef<Path>, b: impl AsRef<Path>, options: &Options) -> Result<TreeDiff> {
+    diff::compare(a.as_ref(), b.as_ref(), options)
+}
+
+/// Computes digests of every block of the given size read from the reader until EOF, together with the digest of the whole data.
+///
+/// The last block is shorter than the block size when the length of the data is not its multiple.
+///
+/// # Panics
+///
+/// Panics if the block size is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let file = File::open(path)?;
+/// let digests = md5::block_digests(file, 4096)?;
+/// for (index, digest) in digests.blocks().iter().enumerate() {
+///     println!("{index}: {digest}");
+/// }
+/// assert_eq!(
+///     digests.digest().to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn block_digests(reader: impl Read, block_size: usize) -> Result<BlockDigests> {
+    block::digests(reader, block_size)
+}
+
+/// Returns indices of blocks of the given size which differ between the data read from both readers until EOF.
+///
+/// Blocks are compared by their digests, and blocks past the end of the shorter data are reported as different.
+///
+/// # Panics
+///
+/// Panics if the block size is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(a: &Path, b: &Path) -> Result<()> {
+/// let indices = md5::diff_blocks(File::open(a)?, File::open(b)?, 4096)?;
+/// for index in indices {
+///     println!("block {index} differs");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn diff_blocks(a: impl Read, b: impl Read, block_size: usize) -> Result<Vec<usize>> {
+    let a = block::digests(a, block_size)?;
+    let b = block::digests(b, block_size)?;
+    Ok(a.diff(&b))
+}
+
+/// Computes the hash of the given file, reporting a [`ResumeState`](resume::ResumeState) every [`CHECKPOINT_INTERVAL`](resume::CHECKPOINT_INTERVAL) bytes.
+///
+/// The reported states can be persisted and passed to the [`resume`](fn@resume) function to continue after an interruption.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let digest = md5::hash_file_resumable(path, |state| {
+///     println!("hashed {} bytes", state.offset());
+/// })?;
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn hash_file_resumable(path: impl AsRef<Path>, checkpoint: impl FnMut(&resume::ResumeState)) -> Result<Digest> {
+    resume::hash(path.as_ref(), checkpoint)
+}
+
+/// Continues hashing of the given file from a state reported by the [`hash_file_resumable`] function.
+///
+/// Fails with [`Error::ResumeMismatch`] when the file was changed since the state was reported.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+/// use md5::resume::ResumeState;
+///
+/// # fn wrapper(path: &Path, state: ResumeState) -> Result<()> {
+/// let digest = md5::resume(path, &state, |_| {})?;
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn resume(
+    path: impl AsRef<Path>,
+    state: &resume::ResumeState,
+    checkpoint: impl FnMut(&resume::ResumeState),
+) -> Result<Digest> {
+    resume::resume(path.as_ref(), state, checkpoint)
+}
+
+/// Computes the hash of the data read from the given reader until EOF.
+///
+/// Any [`Read`] implementation is accepted, including combinators like [`Read::chain`] and [`Read::take`].
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+/// use std::io::Read;
+///
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let file = File::open(path)?;
+/// let digest = md5::chksum_reader(file.take(1024))?;
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_reader(reader: impl Read) -> Result<Digest> {
+    let mut hash = MD5::new();
+    let mut reader = BufReader::new(reader);
+    loop {
+        let buffer = reader.fill_buf()?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(length);
+    }
+    Ok(hash.digest())
+}
+
+/// Computes the hash of the data read from the given reader until EOF.
+///
+/// Any [`AsyncRead`] implementation is accepted, including combinators like [`AsyncReadExt::chain`](tokio::io::AsyncReadExt::chain) and [`AsyncReadExt::take`](tokio::io::AsyncReadExt::take).
+///
+/// Boxed trait objects are accepted as well, e.g. `Box<dyn AsyncRead + Unpin + Send>` or `Pin<Box<dyn AsyncRead + Send>>`. The returned future is [`Send`] when the reader is.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+/// use tokio::fs::File;
+/// use tokio::io::AsyncReadExt;
+///
+/// # async fn wrapper(path: &Path) -> Result<()> {
+/// let file = File::open(path).await?;
+/// let digest = md5::async_chksum_reader(file.take(1024)).await?;
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_reader(reader: impl AsyncRead + Unpin) -> Result<Digest> {
+    let mut hash = MD5::new();
+    let mut reader = AsyncBufReader::new(reader);
+    loop {
+        let buffer = reader.fill_buf().await?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(length);
+    }
+    Ok(hash.digest())
+}
+
+/// Spawns the given command and computes the hash of its standard output, returning it together with the exit status.
+///
+/// The standard output is piped and hashed while the process is running, so the output is never buffered as a whole. A non-zero exit code is not treated as an error, check [`ExitStatus::success`] to verify it.
+///
+/// # Example
+///
+/// ```rust
+/// use std::process::Command;
+///
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+///
+/// # fn wrapper() -> Result<()> {
+/// let mut command = Command::new("generator");
+/// let (digest, status) = md5::chksum_command(command.arg("--seed=1"))?;
+/// assert!(status.success());
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_command(command: &mut Command) -> Result<(Digest, ExitStatus)> {
+    let mut child = command.stdout(Stdio::piped()).spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    match chksum_reader(stdout) {
+        Ok(digest) => Ok((digest, child.wait()?)),
+        Err(error) => {
+            // don't leave the process running when its output cannot be read
+            let _ = child.kill();
+            let _ = child.wait();
+            Err(error)
+        },
+    }
+}
+
+/// Spawns the given command and computes the hash of its standard output, returning it together with the exit status.
+///
+/// The standard output is piped and hashed while the process is running, so the output is never buffered as a whole. A non-zero exit code is not treated as an error, check [`ExitStatus::success`] to verify it.
+///
+/// # Example
+///
+/// ```rust
+/// # use chksum_md5::Result;
+/// use chksum_md5 as md5;
+/// use tokio::process::Command;
+///
+/// # async fn wrapper() -> Result<()> {
+/// let mut command = Command::new("generator");
+/// let (digest, status) = md5::async_chksum_command(command.arg("--seed=1")).await?;
+/// assert!(status.success());
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "5c71dbb287630d65ca93764c34d9aa0d"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_command(command: &mut tokio::process::Command) -> Result<(Digest, ExitStatus)> {
+    let mut child = command.stdout(Stdio::piped()).spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    match async_chksum_reader(stdout).await {
+        Ok(digest) => Ok((digest, child.wait().await?)),
+        Err(error) => {
+            // don't leave the process running when its output cannot be read
+            let _ = child.kill().await;
+            Err(error)
+        },
+    }
+}
+
+/// The MD5 hash instance.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MD5 {
+    inner: hash::Update,
+    #[cfg(feature = "metrics")]
+    pending: metrics::Pending,
+}
+
+impl MD5 {
+    /// Calculates the hash digest of an input data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_md5::MD5;
+    ///
     /// let data = b"example data";
     /// let digest = MD5::hash(data);
     /// assert_eq!(
@@ -451,7 +2082,11 @@ impl MD5 {
     #[must_use]
     pub fn new() -> Self {
         let inner = hash::Update::new();
-        Self { inner }
+        Self {
+            inner,
+            #[cfg(feature = "metrics")]
+            pending: metrics::Pending::default(),
+        }
     }
 
     /// Updates the hash state with an input data.
@@ -475,6 +2110,8 @@ impl MD5 {
     where
         T: AsRef<[u8]>,
     {
+        #[cfg(feature = "metrics")]
+        self.pending.add(data.as_ref().len());
         self.inner.update(data);
     }
 
@@ -500,6 +2137,8 @@ impl MD5 {
 
     /// Produces the hash digest.
     ///
+    /// The hash is left untouched, so it can be updated further. The padding is applied to a copy of the four state words, the length and the pending bytes of the last partial block, without cloning the hash, which keeps intermediate digests of every chunk cheap.
+    ///
     /// # Example
     ///
     /// ```
@@ -538,6 +2177,7 @@ impl core::Hash for MD5 {
 }
 
 /// A hash digest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Digest(hash::Digest);
 
 impl Digest {
@@ -613,6 +2253,51 @@ impl Digest {
         let Self(inner) = self;
         inner.to_hex_uppercase()
     }
+
+    /// Returns the value of the `Content-MD5` header, the base64 representation of the digest defined by RFC 1864.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_md5 as md5;
+    ///
+    /// let digest = md5::hash(b"example data");
+    /// assert_eq!(digest.to_content_md5(), "XHHbsodjDWXKk3ZMNNmqDQ==");
+    /// ```
+    #[must_use]
+    pub fn to_content_md5(&self) -> String {
+        base64::encode(self.as_bytes())
+    }
+
+    /// Parses the value of the `Content-MD5` header.
+    ///
+    /// Surrounding whitespace is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidContentMd5`] if the value is not a base64 encoded digest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use chksum_md5::Result;
+    /// use chksum_md5 as md5;
+    ///
+    /// # fn wrapper() -> Result<()> {
+    /// let digest = md5::Digest::from_content_md5("XHHbsodjDWXKk3ZMNNmqDQ==")?;
+    /// assert_eq!(digest, md5::hash(b"example data"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_content_md5(value: &str) -> Result<Self> {
+        base64::decode(value.trim())
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(Self::new)
+            .ok_or_else(|| {
+                let value = value.to_owned();
+                Error::InvalidContentMd5 { value }
+            })
+    }
 }
 
 impl core::Digest for Digest {}
@@ -656,3 +2341,24 @@ impl From<hash::Digest> for Digest {
         Self(digest)
     }
 }
+
+impl From<Digest> for u128 {
+    /// Interprets the digest as a big-endian integer, like the hexadecimal representation.
+    fn from(digest: Digest) -> Self {
+        Self::from_be_bytes(digest.into_inner())
+    }
+}
+
+#[cfg(feature = "md-5")]
+impl From<md5::digest::Output<md5::Md5>> for Digest {
+    fn from(digest: md5::digest::Output<md5::Md5>) -> Self {
+        Self::new(digest.into())
+    }
+}
+
+#[cfg(feature = "md-5")]
+impl From<Digest> for md5::digest::Output<md5::Md5> {
+    fn from(digest: Digest) -> Self {
+        digest.into_inner().into()
+    }
+}
@@ -0,0 +1,302 @@
+//! The `chksum-md5` command, a `md5sum`-compatible interface to the library.
+//!
+//! Besides files, directories can be given: they are hashed as a whole by default, like the [`chksum`] function does, or file by file with the `--recursive` option.
+
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use chksum_md5::manifest::{self, CheckOptions, Entry, Manifest, Mode, Status};
+use chksum_md5::{chksum, chksum_with, Options};
+
+const USAGE: &str = "\
+Usage: chksum-md5 [OPTION]... [FILE]...
+Print or check MD5 checksums. With no FILE, or when FILE is -, read standard input.
+Directories are hashed as a whole, or file by file with --recursive.
+
+  -b, --binary          read in binary mode
+  -c, --check           read checksums from the FILEs and check them
+  -r, --recursive       list every file of directories
+      --tag             create a BSD-style checksum
+  -t, --text            read in text mode (default)
+  -z, --zero            end each output line with NUL, not newline,
+                          and disable file name escaping
+
+The following options are useful only when verifying checksums:
+      --ignore-missing  don't fail or report status for missing files
+      --quiet           don't print OK for each successfully verified file
+      --status          don't output anything, status code shows success
+      --strict          exit non-zero for improperly formatted checksum lines
+
+  -h, --help            display this help and exit
+  -V, --version         output version information and exit
+";
+
+/// The parsed command line.
+#[derive(Default)]
+struct Arguments {
+    binary: bool,
+    check: bool,
+    recursive: bool,
+    tag: bool,
+    zero: bool,
+    ignore_missing: bool,
+    quiet: bool,
+    status: bool,
+    strict: bool,
+    files: Vec<OsString>,
+}
+
+impl Arguments {
+    fn parse(arguments: impl IntoIterator<Item = OsString>) -> Result<Option<Self>, String> {
+        let mut parsed = Self::default();
+        let mut arguments = arguments.into_iter();
+        while let Some(argument) = arguments.next() {
+            let Some(option) = argument
+                .to_str()
+                .filter(|option| option.starts_with('-') && *option != "-")
+            else {
+                parsed.files.push(argument);
+                continue;
+            };
+            let flags: Vec<String> = if option == "--" {
+                parsed.files.extend(arguments.by_ref());
+                break;
+            } else if option.starts_with("--") {
+                vec![option.to_owned()]
+            } else {
+                option[1..].chars().map(|flag| format!("-{flag}")).collect()
+            };
+            for flag in flags {
+                match flag.as_str() {
+                    "-b" | "--binary" => parsed.binary = true,
+                    "-t" | "--text" => parsed.binary = false,
+                    "-c" | "--check" => parsed.check = true,
+                    "-r" | "--recursive" => parsed.recursive = true,
+                    "--tag" => parsed.tag = true,
+                    "-z" | "--zero" => parsed.zero = true,
+                    "--ignore-missing" => parsed.ignore_missing = true,
+                    "--quiet" => parsed.quiet = true,
+                    "--status" => parsed.status = true,
+                    "--strict" => parsed.strict = true,
+                    "-h" | "--help" => {
+                        print!("{USAGE}");
+                        return Ok(None);
+                    },
+                    "-V" | "--version" => {
+                        println!("chksum-md5 {}", env!("CARGO_PKG_VERSION"));
+                        return Ok(None);
+                    },
+                    _ => return Err(format!("unrecognized option '{flag}'")),
+                }
+            }
+        }
+        if parsed.files.is_empty() {
+            parsed.files.push(OsString::from("-"));
+        }
+        if parsed.check && (parsed.tag || parsed.binary || parsed.recursive) {
+            return Err(String::from(
+                "the --binary, --recursive and --tag options are meaningless when verifying checksums",
+            ));
+        }
+        Ok(Some(parsed))
+    }
+}
+
+fn main() -> ExitCode {
+    let arguments = match Arguments::parse(std::env::args_os().skip(1)) {
+        Ok(Some(arguments)) => arguments,
+        Ok(None) => return ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("chksum-md5: {error}\nTry 'chksum-md5 --help' for more information.");
+            return ExitCode::from(2);
+        },
+    };
+    let result = if arguments.check {
+        check(&arguments)
+    } else {
+        print(&arguments)
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("chksum-md5: {error}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// Prints the digests of the files, returning `false` if any of them cannot be read.
+fn print(arguments: &Arguments) -> io::Result<bool> {
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    let mode = if arguments.binary { Mode::Binary } else { Mode::Text };
+    let mut ok = true;
+    for file in &arguments.files {
+        let path = PathBuf::from(file);
+        match entries(&path, arguments.recursive) {
+            Ok(entries) => {
+                let manifest = entries.into_iter().collect::<Manifest>().with_mode(mode);
+                write(&mut stdout, &manifest, arguments)?;
+            },
+            Err(error) => {
+                stdout.flush()?;
+                eprintln!("chksum-md5: {error}");
+                ok = false;
+            },
+        }
+    }
+    stdout.flush()?;
+    Ok(ok)
+}
+
+/// Calculates the entries of the given file or directory.
+fn entries(path: &Path, recursive: bool) -> chksum_md5::Result<Vec<Entry>> {
+    if path == Path::new("-") {
+        let digest = chksum(io::stdin().lock())?;
+        return Ok(vec![Entry::new(digest, path)]);
+    }
+    if recursive && fs::metadata(path).is_ok_and(|metadata| metadata.is_dir()) {
+        let entries = manifest::create(path, &Options::new())?
+            .into_entries()
+            .into_iter()
+            .map(|entry| Entry::new(entry.digest, path.join(&entry.path)))
+            .collect();
+        return Ok(entries);
+    }
+    let digest = chksum_with(path, &Options::new())?;
+    Ok(vec![Entry::new(digest, path)])
+}
+
+/// Writes the entries in the format selected by the arguments.
+fn write(writer: &mut impl Write, manifest: &Manifest, arguments: &Arguments) -> io::Result<()> {
+    if !arguments.zero {
+        let result = if arguments.tag {
+            manifest.write_bsd(writer)
+        } else {
+            manifest.write_gnu(writer)
+        };
+        return result.map_err(io::Error::other);
+    }
+    // file names are written unescaped, like `md5sum --zero` does
+    for entry in manifest.entries() {
+        let digest = entry.digest;
+        if arguments.tag {
+            write!(writer, "MD5 (")?;
+            write_path(writer, &entry.path)?;
+            write!(writer, ") = {digest:x}\0")?;
+        } else {
+            let mark = if entry.mode == Mode::Binary { '*' } else { ' ' };
+            write!(writer, "{digest:x} {mark}")?;
+            write_path(writer, &entry.path)?;
+            writer.write_all(b"\0")?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the path as it is on Unix, where paths are arbitrary bytes, and lossily converted to UTF-8 elsewhere.
+fn write_path(writer: &mut impl Write, path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        writer.write_all(path.as_os_str().as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        write!(writer, "{}", path.display())
+    }
+}
+
+/// Checks the files listed in the checksum files, returning `false` if any check fails.
+fn check(arguments: &Arguments) -> io::Result<bool> {
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    let check_options = CheckOptions::new()
+        .strict(arguments.strict)
+        .ignore_missing(arguments.ignore_missing);
+    let mut ok = true;
+    for file in &arguments.files {
+        let path = PathBuf::from(file);
+        let reader: Box<dyn BufRead> = if path == Path::new("-") {
+            Box::new(io::stdin().lock())
+        } else {
+            match File::open(&path) {
+                Ok(file) => Box::new(BufReader::new(file)),
+                Err(error) => {
+                    eprintln!("chksum-md5: {}: {error}", path.display());
+                    ok = false;
+                    continue;
+                },
+            }
+        };
+        let report = match manifest::check(reader, ".", &Options::new(), &check_options) {
+            Ok(report) => report,
+            Err(error) => {
+                eprintln!("chksum-md5: {}: {error}", path.display());
+                ok = false;
+                continue;
+            },
+        };
+
+        let terminator = if arguments.zero { '\0' } else { '\n' };
+        let mut failed = 0;
+        let mut unreadable = 0;
+        for result in report.results() {
+            let name = result.path.display();
+            let outcome = match &result.status {
+                Status::Ok => "OK",
+                Status::Mismatch { .. } => {
+                    failed += 1;
+                    "FAILED"
+                },
+                Status::Missing | Status::Unreadable(_) => {
+                    unreadable += 1;
+                    "FAILED open or read"
+                },
+                _ => continue,
+            };
+            if !(arguments.status || arguments.quiet && result.is_ok()) {
+                write!(stdout, "{name}: {outcome}{terminator}")?;
+            }
+        }
+        stdout.flush()?;
+
+        let malformed = report.malformed().len();
+        if report.results().is_empty() {
+            eprintln!(
+                "chksum-md5: {}: no properly formatted checksum lines found",
+                path.display()
+            );
+            ok = false;
+            continue;
+        }
+        if !arguments.status {
+            warn(malformed, "line is", "lines are", "improperly formatted");
+            warn(unreadable, "listed file", "listed files", "could not be read");
+            warn(failed, "computed checksum", "computed checksums", "did NOT match");
+        }
+        if arguments.ignore_missing
+            && report
+                .results()
+                .iter()
+                .all(|result| matches!(result.status, Status::Skipped))
+        {
+            eprintln!("chksum-md5: {}: no file was verified", path.display());
+            ok = false;
+        }
+        ok &= report.is_ok();
+    }
+    Ok(ok)
+}
+
+/// Prints a warning about the given number of problems, unless there are none.
+fn warn(count: usize, singular: &str, plural: &str, problem: &str) {
+    match count {
+        0 => {},
+        1 => eprintln!("chksum-md5: WARNING: 1 {singular} {problem}"),
+        count => eprintln!("chksum-md5: WARNING: {count} {plural} {problem}"),
+    }
+}
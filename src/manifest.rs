@@ -0,0 +1,1150 @@
+//! Checksum files in the formats of the GNU `md5sum` and BSD `md5` utilities.
+//!
+//! Checksum files can be parsed with the [`Manifest::read_gnu`], [`Manifest::read_bsd`] and [`Manifest::read`] methods, or created for a tree of files with the [`create`] function and written with the [`Manifest::write_gnu`] and [`Manifest::write_bsd`] methods. Listed files can be checked with the [`verify`] function, or parsed and checked at once with the [`check`] function, like `md5sum --check` does, and two manifests can be compared with the [`Manifest::diff`] method.
+//!
+//! In the GNU format, every line holds the hexadecimal digest, a space, a mode marker (a space for text mode or `*` for binary mode) and the path of a file. In the BSD tag format, also written by `md5sum --tag`, every line has the form `MD5 (path) = digest`. In both formats, a line starting with a backslash has the path escaped, with `\\`, `\n` and `\r` standing for a backslash, a line feed and a carriage return. Empty lines and comments starting with `#` are ignored.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use chksum_md5::Result;
+//! use chksum_md5::manifest::{Manifest, Mode};
+//!
+//! # fn wrapper() -> Result<()> {
+//! let data = b"# release files\n5c71dbb287630d65ca93764c34d9aa0d *data.bin\n";
+//! let manifest = Manifest::read_gnu(&data[..])?;
+//! let [entry] = manifest.entries() else {
+//!     panic!("one entry is listed");
+//! };
+//! assert_eq!(
+//!     entry.digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! assert_eq!(entry.path.to_str(), Some("data.bin"));
+//! assert_eq!(entry.mode, Mode::Binary);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{fs, mem, result};
+
+use crate::error::{Context, Operation};
+use crate::{chksum_with, hash, walk, Digest, Error, Options, Result, MD5};
+
+/// The entries of a checksum file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    entries: Vec<Entry>,
+}
+
+impl Manifest {
+    /// Parses a checksum file in the GNU format, read from the given reader until EOF.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidLine`] error with the line number if any line is malformed.
+    pub fn read_gnu(reader: impl BufRead) -> Result<Self> {
+        read(reader, parse_gnu)
+    }
+
+    /// Parses a checksum file in the BSD tag format, read from the given reader until EOF.
+    ///
+    /// Entries of this format are always listed in the binary mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidLine`] error with the line number if any line is malformed.
+    pub fn read_bsd(reader: impl BufRead) -> Result<Self> {
+        read(reader, parse_bsd)
+    }
+
+    /// Parses a checksum file read from the given reader until EOF, detecting the format of every line, like `md5sum --check` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidLine`] error with the line number if any line is malformed.
+    pub fn read(reader: impl BufRead) -> Result<Self> {
+        read(reader, parse_any)
+    }
+
+    /// Writes the entries in the GNU format, byte-compatible with the output of the `md5sum` utility.
+    ///
+    /// Paths containing backslashes or line terminators are escaped, like `md5sum` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails, or if a path is not valid UTF-8 on platforms other than Unix.
+    pub fn write_gnu(&self, mut writer: impl Write) -> Result<()> {
+        for entry in &self.entries {
+            let path = path_to_bytes(&entry.path)?;
+            let escaped = needs_escape(path);
+            if escaped {
+                writer.write_all(b"\\")?;
+            }
+            writer.write_all(entry.digest.to_hex_lowercase().as_bytes())?;
+            writer.write_all(match entry.mode {
+                Mode::Text => b"  ",
+                Mode::Binary => b" *",
+            })?;
+            if escaped {
+                writer.write_all(&escape(path))?;
+            } else {
+                writer.write_all(path)?;
+            }
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the entries in the BSD tag format, byte-compatible with the output of the `md5sum --tag` and BSD `md5` utilities.
+    ///
+    /// Paths containing backslashes or line terminators are escaped, like `md5sum --tag` does. The mode of entries is not recorded in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails, or if a path is not valid UTF-8 on platforms other than Unix.
+    pub fn write_bsd(&self, mut writer: impl Write) -> Result<()> {
+        for entry in &self.entries {
+            let path = path_to_bytes(&entry.path)?;
+            let escaped = needs_escape(path);
+            if escaped {
+                writer.write_all(b"\\")?;
+            }
+            writer.write_all(BSD_TAG)?;
+            if escaped {
+                writer.write_all(&escape(path))?;
+            } else {
+                writer.write_all(path)?;
+            }
+            writer.write_all(b") = ")?;
+            writer.write_all(entry.digest.to_hex_lowercase().as_bytes())?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the entries as a JSON array of objects with the `path`, `size`, `digest` and `mtime` fields.
+    ///
+    /// The modification time is written in the RFC 3339 format in UTC, with a precision of seconds. Unknown sizes and modification times are written as `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails, or if a path is not valid UTF-8.
+    pub fn write_json(&self, mut writer: impl Write) -> Result<()> {
+        writer.write_all(b"[")?;
+        for (index, entry) in self.entries.iter().enumerate() {
+            let mut line = String::from(if index > 0 { ",\n  " } else { "\n  " });
+            line.push_str("{\"path\":");
+            json_string(path_to_str(&entry.path)?, &mut line);
+            line.push_str(",\"size\":");
+            match entry.size {
+                Some(size) => {
+                    let _ = write!(line, "{size}");
+                },
+                None => line.push_str("null"),
+            }
+            let _ = write!(line, ",\"digest\":\"{:x}\",\"mtime\":", entry.digest);
+            match entry.modified {
+                Some(modified) => json_string(&format_time(modified), &mut line),
+                None => line.push_str("null"),
+            }
+            line.push('}');
+            writer.write_all(line.as_bytes())?;
+        }
+        writer.write_all(if self.entries.is_empty() { b"]\n" } else { b"\n]\n" })?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the entries as CSV with the `path`, `size`, `digest` and `mtime` columns, preceded by a header.
+    ///
+    /// Fields are quoted as described in RFC 4180 when needed. The modification time is written in the RFC 3339 format in UTC, with a precision of seconds. Unknown sizes and modification times are written as empty fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails, or if a path is not valid UTF-8.
+    pub fn write_csv(&self, mut writer: impl Write) -> Result<()> {
+        writer.write_all(b"path,size,digest,mtime\n")?;
+        for entry in &self.entries {
+            let mut line = String::new();
+            csv_field(path_to_str(&entry.path)?, &mut line);
+            line.push(',');
+            if let Some(size) = entry.size {
+                let _ = write!(line, "{size}");
+            }
+            let _ = write!(line, ",{:x},", entry.digest);
+            if let Some(modified) = entry.modified {
+                line.push_str(&format_time(modified));
+            }
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Compares the manifest with a newer one, without accessing the filesystem.
+    ///
+    /// Entries are matched by their paths and compared by their digests, so changes of sizes or modification times alone are not reported. If a path is listed more than once, its last entry is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use chksum_md5::Result;
+    /// use chksum_md5::manifest::Manifest;
+    ///
+    /// # fn wrapper() -> Result<()> {
+    /// let yesterday = Manifest::read_gnu(&b"8d777f385d3dfec8815d20f7496026dc  a.txt\n"[..])?;
+    /// let today = Manifest::read_gnu(&b"5c71dbb287630d65ca93764c34d9aa0d  a.txt\n"[..])?;
+    /// let diff = yesterday.diff(&today);
+    /// assert_eq!(diff.changed().len(), 1);
+    /// assert!(diff.added().is_empty() && diff.removed().is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> ManifestDiff {
+        let entries = |manifest: &Self| -> BTreeMap<PathBuf, Entry> {
+            manifest
+                .entries
+                .iter()
+                .map(|entry| (entry.path.clone(), entry.clone()))
+                .collect()
+        };
+        let old = entries(self);
+        let mut new = entries(other);
+
+        let mut diff = ManifestDiff::default();
+        for (path, old) in old {
+            match new.remove(&path) {
+                None => diff.removed.push(old),
+                Some(new) if new.digest != old.digest => {
+                    diff.changed.push(Change {
+                        path,
+                        old: old.digest,
+                        new: new.digest,
+                    });
+                },
+                Some(_) => {},
+            }
+        }
+        diff.added = new.into_values().collect();
+        diff
+    }
+
+    /// Sets the mode of every entry, which is marked when writing in the GNU format.
+    ///
+    /// Only the markers are changed, the digests are kept.
+    #[must_use]
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.entries.iter_mut().for_each(|entry| entry.mode = mode);
+        self
+    }
+
+    /// Returns the entries, in the order of lines.
+    #[must_use]
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Consumes the manifest, returning the entries.
+    #[must_use]
+    pub fn into_entries(self) -> Vec<Entry> {
+        self.entries
+    }
+}
+
+impl FromIterator<Entry> for Manifest {
+    fn from_iter<T: IntoIterator<Item = Entry>>(entries: T) -> Self {
+        let entries = entries.into_iter().collect();
+        Self { entries }
+    }
+}
+
+/// The difference between two manifests produced by the [`Manifest::diff`] method.
+///
+/// All entries are sorted by their paths.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ManifestDiff {
+    added: Vec<Entry>,
+    removed: Vec<Entry>,
+    changed: Vec<Change>,
+}
+
+impl ManifestDiff {
+    /// Returns entries listed only in the newer manifest.
+    #[must_use]
+    pub fn added(&self) -> &[Entry] {
+        &self.added
+    }
+
+    /// Returns entries listed only in the older manifest.
+    #[must_use]
+    pub fn removed(&self) -> &[Entry] {
+        &self.removed
+    }
+
+    /// Returns paths listed in both manifests with different digests.
+    #[must_use]
+    pub fn changed(&self) -> &[Change] {
+        &self.changed
+    }
+
+    /// Returns `true` if both manifests list the same paths with identical digests.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A path listed in both manifests with different digests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Change {
+    /// The listed path.
+    pub path: PathBuf,
+    /// The digest listed in the older manifest.
+    pub old: Digest,
+    /// The digest listed in the newer manifest.
+    pub new: Digest,
+}
+
+/// Creates a manifest with the digests of all files of the given tree.
+///
+/// Paths are relative to the root and use `/` as the separator, and entries are listed in the deterministic traversal order of the [`chksum`](crate::chksum) function. If the root is a file, the manifest lists it by its file name.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5::{manifest, Options};
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let manifest = manifest::create(path, &Options::new())?;
+/// let mut output = Vec::new();
+/// manifest.write_gnu(&mut output)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn create(root: impl AsRef<Path>, options: &Options) -> Result<Manifest> {
+    let root = root.as_ref();
+    let entries = walk::files(root, options)?
+        .into_iter()
+        .map(|(path, _)| entry(root, &path, options))
+        .collect::<Result<_>>()?;
+    Ok(Manifest { entries })
+}
+
+/// Creates a manifest with the digests of all files of the given tree, hashing up to the given number of files at once on the blocking thread pool of Tokio.
+///
+/// The manifest is identical to the one created by the [`create`] function.
+///
+/// # Panics
+///
+/// Panics if the concurrency is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5::{manifest, Options};
+///
+/// # async fn wrapper(path: &Path) -> Result<()> {
+/// let manifest = manifest::async_create(path, &Options::new(), 8).await?;
+/// let mut output = Vec::new();
+/// manifest.write_gnu(&mut output)?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_create(root: impl AsRef<Path>, options: &Options, concurrency: usize) -> Result<Manifest> {
+    assert!(concurrency > 0, "concurrency must be non-zero");
+
+    let root = root.as_ref().to_path_buf();
+    let files = {
+        let root = root.clone();
+        let options = options.clone();
+        match tokio::task::spawn_blocking(move || walk::files(&root, &options)).await {
+            Ok(files) => files?,
+            Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+            Err(error) => return Err(io::Error::other(error).into()),
+        }
+    };
+    let tasks = files.into_iter().map(|(path, _)| {
+        let root = root.clone();
+        let options = options.clone();
+        move || entry(&root, &path, &options)
+    });
+    let entries = bounded(tasks.collect(), concurrency)
+        .await
+        .into_iter()
+        .collect::<Result<_>>()?;
+    Ok(Manifest { entries })
+}
+
+/// Creates the entry of the file found under the root.
+fn entry(root: &Path, path: &Path, options: &Options) -> Result<Entry> {
+    let digest = chksum_with(path, options)?;
+    let metadata = fs::metadata(path).context(path, Operation::Metadata)?;
+    let relative = match path.strip_prefix(root) {
+        Ok(relative) if relative != Path::new("") => relative,
+        _ => path.file_name().map_or(path, Path::new),
+    };
+    Ok(Entry {
+        digest,
+        path: portable(relative),
+        mode: Mode::Text,
+        size: Some(metadata.len()),
+        modified: metadata.modified().ok(),
+    })
+}
+
+/// Checks the files listed in the manifest, resolving their paths against the given root.
+///
+/// Every entry is checked even if some fail, and the results are collected in the order of entries. Listed paths have to be files, directories are reported as unreadable.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// use chksum_md5::manifest::{self, Manifest};
+/// use chksum_md5::Options;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let file = BufReader::new(File::open(path.join("MD5SUMS"))?);
+/// let manifest = Manifest::read(file)?;
+/// let report = manifest::verify(&manifest, path, &Options::new());
+/// for failure in report.failures() {
+///     eprintln!("{}: FAILED", failure.path.display());
+/// }
+/// assert!(report.is_ok());
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn verify(manifest: &Manifest, root: impl AsRef<Path>, options: &Options) -> VerificationReport {
+    verify_with(manifest, root, options, &CheckOptions::new())
+}
+
+/// Parses a checksum file and checks the listed files, resolving their paths against the given root, like `md5sum --check` does.
+///
+/// The format of every line is detected like in the [`Manifest::read`] method. Malformed lines are skipped and reported by [`VerificationReport::malformed`], unless the [`CheckOptions::strict`] option is enabled.
+///
+/// # Errors
+///
+/// Returns an error if reading fails, or an [`Error::InvalidLine`] error with the line number if any line is malformed in the strict mode.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// use chksum_md5::manifest::{self, CheckOptions};
+/// use chksum_md5::Options;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let file = BufReader::new(File::open(path.join("MD5SUMS"))?);
+/// let check_options = CheckOptions::new().strict(true).ignore_missing(true);
+/// let report = manifest::check(file, path, &Options::new(), &check_options)?;
+/// assert!(report.is_ok());
+/// # Ok(())
+/// # }
+/// ```
+pub fn check(
+    reader: impl BufRead,
+    root: impl AsRef<Path>,
+    options: &Options,
+    check_options: &CheckOptions,
+) -> Result<VerificationReport> {
+    let start = Instant::now();
+    let (manifest, malformed) = read_lines(reader, parse_any, check_options.strict)?;
+    let report = verify_with(&manifest, root, options, check_options);
+    Ok(VerificationReport {
+        malformed,
+        elapsed: start.elapsed(),
+        ..report
+    })
+}
+
+/// Checks the files listed in the manifest like the [`verify`] function, with the given policy for missing files.
+#[must_use]
+pub fn verify_with(
+    manifest: &Manifest,
+    root: impl AsRef<Path>,
+    options: &Options,
+    check_options: &CheckOptions,
+) -> VerificationReport {
+    let start = Instant::now();
+    let root = root.as_ref();
+    let options = options.clone().file_only(true);
+    let results = manifest
+        .entries
+        .iter()
+        .map(|entry| check_entry(root, entry, &options, check_options))
+        .collect();
+    VerificationReport {
+        results,
+        malformed: Vec::new(),
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Options for checking checksum files with the [`check`] and [`verify_with`] functions, mirroring the flags of `md5sum --check`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CheckOptions {
+    strict: bool,
+    ignore_missing: bool,
+    normalize_text: bool,
+}
+
+impl CheckOptions {
+    /// Creates new default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fails on malformed lines instead of skipping them, like `md5sum --strict` does.
+    #[must_use]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Skips entries of files which don't exist, instead of reporting them as missing, like `md5sum --ignore-missing` does.
+    ///
+    /// Skipped entries are counted by [`VerificationReport::skipped`] and don't fail the check.
+    #[must_use]
+    pub fn ignore_missing(mut self, ignore_missing: bool) -> Self {
+        self.ignore_missing = ignore_missing;
+        self
+    }
+
+    /// Hashes files of entries in the text mode with CRLF line endings converted to LF, like `md5sum` reads text files on Windows.
+    ///
+    /// With this option, checksum files written on Windows in the text mode can be checked against files with either line ending. Entries in the binary mode are always hashed as they are.
+    #[must_use]
+    pub fn normalize_text(mut self, normalize_text: bool) -> Self {
+        self.normalize_text = normalize_text;
+        self
+    }
+}
+
+/// Checks the files listed in the manifest, resolving their paths against the given root and hashing up to the given number of files at once on the blocking thread pool of Tokio.
+///
+/// The results are identical to the ones returned by the [`verify`] function.
+///
+/// # Panics
+///
+/// Panics if the concurrency is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_md5::Result;
+/// use chksum_md5::manifest::{self, Manifest};
+/// use chksum_md5::Options;
+///
+/// # async fn wrapper(path: &Path, manifest: &Manifest) -> Result<()> {
+/// let report = manifest::async_verify(manifest, path, &Options::new(), 8).await;
+/// assert!(report.is_ok());
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_verify(
+    manifest: &Manifest,
+    root: impl AsRef<Path>,
+    options: &Options,
+    concurrency: usize,
+) -> VerificationReport {
+    assert!(concurrency > 0, "concurrency must be non-zero");
+
+    let start = Instant::now();
+    let root = root.as_ref().to_path_buf();
+    let options = options.clone().file_only(true);
+    let tasks = manifest.entries.iter().cloned().map(|entry| {
+        let root = root.clone();
+        let options = options.clone();
+        move || check_entry(&root, &entry, &options, &CheckOptions::new())
+    });
+    let results = bounded(tasks.collect(), concurrency).await;
+    VerificationReport {
+        results,
+        malformed: Vec::new(),
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Checks the file of the entry, resolving its path against the root.
+fn check_entry(root: &Path, entry: &Entry, options: &Options, check_options: &CheckOptions) -> Verification {
+    let path = root.join(&entry.path);
+    let digest = if check_options.normalize_text && entry.mode == Mode::Text {
+        chksum_text(&path, options)
+    } else {
+        chksum_with(&path, options)
+    };
+    let status = match digest {
+        Ok(digest) if crate::verify::eq(&digest, &entry.digest) => Status::Ok,
+        Ok(digest) => {
+            Status::Mismatch {
+                expected: entry.digest,
+                actual: digest,
+            }
+        },
+        Err(Error::Path { ref source, .. }) if source.kind() == ErrorKind::NotFound => {
+            if check_options.ignore_missing {
+                Status::Skipped
+            } else {
+                Status::Missing
+            }
+        },
+        Err(error) => Status::Unreadable(error),
+    };
+    Verification {
+        path: entry.path.clone(),
+        status,
+    }
+}
+
+/// Calculates the digest of the file with CRLF line endings converted to LF.
+fn chksum_text(path: &Path, options: &Options) -> Result<Digest> {
+    let metadata = fs::metadata(path).context(path, Operation::Metadata)?;
+    if metadata.is_dir() {
+        let path = path.to_path_buf();
+        return Err(Error::IsADirectory { path });
+    }
+    let file = File::open(path).context(path, Operation::Open)?;
+    let mut reader = BufReader::with_capacity(options.buffer_size.resolve(&metadata), file);
+    let mut hash = MD5::new();
+    // a carriage return at the end of a buffer is held back until the next byte is known
+    let mut carriage_return = false;
+    loop {
+        let buffer = reader.fill_buf().context(path, Operation::Read)?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        if mem::take(&mut carriage_return) && buffer[0] != b'\n' {
+            hash.update(b"\r");
+        }
+        let mut start = 0;
+        for index in (0..length).filter(|&index| buffer[index] == b'\r') {
+            match buffer.get(index + 1) {
+                Some(b'\n') => {},
+                Some(_) => continue,
+                None => carriage_return = true,
+            }
+            hash.update(&buffer[start..index]);
+            start = index + 1;
+        }
+        hash.update(&buffer[start..]);
+        reader.consume(length);
+    }
+    if carriage_return {
+        hash.update(b"\r");
+    }
+    Ok(hash.digest())
+}
+
+/// Runs the tasks on the blocking thread pool of Tokio, up to the given number at once, returning their results in order.
+#[cfg(feature = "async-runtime-tokio")]
+async fn bounded<T, F>(tasks: Vec<F>, concurrency: usize) -> Vec<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let mut results: Vec<Option<T>> = (0..tasks.len()).map(|_| None).collect();
+    let mut tasks = tasks.into_iter().enumerate();
+    let mut running = tokio::task::JoinSet::new();
+    loop {
+        while running.len() < concurrency {
+            let Some((index, task)) = tasks.next() else {
+                break;
+            };
+            running.spawn_blocking(move || (index, task()));
+        }
+        match running.join_next().await {
+            None => break,
+            Some(Ok((index, result))) => results[index] = Some(result),
+            // tasks are cancelled only together with the set, so the error is a panic
+            Some(Err(error)) => std::panic::resume_unwind(error.into_panic()),
+        }
+    }
+    results
+        .into_iter()
+        .map(|result| result.expect("every task is finished"))
+        .collect()
+}
+
+/// The outcome of checking the files listed in a manifest with the [`verify`] function.
+#[derive(Debug)]
+pub struct VerificationReport {
+    results: Vec<Verification>,
+    malformed: Vec<usize>,
+    elapsed: Duration,
+}
+
+impl VerificationReport {
+    /// Returns `true` if every file which was not skipped matches its listed digest.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Returns the number of files which match their listed digests.
+    #[must_use]
+    pub fn ok(&self) -> usize {
+        self.count(|status| matches!(status, Status::Ok))
+    }
+
+    /// Returns the number of files which don't match their listed digests.
+    #[must_use]
+    pub fn mismatched(&self) -> usize {
+        self.count(|status| matches!(status, Status::Mismatch { .. }))
+    }
+
+    /// Returns the number of files which don't exist.
+    #[must_use]
+    pub fn missing(&self) -> usize {
+        self.count(|status| matches!(status, Status::Missing))
+    }
+
+    /// Returns the number of files which cannot be read.
+    #[must_use]
+    pub fn unreadable(&self) -> usize {
+        self.count(|status| matches!(status, Status::Unreadable(_)))
+    }
+
+    /// Returns the number of entries which were skipped without checking their files.
+    #[must_use]
+    pub fn skipped(&self) -> usize {
+        self.count(|status| matches!(status, Status::Skipped))
+    }
+
+    /// Returns the numbers of malformed lines which were skipped by the [`check`] function.
+    #[must_use]
+    pub fn malformed(&self) -> &[usize] {
+        &self.malformed
+    }
+
+    /// Returns the time spent checking the files.
+    #[must_use]
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the results of all entries, in the order of the manifest.
+    #[must_use]
+    pub fn results(&self) -> &[Verification] {
+        &self.results
+    }
+
+    /// Returns an iterator over the results of entries which failed the check.
+    pub fn failures(&self) -> impl Iterator<Item = &Verification> {
+        self.results
+            .iter()
+            .filter(|result| !matches!(result.status, Status::Ok | Status::Skipped))
+    }
+
+    /// Consumes the report, returning the results of all entries.
+    #[must_use]
+    pub fn into_results(self) -> Vec<Verification> {
+        self.results
+    }
+
+    fn count(&self, predicate: impl Fn(&Status) -> bool) -> usize {
+        self.results.iter().filter(|result| predicate(&result.status)).count()
+    }
+}
+
+/// The result of checking a single entry of a manifest with the [`verify`] function.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Verification {
+    /// The path of the entry, as listed in the manifest.
+    pub path: PathBuf,
+    /// The outcome of the check.
+    pub status: Status,
+}
+
+impl Verification {
+    /// Returns `true` if the file matches the listed digest.
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        matches!(self.status, Status::Ok)
+    }
+}
+
+/// The outcome of checking a single file.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Status {
+    /// The file matches the listed digest.
+    Ok,
+    /// The file doesn't match the listed digest.
+    Mismatch {
+        /// The listed digest.
+        expected: Digest,
+        /// The digest of the file.
+        actual: Digest,
+    },
+    /// The file doesn't exist.
+    Missing,
+    /// The file cannot be read.
+    Unreadable(Error),
+    /// The file doesn't exist and was skipped by the [`CheckOptions::ignore_missing`] option.
+    Skipped,
+}
+
+/// A single entry of a checksum file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Entry {
+    /// The digest of the file.
+    pub digest: Digest,
+    /// The path of the file, as written in the checksum file.
+    pub path: PathBuf,
+    /// The mode in which the file was read.
+    pub mode: Mode,
+    /// The size of the file, known for manifests created with the [`create`] function.
+    pub size: Option<u64>,
+    /// The last modification time of the file, known for manifests created with the [`create`] function.
+    pub modified: Option<SystemTime>,
+}
+
+impl Entry {
+    /// Creates an entry in the text mode, without a size and a modification time.
+    #[must_use]
+    pub fn new(digest: Digest, path: impl Into<PathBuf>) -> Self {
+        Self {
+            digest,
+            path: path.into(),
+            mode: Mode::Text,
+            size: None,
+            modified: None,
+        }
+    }
+}
+
+/// The mode in which a file was read, marked in checksum files.
+///
+/// Both modes are identical on POSIX systems. Files of entries in the text mode can be hashed with CRLF line endings converted to LF with the [`CheckOptions::normalize_text`] option, for checksum files written on Windows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// The text mode, marked with a space.
+    #[default]
+    Text,
+    /// The binary mode, marked with `*`.
+    Binary,
+}
+
+/// The reason why a line of a checksum file is malformed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The line doesn't contain a valid hexadecimal digest.
+    #[error("invalid digest")]
+    InvalidDigest,
+    /// The digest isn't separated from the path properly.
+    #[error("invalid separator")]
+    InvalidSeparator,
+    /// The line in the BSD tag format doesn't start with the `MD5 (` tag.
+    #[error("invalid tag")]
+    InvalidTag,
+    /// The path is empty.
+    #[error("empty path")]
+    EmptyPath,
+    /// The escaped path contains an unknown escape sequence.
+    #[error("invalid escape sequence")]
+    InvalidEscape,
+    /// The path is not valid on this platform.
+    #[error("invalid path")]
+    InvalidPath,
+}
+
+/// The prefix of lines in the BSD tag format.
+const BSD_TAG: &[u8] = b"MD5 (";
+
+/// Parses all lines read from the reader with the given parser, skipping empty lines and comments.
+fn read<F>(reader: impl BufRead, parse: F) -> Result<Manifest>
+where
+    F: FnMut(&[u8]) -> result::Result<Entry, ParseError>,
+{
+    read_lines(reader, parse, true).map(|(manifest, _)| manifest)
+}
+
+/// Parses the lines of a checksum file, failing on malformed lines in the strict mode and returning their numbers otherwise.
+fn read_lines<F>(mut reader: impl BufRead, mut parse: F, strict: bool) -> Result<(Manifest, Vec<usize>)>
+where
+    F: FnMut(&[u8]) -> result::Result<Entry, ParseError>,
+{
+    let mut entries = Vec::new();
+    let mut malformed = Vec::new();
+    let mut buffer = Vec::new();
+    for number in 1.. {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer)? == 0 {
+            break;
+        }
+        let line = trim_newline(&buffer);
+        if line.is_empty() || line.starts_with(b"#") {
+            continue;
+        }
+        match parse(line) {
+            Ok(entry) => entries.push(entry),
+            Err(error) if strict => return Err(Error::InvalidLine { line: number, error }),
+            Err(_) => malformed.push(number),
+        }
+    }
+    Ok((Manifest { entries }, malformed))
+}
+
+/// Parses a single non-empty line, detecting its format.
+fn parse_any(line: &[u8]) -> result::Result<Entry, ParseError> {
+    if line.strip_prefix(b"\\").unwrap_or(line).starts_with(BSD_TAG) {
+        parse_bsd(line)
+    } else {
+        parse_gnu(line)
+    }
+}
+
+/// Removes the line terminator, either `\n` or `\r\n`.
+fn trim_newline(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// Parses a single non-empty line in the GNU format.
+fn parse_gnu(line: &[u8]) -> result::Result<Entry, ParseError> {
+    let (escaped, line) = match line.strip_prefix(b"\\") {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+    if line.len() < hash::digest::LENGTH_HEX {
+        return Err(ParseError::InvalidDigest);
+    }
+    let (digest, rest) = line.split_at(hash::digest::LENGTH_HEX);
+    let digest = parse_digest(digest)?;
+    let (mode, path) = match rest {
+        [b' ', b' ', path @ ..] => (Mode::Text, path),
+        [b' ', b'*', path @ ..] => (Mode::Binary, path),
+        _ => return Err(ParseError::InvalidSeparator),
+    };
+    if path.is_empty() {
+        return Err(ParseError::EmptyPath);
+    }
+    let path = if escaped { unescape(path)? } else { path.to_vec() };
+    let path = path_from_bytes(path)?;
+    Ok(Entry {
+        digest,
+        path,
+        mode,
+        size: None,
+        modified: None,
+    })
+}
+
+/// Parses a single non-empty line in the BSD tag format.
+fn parse_bsd(line: &[u8]) -> result::Result<Entry, ParseError> {
+    let (escaped, line) = match line.strip_prefix(b"\\") {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+    let line = line.strip_prefix(BSD_TAG).ok_or(ParseError::InvalidTag)?;
+    // the digest has a fixed length, so paths can contain the separator as well
+    let Some(split) = line.len().checked_sub(hash::digest::LENGTH_HEX) else {
+        return Err(ParseError::InvalidDigest);
+    };
+    let (path, digest) = line.split_at(split);
+    let digest = parse_digest(digest)?;
+    let path = path.strip_suffix(b") = ").ok_or(ParseError::InvalidSeparator)?;
+    if path.is_empty() {
+        return Err(ParseError::EmptyPath);
+    }
+    let path = if escaped { unescape(path)? } else { path.to_vec() };
+    let path = path_from_bytes(path)?;
+    Ok(Entry {
+        digest,
+        path,
+        mode: Mode::Binary,
+        size: None,
+        modified: None,
+    })
+}
+
+/// Parses a digest in the hexadecimal representation.
+fn parse_digest(digest: &[u8]) -> result::Result<Digest, ParseError> {
+    if !digest.iter().all(u8::is_ascii_hexdigit) {
+        return Err(ParseError::InvalidDigest);
+    }
+    let digest = std::str::from_utf8(digest).map_err(|_| ParseError::InvalidDigest)?;
+    let digest = hash::Digest::try_from(digest).map_err(|_| ParseError::InvalidDigest)?;
+    Ok(Digest::from(digest))
+}
+
+/// Resolves escape sequences of an escaped path.
+fn unescape(path: &[u8]) -> result::Result<Vec<u8>, ParseError> {
+    let mut unescaped = Vec::with_capacity(path.len());
+    let mut bytes = path.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte != b'\\' {
+            unescaped.push(byte);
+            continue;
+        }
+        let byte = match bytes.next() {
+            Some(b'\\') => b'\\',
+            Some(b'n') => b'\n',
+            Some(b'r') => b'\r',
+            _ => return Err(ParseError::InvalidEscape),
+        };
+        unescaped.push(byte);
+    }
+    Ok(unescaped)
+}
+
+/// Joins components of the relative path with `/` on every platform.
+fn portable(path: &Path) -> PathBuf {
+    let mut portable = PathBuf::new().into_os_string();
+    for component in path.components() {
+        if let Component::Normal(name) = component {
+            if !portable.is_empty() {
+                portable.push("/");
+            }
+            portable.push(name);
+        }
+    }
+    PathBuf::from(portable)
+}
+
+/// Checks whether the path has to be escaped.
+fn needs_escape(path: &[u8]) -> bool {
+    path.iter().any(|byte| matches!(byte, b'\\' | b'\n' | b'\r'))
+}
+
+/// Escapes backslashes and line terminators of a path.
+fn escape(path: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(path.len() + 2);
+    for &byte in path {
+        match byte {
+            b'\\' => escaped.extend_from_slice(b"\\\\"),
+            b'\n' => escaped.extend_from_slice(b"\\n"),
+            b'\r' => escaped.extend_from_slice(b"\\r"),
+            byte => escaped.push(byte),
+        }
+    }
+    escaped
+}
+
+/// Converts a path to raw bytes; paths have to be valid UTF-8 on platforms other than Unix.
+pub(crate) fn path_to_bytes(path: &Path) -> io::Result<&[u8]> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        Ok(path.as_os_str().as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        path.to_str()
+            .map(str::as_bytes)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "path is not valid UTF-8"))
+    }
+}
+
+/// Converts a path to a string, as required by the structured formats.
+fn path_to_str(path: &Path) -> io::Result<&str> {
+    path.to_str()
+        .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "path is not valid UTF-8"))
+}
+
+/// Appends the string quoted and escaped as a JSON string.
+fn json_string(string: &str, output: &mut String) {
+    output.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\0'..='\u{1f}' => {
+                let _ = write!(output, "\\u{:04x}", u32::from(character));
+            },
+            _ => output.push(character),
+        }
+    }
+    output.push('"');
+}
+
+/// Appends the field, quoted if it contains separators, quotes or line terminators.
+fn csv_field(field: &str, output: &mut String) {
+    if field.contains([',', '"', '\n', '\r']) {
+        output.push('"');
+        output.push_str(&field.replace('"', "\"\""));
+        output.push('"');
+    } else {
+        output.push_str(field);
+    }
+}
+
+/// Formats the time in the RFC 3339 format in UTC, truncated to seconds.
+fn format_time(time: SystemTime) -> String {
+    let seconds = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        // round towards the past, like for times after the epoch
+        Err(error) => -(error.duration().as_secs_f64().ceil() as i64),
+    };
+    let (days, seconds) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+    // the conversion of days to a civil date from Howard Hinnant's date algorithms
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Converts raw bytes to a path; paths have to be valid UTF-8 on platforms other than Unix.
+pub(crate) fn path_from_bytes(path: Vec<u8>) -> result::Result<PathBuf, ParseError> {
+    #[cfg(unix)]
+    {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        Ok(PathBuf::from(OsString::from_vec(path)))
+    }
+    #[cfg(not(unix))]
+    {
+        String::from_utf8(path)
+            .map(PathBuf::from)
+            .map_err(|_| ParseError::InvalidPath)
+    }
+}
@@ -0,0 +1,210 @@
+//! Merkle trees of MD5 digests over fixed-size chunks of data.
+//!
+//! The leaves of a [`MerkleTree`] are digests of consecutive chunks of data, and every node above them is the digest of up to `fanout` digests of its children. Leaves and nodes are hashed with distinct one-byte prefixes, so a node can never be mistaken for a leaf. Since chunks are hashed independently, leaves can be calculated in parallel, and a single chunk can be verified against the root digest with an inclusion [`Proof`], without reading the rest of the data.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::io::Cursor;
+//!
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//!
+//! # fn wrapper() -> Result<()> {
+//! let data = b"example data";
+//! let tree = md5::merkle::build(Cursor::new(data), 4, 2)?;
+//! let proof = tree.proof(1).expect("chunk exists");
+//! assert!(proof.verify(b"ple ", tree.root()));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::Read;
+
+#[cfg(feature = "rayon")]
+use crate::pool::Pool;
+use crate::{block, Digest, Result, MD5};
+
+/// The prefix of hashed leaves.
+const LEAF_PREFIX: u8 = 0x00;
+
+/// The prefix of hashed nodes.
+const NODE_PREFIX: u8 = 0x01;
+
+/// A Merkle tree of MD5 digests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+    chunk_size: usize,
+    fanout: usize,
+    levels: Vec<Vec<Digest>>,
+}
+
+impl MerkleTree {
+    /// Creates a tree from the digests of leaves calculated with the [`leaf`] function.
+    ///
+    /// An empty list of leaves is treated as a single empty chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chunk size is zero or the fanout is lower than two.
+    #[must_use]
+    pub fn from_leaves(leaves: Vec<Digest>, chunk_size: usize, fanout: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        assert!(fanout > 1, "fanout must be at least two");
+
+        let leaves = if leaves.is_empty() { vec![leaf(b"")] } else { leaves };
+        let mut levels = vec![leaves];
+        while let [.., level] = levels.as_slice() {
+            if level.len() == 1 {
+                break;
+            }
+            let level = level.chunks(fanout).map(node).collect();
+            levels.push(level);
+        }
+        Self {
+            chunk_size,
+            fanout,
+            levels,
+        }
+    }
+
+    /// Returns the root digest of the tree.
+    #[must_use]
+    pub fn root(&self) -> &Digest {
+        &self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Returns the digests of the leaves, in the order of chunks.
+    #[must_use]
+    pub fn leaves(&self) -> &[Digest] {
+        &self.levels[0]
+    }
+
+    /// Returns the size of the chunks.
+    #[must_use]
+    pub const fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the maximal number of children of a node.
+    #[must_use]
+    pub const fn fanout(&self) -> usize {
+        self.fanout
+    }
+
+    /// Returns the inclusion proof of the chunk with the given index, or `None` if there is no such chunk.
+    #[must_use]
+    pub fn proof(&self, index: usize) -> Option<Proof> {
+        if index >= self.leaves().len() {
+            return None;
+        }
+
+        let mut position = index;
+        let mut steps = Vec::with_capacity(self.levels.len() - 1);
+        for level in &self.levels[..self.levels.len() - 1] {
+            let start = position - position % self.fanout;
+            let end = (start + self.fanout).min(level.len());
+            let mut siblings = level[start..end].to_vec();
+            siblings.remove(position - start);
+            steps.push(Step {
+                position: position - start,
+                siblings,
+            });
+            position /= self.fanout;
+        }
+        Some(Proof { index, steps })
+    }
+}
+
+/// An inclusion proof of a single chunk in a [`MerkleTree`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Proof {
+    index: usize,
+    steps: Vec<Step>,
+}
+
+/// The siblings of a node on the path from a leaf to the root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step {
+    position: usize,
+    siblings: Vec<Digest>,
+}
+
+impl Proof {
+    /// Returns the index of the proved chunk.
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Checks whether the given chunk belongs to the tree with the given root digest.
+    #[must_use]
+    pub fn verify(&self, chunk: impl AsRef<[u8]>, root: &Digest) -> bool {
+        let digest = self.steps.iter().fold(leaf(chunk), |digest, step| {
+            let mut children = step.siblings.clone();
+            children.insert(step.position.min(children.len()), digest);
+            node(&children)
+        });
+        digest == *root
+    }
+}
+
+/// Computes the digest of a leaf of the given chunk.
+pub fn leaf(chunk: impl AsRef<[u8]>) -> Digest {
+    let mut hash = MD5::new();
+    hash.update([LEAF_PREFIX]);
+    hash.update(chunk);
+    hash.digest()
+}
+
+/// Computes the digest of a node with the given children.
+fn node(children: &[Digest]) -> Digest {
+    let mut hash = MD5::new();
+    hash.update([NODE_PREFIX]);
+    children.iter().for_each(|child| {
+        hash.update(child);
+    });
+    hash.digest()
+}
+
+/// Builds a tree over chunks of the given size read from the reader until EOF.
+///
+/// # Panics
+///
+/// Panics if the chunk size is zero or the fanout is lower than two.
+pub fn build(reader: impl Read, chunk_size: usize, fanout: usize) -> Result<MerkleTree> {
+    assert!(chunk_size > 0, "chunk size must be non-zero");
+    assert!(fanout > 1, "fanout must be at least two");
+
+    let mut leaves = Vec::new();
+    block::for_each(reader, chunk_size, |chunk| leaves.push(leaf(chunk)))?;
+    Ok(MerkleTree::from_leaves(leaves, chunk_size, fanout))
+}
+
+/// Builds a tree over chunks of the given size of the data, hashing chunks in parallel using the global [`rayon`] thread pool.
+///
+/// # Panics
+///
+/// Panics if the chunk size is zero or the fanout is lower than two.
+#[cfg(feature = "rayon")]
+#[must_use]
+pub fn par_build(data: &[u8], chunk_size: usize, fanout: usize) -> MerkleTree {
+    use rayon::iter::ParallelIterator;
+    use rayon::slice::ParallelSlice;
+
+    assert!(chunk_size > 0, "chunk size must be non-zero");
+
+    let leaves = data.par_chunks(chunk_size).map(leaf).collect();
+    MerkleTree::from_leaves(leaves, chunk_size, fanout)
+}
+
+/// Builds a tree over chunks of the given size of the data, hashing chunks in parallel using the given thread [`Pool`].
+///
+/// # Panics
+///
+/// Panics if the chunk size is zero or the fanout is lower than two.
+#[cfg(feature = "rayon")]
+#[must_use]
+pub fn par_build_in(pool: &Pool, data: &[u8], chunk_size: usize, fanout: usize) -> MerkleTree {
+    pool.install(|| par_build(data, chunk_size, fanout))
+}
@@ -0,0 +1,149 @@
+//! This module is optional and can be enabled using the `metrics` Cargo feature.
+//!
+//! Hashing operations record their metrics through the [`metrics`] facade, so any recorder installed by the application, such as the Prometheus exporter of the `metrics-exporter-prometheus` crate, exposes them without wrapping every call. Without a recorder, recording does nothing.
+//!
+//! | Name | Type | Description |
+//! | --- | --- | --- |
+//! | [`BYTES`] | counter | The number of bytes fed to the [`MD5`](crate::MD5) hash, by any function of the crate, recorded in batches of 1 MiB and when the hash is dropped. |
+//! | [`FILES`] | counter | The number of files read by the functions taking [`Options`](crate::Options), like [`chksum_with`](crate::chksum_with). |
+//! | [`THROUGHPUT`] | histogram | The rate of reading and hashing of these files, in bytes per second. |
+//! | [`ERRORS`] | counter | The number of errors returned by the [`chksum`](crate::chksum) and [`async_chksum`](crate::async_chksum) functions and the functions taking [`Options`](crate::Options), labeled by `kind`. |
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `metrics` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["metrics"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features metrics
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_md5 as md5;
+//!
+//! // after installing a recorder
+//! md5::metrics::describe();
+//! let digest = md5::chksum(b"example data");
+//! ```
+
+use std::time::Duration;
+
+use metrics::{counter, describe_counter, describe_histogram, histogram, Unit};
+
+use crate::Error;
+
+/// The name of the counter of hashed bytes.
+pub const BYTES: &str = "chksum_md5_bytes_total";
+
+/// The name of the counter of hashed files.
+pub const FILES: &str = "chksum_md5_files_total";
+
+/// The name of the histogram of the throughput of hashing files.
+pub const THROUGHPUT: &str = "chksum_md5_throughput_bytes_per_second";
+
+/// The name of the counter of errors, labeled by `kind`.
+pub const ERRORS: &str = "chksum_md5_errors_total";
+
+/// Describes the metrics to the installed recorder, providing their units and descriptions to exporters.
+pub fn describe() {
+    describe_counter!(BYTES, Unit::Bytes, "The number of bytes hashed with MD5.");
+    describe_counter!(FILES, Unit::Count, "The number of files hashed with MD5.");
+    describe_histogram!(
+        THROUGHPUT,
+        "The throughput of reading and hashing files with MD5, in bytes per second."
+    );
+    describe_counter!(ERRORS, Unit::Count, "The number of errors of MD5 hashing operations.");
+}
+
+/// The number of pending bytes after which they are recorded before the hash is dropped.
+const BATCH: u64 = 1024 * 1024;
+
+/// The bytes fed to a hash which are not recorded yet.
+///
+/// Looking up the counter on every update of the hash would slow down hashing of small chunks, so the bytes are recorded in batches and when the hash is dropped, mostly once per operation. Clones start without pending bytes, so bytes are recorded once, and the count doesn't affect the equality of hashes.
+#[derive(Debug, Default)]
+pub(crate) struct Pending(u64);
+
+impl Pending {
+    /// Adds bytes fed to the hash.
+    pub(crate) fn add(&mut self, length: usize) {
+        self.0 += length as u64;
+        if self.0 >= BATCH {
+            counter!(BYTES).increment(self.0);
+            self.0 = 0;
+        }
+    }
+}
+
+impl Clone for Pending {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl PartialEq for Pending {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Pending {}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        if self.0 > 0 {
+            counter!(BYTES).increment(self.0);
+        }
+    }
+}
+
+/// Records a file hashed in the given time.
+pub(crate) fn file(bytes: u64, elapsed: Duration) {
+    counter!(FILES).increment(1);
+    let seconds = elapsed.as_secs_f64();
+    // files read faster than the resolution of the clock have no meaningful rate
+    if seconds > 0.0 {
+        histogram!(THROUGHPUT).record(bytes as f64 / seconds);
+    }
+}
+
+/// Records the error of an operation.
+pub(crate) fn error(error: &Error) {
+    counter!(ERRORS, "kind" => kind(error)).increment(1);
+}
+
+/// Returns the label of the kind of the error.
+fn kind(error: &Error) -> &'static str {
+    match error {
+        Error::IsTerminal => "is_terminal",
+        Error::Io(_) | Error::Path { .. } => "io",
+        Error::IsADirectory { .. } => "is_a_directory",
+        Error::SameFile { .. } => "same_file",
+        Error::SpecialFile { .. } => "special_file",
+        Error::ResumeMismatch { .. } => "resume_mismatch",
+        Error::SymlinkCycle { .. } => "symlink_cycle",
+        Error::InvalidLine { .. } => "invalid_line",
+        Error::NotInSidecar { .. } => "not_in_sidecar",
+        Error::InvalidEtag { .. } => "invalid_etag",
+        Error::InvalidContentMd5 { .. } => "invalid_content_md5",
+        Error::InvalidSalt { .. } => "invalid_salt",
+        Error::InvalidPasswordHash { .. } => "invalid_password_hash",
+        Error::InvalidApopTimestamp { .. } => "invalid_apop_timestamp",
+        Error::InvalidRadiusPassword { .. } => "invalid_radius_password",
+        Error::InvalidKeyLength { .. } => "invalid_key_length",
+        Error::InvalidEapPacket => "invalid_eap_packet",
+        Error::FrameTooLong { .. } => "frame_too_long",
+        Error::FrameDigestMismatch => "frame_digest_mismatch",
+        Error::InvalidDigest(_) => "invalid_digest",
+        #[cfg(feature = "serde")]
+        Error::Serialize(_) => "serialize",
+    }
+}
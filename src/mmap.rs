@@ -0,0 +1,97 @@
+//! This module is optional and can be enabled using the `mmap` Cargo feature.
+//!
+//! The [`chksum`] function memory-maps files above a size threshold and hashes them in large windows, which avoids copying the data through a read buffer. Smaller files, as well as directories, are processed in the usual way.
+//!
+//! # Safety
+//!
+//! Memory-mapped data can change underneath the hash when another process modifies the file at the same time. Use this module only for files which are not modified during the calculation, otherwise the digest is unspecified.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `mmap` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["mmap"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features mmap
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let digest = md5::mmap::chksum(path)?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+#![allow(unsafe_code)]
+
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::MmapOptions;
+
+use crate::{Digest, Result, MD5};
+
+/// The default size above which files are memory-mapped.
+pub const THRESHOLD: u64 = 16 * 1024 * 1024;
+
+/// The size of a single mapped window.
+const WINDOW: u64 = 64 * 1024 * 1024;
+
+/// Computes the hash of the given path, memory-mapping files larger than [`THRESHOLD`].
+pub fn chksum(path: impl AsRef<Path>) -> Result<Digest> {
+    chksum_with_threshold(path, THRESHOLD)
+}
+
+/// Computes the hash of the given path, memory-mapping files larger than the given threshold.
+pub fn chksum_with_threshold(path: impl AsRef<Path>, threshold: u64) -> Result<Digest> {
+    let path = path.as_ref();
+    if path.metadata()?.is_dir() {
+        crate::chksum(path)
+    } else {
+        let file = File::open(path)?;
+        chksum_file_with_threshold(&file, threshold)
+    }
+}
+
+/// Computes the hash of the given file, memory-mapping it when it is larger than [`THRESHOLD`].
+pub fn chksum_file(file: &File) -> Result<Digest> {
+    chksum_file_with_threshold(file, THRESHOLD)
+}
+
+/// Computes the hash of the given file, memory-mapping it when it is larger than the given threshold.
+pub fn chksum_file_with_threshold(file: &File, threshold: u64) -> Result<Digest> {
+    let metadata = file.metadata()?;
+    if !metadata.is_file() || metadata.len() <= threshold {
+        return crate::chksum(file);
+    }
+
+    let length = metadata.len();
+    let mut hash = MD5::new();
+    let mut offset = 0;
+    while offset < length {
+        let size = WINDOW.min(length - offset);
+        // SAFETY: the mapping is read-only and the module documentation requires the file not to be modified during the calculation
+        let window = unsafe { MmapOptions::new().offset(offset).len(size as usize).map(file)? };
+        #[cfg(unix)]
+        window.advise(memmap2::Advice::Sequential)?;
+        hash.update(&window[..]);
+        offset += size;
+    }
+    Ok(hash.digest())
+}
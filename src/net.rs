@@ -0,0 +1,106 @@
+//! This module provides checksum calculation of network streams.
+//!
+//! The data is read from the stream until EOF, i.e. until the peer shuts down its writing half of the connection. An optional read timeout bounds the time spent waiting for every single read; when it elapses, the calculation fails with an I/O error ([`ErrorKind::WouldBlock`](std::io::ErrorKind::WouldBlock) or [`ErrorKind::TimedOut`](std::io::ErrorKind::TimedOut), depending on the platform).
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::net::TcpStream;
+//! use std::time::Duration;
+//!
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//!
+//! # fn wrapper() -> Result<()> {
+//! let stream = TcpStream::connect("127.0.0.1:8080")?;
+//! let digest = md5::net::chksum_tcp(&stream, Some(Duration::from_secs(5)))?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::net::TcpStream;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+#[cfg(feature = "async-runtime-tokio")]
+use std::{future::Future, io};
+
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::net::TcpStream as AsyncTcpStream;
+#[cfg(all(unix, feature = "async-runtime-tokio"))]
+use tokio::net::UnixStream as AsyncUnixStream;
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::time::timeout;
+
+#[cfg(feature = "async-runtime-tokio")]
+use crate::MD5;
+use crate::{chksum_reader, Digest, Result};
+
+/// Computes the hash of the data received from the given TCP stream until EOF.
+///
+/// The previous read timeout of the stream is restored afterwards.
+pub fn chksum_tcp(stream: &TcpStream, read_timeout: Option<Duration>) -> Result<Digest> {
+    let previous = stream.read_timeout()?;
+    stream.set_read_timeout(read_timeout)?;
+    let digest = chksum_reader(stream);
+    stream.set_read_timeout(previous)?;
+    digest
+}
+
+/// Computes the hash of the data received from the given Unix stream until EOF.
+///
+/// The previous read timeout of the stream is restored afterwards.
+#[cfg(unix)]
+pub fn chksum_unix(stream: &UnixStream, read_timeout: Option<Duration>) -> Result<Digest> {
+    let previous = stream.read_timeout()?;
+    stream.set_read_timeout(read_timeout)?;
+    let digest = chksum_reader(stream);
+    stream.set_read_timeout(previous)?;
+    digest
+}
+
+/// Computes the hash of the data received from the given TCP stream until EOF.
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_tcp(stream: &mut AsyncTcpStream, read_timeout: Option<Duration>) -> Result<Digest> {
+    async_chksum_with_timeout(stream, read_timeout).await
+}
+
+/// Computes the hash of the data received from the given Unix stream until EOF.
+#[cfg(all(unix, feature = "async-runtime-tokio"))]
+pub async fn async_chksum_unix(stream: &mut AsyncUnixStream, read_timeout: Option<Duration>) -> Result<Digest> {
+    async_chksum_with_timeout(stream, read_timeout).await
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+async fn async_chksum_with_timeout(stream: impl AsyncRead + Unpin, read_timeout: Option<Duration>) -> Result<Digest> {
+    let mut hash = MD5::new();
+    let mut reader = BufReader::new(stream);
+    loop {
+        let buffer = with_timeout(reader.fill_buf(), read_timeout).await?;
+        let length = buffer.len();
+        if length == 0 {
+            break;
+        }
+        hash.update(buffer);
+        reader.consume(length);
+    }
+    Ok(hash.digest())
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+async fn with_timeout<T>(future: impl Future<Output = io::Result<T>>, duration: Option<Duration>) -> io::Result<T> {
+    match duration {
+        Some(duration) => {
+            timeout(duration, future)
+                .await
+                .map_err(|_| io::Error::from(io::ErrorKind::TimedOut))?
+        },
+        None => future.await,
+    }
+}
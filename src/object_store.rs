@@ -0,0 +1,62 @@
+//! This module is optional and can be enabled using the `object-store` Cargo feature.
+//!
+//! The [`async_chksum`] function calculates the digest of an object stored in any [`ObjectStore`] implementation, like Amazon S3, Google Cloud Storage, Azure Blob Storage or the local filesystem, streaming the object through the hash via its streaming get API.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `object-store` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["object-store"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features object-store
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//! use object_store::path::Path;
+//! use object_store::ObjectStore;
+//!
+//! # async fn wrapper(store: &dyn ObjectStore) -> Result<()> {
+//! let location = Path::from("data/file.txt");
+//! let (length, digest) = md5::object_store::async_chksum(store, &location).await?;
+//! assert_eq!(length, 12);
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io;
+
+use futures_util::StreamExt;
+use object_store::path::Path;
+use object_store::ObjectStore;
+
+use crate::{Digest, Result, MD5};
+
+/// Computes the hash of the object at the given location.
+///
+/// Returns the number of received bytes together with the digest.
+pub async fn async_chksum(store: &(impl ObjectStore + ?Sized), location: &Path) -> Result<(u64, Digest)> {
+    let result = store.get(location).await.map_err(io::Error::other)?;
+    let mut stream = result.into_stream();
+    let mut hash = MD5::new();
+    let mut length = 0;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(io::Error::other)?;
+        hash.update(&chunk);
+        length += chunk.len() as u64;
+    }
+    Ok((length, hash.digest()))
+}
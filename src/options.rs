@@ -0,0 +1,313 @@
+//! Options for checksum calculation of paths.
+//!
+//! The [`Options`] struct configures how files and directories are processed by the [`chksum_with`](crate::chksum_with) function.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//! use md5::options::{BufferSize, Options};
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let options = Options::new().buffer_size(BufferSize::Fixed(64 * 1024));
+//! let digest = md5::chksum_with(path, &options)?;
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::Metadata;
+use std::sync::{PoisonError, RwLock};
+
+use crate::cache::DigestCache;
+#[cfg(feature = "indicatif")]
+use crate::progress::Progress;
+
+/// The smallest buffer used for reading files.
+pub const MIN_BUFFER_SIZE: usize = 4 * 1024;
+
+/// The largest buffer picked by the [`BufferSize::Adaptive`] strategy.
+pub const MAX_ADAPTIVE_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// The strategy used by default options, see the [`set_default_buffer_size`] function.
+static DEFAULT_BUFFER_SIZE: RwLock<BufferSize> = RwLock::new(BufferSize::Adaptive);
+
+/// Sets the process-wide strategy for choosing the read buffer size, used by options created afterwards.
+///
+/// Options created before the call keep their strategy, and [`Options::buffer_size`] overrides the default for a single calculation.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5::options::{self, BufferSize, Options};
+///
+/// options::set_default_buffer_size(BufferSize::Fixed(4 * 1024 * 1024));
+/// assert_eq!(
+///     Options::new(),
+///     Options::new().buffer_size(BufferSize::Fixed(4 * 1024 * 1024))
+/// );
+/// ```
+pub fn set_default_buffer_size(buffer_size: BufferSize) {
+    *DEFAULT_BUFFER_SIZE.write().unwrap_or_else(PoisonError::into_inner) = buffer_size;
+}
+
+/// Returns the process-wide strategy for choosing the read buffer size, [`BufferSize::Adaptive`] unless it was changed with the [`set_default_buffer_size`] function.
+#[must_use]
+pub fn default_buffer_size() -> BufferSize {
+    *DEFAULT_BUFFER_SIZE.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Options for checksum calculation of paths.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    pub(crate) buffer_size: BufferSize,
+    pub(crate) on_error: OnError,
+    pub(crate) dedup_hardlinks: bool,
+    pub(crate) file_only: bool,
+    pub(crate) special_files: SpecialFiles,
+    pub(crate) cache: Option<DigestCache>,
+    pub(crate) read_ahead: bool,
+    pub(crate) vectored_reads: bool,
+    #[cfg(feature = "sparse")]
+    pub(crate) sparse: bool,
+    #[cfg(feature = "unicode-normalization")]
+    pub(crate) normalize_unicode: bool,
+    #[cfg(feature = "indicatif")]
+    pub(crate) progress: Option<Progress>,
+    #[cfg(feature = "io-uring")]
+    pub(crate) io_uring: bool,
+    #[cfg(feature = "direct-io")]
+    pub(crate) direct_io: bool,
+}
+
+impl Options {
+    /// Creates new default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the strategy for choosing the read buffer size, instead of the process-wide [`default_buffer_size`].
+    ///
+    /// Large fixed buffers, like 4 MiB, can speed up reading from storage arrays with large stripes.
+    #[must_use]
+    pub fn buffer_size(mut self, buffer_size: BufferSize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// Sets the policy for I/O errors of entries encountered during directory traversal.
+    #[must_use]
+    pub fn on_error(mut self, on_error: OnError) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Enables reuse of digests of hard-linked files.
+    ///
+    /// With this option, the digest of a directory is calculated over the digests of its files instead of their contents, so a file which was already hashed through another hard link is not read again. The digest does not depend on which files are hard-linked, but it differs from the one calculated without this option. Hard links are detected by device and inode numbers on Unix platforms; on other platforms every file is read.
+    #[must_use]
+    pub fn dedup_hardlinks(mut self, dedup_hardlinks: bool) -> Self {
+        self.dedup_hardlinks = dedup_hardlinks;
+        self
+    }
+
+    /// Rejects directories given as the path to hash with [`Error::IsADirectory`](crate::Error::IsADirectory), instead of traversing them.
+    #[must_use]
+    pub fn file_only(mut self, file_only: bool) -> Self {
+        self.file_only = file_only;
+        self
+    }
+
+    /// Sets the policy for special files, like FIFOs, sockets and devices.
+    #[must_use]
+    pub fn special_files(mut self, special_files: SpecialFiles) -> Self {
+        self.special_files = special_files;
+        self
+    }
+
+    /// Sets the cache of file digests, which are reused for files whose size and modification time did not change.
+    ///
+    /// See the [`cache`](crate::cache) module for the cases in which the cache is consulted.
+    #[must_use]
+    pub fn cache(mut self, cache: DigestCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Enables reading of regular files larger than the read buffer on a separate thread.
+    ///
+    /// The next chunk of a file is read into a second buffer while the current one is hashed, which overlaps the latency of the disk with hashing of large files without the `rayon` feature. Where threads can't be spawned, files are read in the usual way.
+    #[must_use]
+    pub fn read_ahead(mut self, read_ahead: bool) -> Self {
+        self.read_ahead = read_ahead;
+        self
+    }
+
+    /// Enables vectored reads of regular files, each filling eight read buffers with a single system call.
+    ///
+    /// Fewer and larger reads reduce the overhead of system calls on devices with many I/O operations per second, at the cost of eight times the memory of the buffer size. On platforms without vectored reads, like Windows, each read fills only the first buffer.
+    #[must_use]
+    pub fn vectored_reads(mut self, vectored_reads: bool) -> Self {
+        self.vectored_reads = vectored_reads;
+        self
+    }
+
+    /// Enables detection of holes in sparse files.
+    ///
+    /// Holes are detected with `SEEK_HOLE` and `SEEK_DATA` on Linux, Android, macOS, iOS and FreeBSD, and fed to the hash as zeros without reading them from the disk. The digest is identical to the one calculated by reading the whole file. On other platforms, and on filesystems without hole detection, files are read in the usual way.
+    ///
+    /// This option requires the `sparse` Cargo feature.
+    #[cfg(feature = "sparse")]
+    #[must_use]
+    pub fn sparse(mut self, sparse: bool) -> Self {
+        self.sparse = sparse;
+        self
+    }
+
+    /// Enables Unicode NFC normalization of entry names before sorting.
+    ///
+    /// Entries of directories are sorted by the raw bytes of their names. With this option, names which are valid Unicode are normalized to the NFC form first, so trees stored with decomposed names (e.g. on macOS) are hashed in the same order as trees with precomposed names.
+    ///
+    /// This option requires the `unicode-normalization` Cargo feature.
+    #[cfg(feature = "unicode-normalization")]
+    #[must_use]
+    pub fn normalize_unicode(mut self, normalize_unicode: bool) -> Self {
+        self.normalize_unicode = normalize_unicode;
+        self
+    }
+
+    /// Sets the progress bars advanced by the bytes read from files, like an [`indicatif::ProgressBar`] or an [`indicatif::MultiProgress`].
+    ///
+    /// See the [`progress`](crate::progress) module for the way the progress is reported. Files reused from the cache or through hard links are not read, so they don't advance the bars.
+    ///
+    /// This option requires the `indicatif` Cargo feature.
+    #[cfg(feature = "indicatif")]
+    #[must_use]
+    pub fn progress(mut self, progress: impl Into<Progress>) -> Self {
+        self.progress = Some(progress.into());
+        self
+    }
+
+    /// Enables reading of regular files through `io_uring` on Linux.
+    ///
+    /// Reads of the following chunks of a file are queued in the kernel while the current chunk is hashed, which overlaps I/O with hashing; with the [`async_chksum_with`](crate::async_chksum_with) function, each file is read by a single blocking thread instead of many small reads of the thread pool. The buffers of the reads are registered with the kernel when the limit of locked memory allows it. Where `io_uring` is unavailable, e.g. on other platforms or when it is disabled by the kernel or a seccomp filter, files are read in the usual way.
+    ///
+    /// This option requires the `io-uring` Cargo feature.
+    #[cfg(feature = "io-uring")]
+    #[must_use]
+    pub fn io_uring(mut self, io_uring: bool) -> Self {
+        self.io_uring = io_uring;
+        self
+    }
+
+    /// Enables reading of regular files bypassing the page cache on Linux, with the `O_DIRECT` flag.
+    ///
+    /// Hashing files larger than the memory otherwise evicts the cached pages of other processes, like the ones of a database. Direct reads go straight from the device into a buffer aligned to 4 KiB, whose size is the buffer size rounded up to a multiple of 4 KiB. Where direct I/O is unsupported, e.g. on other platforms or filesystems without it, files are read in the usual way. The option takes precedence over the `sparse` and `io_uring` options.
+    ///
+    /// This option requires the `direct-io` Cargo feature.
+    #[cfg(feature = "direct-io")]
+    #[must_use]
+    pub fn direct_io(mut self, direct_io: bool) -> Self {
+        self.direct_io = direct_io;
+        self
+    }
+}
+
+/// A policy for I/O errors of entries encountered during directory traversal.
+///
+/// The policy applies only to entries below the given path; errors of the path itself always fail the calculation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnError {
+    /// Fails the whole calculation.
+    #[default]
+    Fail,
+    /// Skips the entry, counting it in [`Statistics::skipped`](crate::report::Statistics::skipped).
+    Skip,
+    /// Skips the entry, counting it and recording it in [`Report::skipped`](crate::Report::skipped).
+    Collect,
+}
+
+/// A policy for special files, like FIFOs, sockets and devices.
+///
+/// Special files are detected by their metadata before they are opened.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpecialFiles {
+    /// Reads special files until EOF, like regular files.
+    ///
+    /// Reading can block forever, e.g. for a FIFO without a writer, or never end, e.g. for `/dev/zero`.
+    #[default]
+    Read,
+    /// Reads at most the given number of bytes of special files.
+    ReadLimit(u64),
+    /// Skips special files, counting them in [`Statistics::special_files`](crate::report::Statistics::special_files).
+    Skip,
+    /// Fails with [`Error::SpecialFile`](crate::Error::SpecialFile).
+    Error,
+}
+
+/// A strategy for choosing the read buffer size.
+///
+/// The default strategy is the process-wide one returned by the [`default_buffer_size`] function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferSize {
+    /// Uses buffer of the given size for every file.
+    Fixed(usize),
+    /// Picks buffer size based on the file size and the filesystem block size.
+    ///
+    /// Tiny files are read with a single block-sized buffer, while large files use buffers up to [`MAX_ADAPTIVE_BUFFER_SIZE`].
+    Adaptive,
+    /// Starts with the [`Adaptive`](Self::Adaptive) size within the given bounds and adjusts it to the measured throughput.
+    ///
+    /// The throughput of the first reads of each file is compared while the buffer is doubled, or halved when a larger buffer doesn't read faster, and the size which read fastest is used for the rest of the file. This suits network filesystems, which benefit from large buffers, as well as local disks without tuning the size for every deployment.
+    Tuned {
+        /// The smallest buffer size.
+        min: usize,
+        /// The largest buffer size.
+        max: usize,
+    },
+}
+
+impl Default for BufferSize {
+    fn default() -> Self {
+        default_buffer_size()
+    }
+}
+
+impl BufferSize {
+    /// Returns the buffer size which is used for a file with the given metadata.
+    #[must_use]
+    pub fn resolve(&self, metadata: &Metadata) -> usize {
+        match *self {
+            Self::Fixed(size) => size.max(1),
+            Self::Adaptive => {
+                let block_size = block_size(metadata).max(MIN_BUFFER_SIZE);
+                let file_size = usize::try_from(metadata.len()).unwrap_or(usize::MAX);
+                let size = file_size.clamp(block_size, MAX_ADAPTIVE_BUFFER_SIZE.max(block_size));
+                // round up to the multiple of filesystem block size
+                size.div_ceil(block_size).saturating_mul(block_size)
+            },
+            Self::Tuned { min, max } => {
+                let min = min.max(1);
+                Self::Adaptive.resolve(metadata).clamp(min, max.max(min))
+            },
+        }
+    }
+}
+
+#[cfg(unix)]
+fn block_size(metadata: &Metadata) -> usize {
+    use std::os::unix::fs::MetadataExt;
+
+    usize::try_from(metadata.blksize()).unwrap_or(MIN_BUFFER_SIZE)
+}
+
+#[cfg(not(unix))]
+fn block_size(_: &Metadata) -> usize {
+    MIN_BUFFER_SIZE
+}
@@ -0,0 +1,103 @@
+//! This module is optional and can be enabled using the `rayon` Cargo feature.
+//!
+//! A [`Pool`] confines the parallel functions to threads provided by the caller instead of the global [`rayon`] thread pool, e.g. to run integrity scans on a few low-priority threads next to a latency-sensitive service. It wraps an existing [`ThreadPool`], or creates one whose worker threads are started by a closure, which can set their priority, affinity or name before running them.
+//!
+//! The [`par_chksum_many_in`](crate::par_chksum_many_in), [`par_find_duplicates_in`](crate::par_find_duplicates_in) and [`merkle::par_build_in`](crate::merkle::par_build_in) functions run in the given pool.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `rayon` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["rayon"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features rayon
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::PathBuf;
+//! # use chksum_md5::Result;
+//! use std::thread;
+//!
+//! use chksum_md5 as md5;
+//! use md5::pool::Pool;
+//!
+//! # fn wrapper(paths: Vec<PathBuf>) -> Result<()> {
+//! let pool = Pool::with_spawner(2, |index, run| {
+//!     thread::Builder::new()
+//!         .name(format!("scan-{index}"))
+//!         .spawn(run)
+//!         .map(|_| ())
+//! })
+//! .expect("threads are spawned");
+//! for digest in md5::par_chksum_many_in(&pool, paths) {
+//!     assert_eq!(
+//!         digest?.to_hex_lowercase(),
+//!         "5c71dbb287630d65ca93764c34d9aa0d"
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io;
+use std::sync::Arc;
+
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+
+/// A thread pool which runs the parallel functions, created from a [`ThreadPool`] or with a spawner of its threads.
+///
+/// Clones share the same threads.
+#[derive(Clone, Debug)]
+pub struct Pool {
+    pool: Arc<ThreadPool>,
+}
+
+impl Pool {
+    /// Creates a pool of the given number of threads, or of one thread per CPU if it is zero, whose threads are started by the spawner.
+    ///
+    /// The spawner is called with the index of every thread and the function which the thread has to run, e.g. on a thread with a lowered priority. The function returns once the pool is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spawner fails to start a thread.
+    pub fn with_spawner<F>(threads: usize, mut spawner: F) -> Result<Self, ThreadPoolBuildError>
+    where
+        F: FnMut(usize, Box<dyn FnOnce() + Send>) -> io::Result<()>,
+    {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .spawn_handler(|thread| spawner(thread.index(), Box::new(move || thread.run())))
+            .build()?;
+        Ok(Self::from(pool))
+    }
+
+    /// Runs the operation in the pool, so that the parallel iterators it uses are executed by the threads of the pool.
+    pub(crate) fn install<R, F>(&self, operation: F) -> R
+    where
+        R: Send,
+        F: FnOnce() -> R + Send,
+    {
+        self.pool.install(operation)
+    }
+}
+
+impl From<ThreadPool> for Pool {
+    fn from(pool: ThreadPool) -> Self {
+        let pool = Arc::new(pool);
+        Self { pool }
+    }
+}
+
+impl From<Arc<ThreadPool>> for Pool {
+    fn from(pool: Arc<ThreadPool>) -> Self {
+        Self { pool }
+    }
+}
@@ -0,0 +1,150 @@
+//! This module is optional and can be enabled using the `indicatif` Cargo feature.
+//!
+//! The [`Progress`] of hashing files and directories with the [`chksum_with`](crate::chksum_with) function is reported to [`indicatif`] progress bars set with the [`Options::progress`](crate::Options::progress) option. A single [`ProgressBar`] is advanced by the bytes read and shows the path of the file being read in its message, while a [`MultiProgress`] gets a bar of every file being read, removed once the file is hashed.
+//!
+//! The length of a single bar is left untouched, so set it to the total size of the files, e.g. from their metadata, to show the completion of the whole calculation.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `indicatif` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["indicatif"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features indicatif
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//! use indicatif::ProgressBar;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let bar = ProgressBar::new(path.metadata()?.len());
+//! let options = md5::Options::new().progress(bar.clone());
+//! let digest = md5::chksum_with(path, &options)?;
+//! bar.finish_with_message(format!("{digest:x}"));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Debug, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+/// The template of bars of files added to a [`MultiProgress`].
+const TEMPLATE: &str = "{wide_msg} {bar:40} {bytes}/{total_bytes}";
+
+/// A destination of the progress of hashing files, created from a [`ProgressBar`] or a [`MultiProgress`].
+///
+/// Clones report to the same bars; progress values are equal if they are clones of each other.
+#[derive(Clone)]
+pub struct Progress {
+    kind: Arc<Kind>,
+}
+
+enum Kind {
+    Bar(ProgressBar),
+    Multi { multi: MultiProgress, style: ProgressStyle },
+}
+
+impl Progress {
+    /// Sets the style of bars of files added to a [`MultiProgress`], which shows the path and the bytes read by default.
+    ///
+    /// The style is ignored when reporting to a single [`ProgressBar`].
+    #[must_use]
+    pub fn style(self, style: ProgressStyle) -> Self {
+        let kind = match &*self.kind {
+            Kind::Bar(bar) => Kind::Bar(bar.clone()),
+            Kind::Multi { multi, .. } => {
+                let multi = multi.clone();
+                Kind::Multi { multi, style }
+            },
+        };
+        let kind = Arc::new(kind);
+        Self { kind }
+    }
+
+    /// Starts reporting the progress of reading the file of the given length.
+    pub(crate) fn file(&self, path: &Path, length: u64) -> FileProgress {
+        let message = path.display().to_string();
+        match &*self.kind {
+            Kind::Bar(bar) => {
+                bar.set_message(message);
+                let bar = bar.clone();
+                FileProgress { bar, multi: None }
+            },
+            Kind::Multi { multi, style } => {
+                let bar = multi.add(ProgressBar::new(length).with_style(style.clone()).with_message(message));
+                let multi = Some(multi.clone());
+                FileProgress { bar, multi }
+            },
+        }
+    }
+}
+
+impl From<ProgressBar> for Progress {
+    fn from(bar: ProgressBar) -> Self {
+        let kind = Arc::new(Kind::Bar(bar));
+        Self { kind }
+    }
+}
+
+impl From<MultiProgress> for Progress {
+    fn from(multi: MultiProgress) -> Self {
+        let style = ProgressStyle::with_template(TEMPLATE).expect("template is valid");
+        let kind = Arc::new(Kind::Multi { multi, style });
+        Self { kind }
+    }
+}
+
+impl PartialEq for Progress {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.kind, &other.kind)
+    }
+}
+
+impl Eq for Progress {}
+
+impl Debug for Progress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_struct("Progress");
+        match &*self.kind {
+            Kind::Bar(bar) => f.field("bar", bar),
+            Kind::Multi { multi, .. } => f.field("multi", multi),
+        };
+        f.finish_non_exhaustive()
+    }
+}
+
+/// The progress of reading a single file, which removes the bar of the file from a [`MultiProgress`] when dropped.
+pub(crate) struct FileProgress {
+    bar: ProgressBar,
+    multi: Option<MultiProgress>,
+}
+
+impl FileProgress {
+    /// Advances the bar by the given number of bytes.
+    pub(crate) fn inc(&self, bytes: u64) {
+        self.bar.inc(bytes);
+    }
+}
+
+impl Drop for FileProgress {
+    fn drop(&mut self) {
+        if let Some(multi) = &self.multi {
+            self.bar.finish_and_clear();
+            multi.remove(&self.bar);
+        }
+    }
+}
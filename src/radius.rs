@@ -0,0 +1,105 @@
+//! The MD5-based transforms of the RADIUS protocol, as specified in [RFC 2865](https://www.rfc-editor.org/rfc/rfc2865).
+//!
+//! The [`response_authenticator`] function authenticates responses of the server to a request, and the [`hide_password`] and [`unhide_password`] functions obfuscate the `User-Password` attribute of requests with the shared secret.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use chksum_md5::Result;
+//! use chksum_md5::radius;
+//!
+//! # fn wrapper(request_authenticator: [u8; 16]) -> Result<()> {
+//! let hidden = radius::hide_password(b"arctangent", b"xyzzy5461", &request_authenticator)?;
+//! let password = radius::unhide_password(&hidden, b"xyzzy5461", &request_authenticator)?;
+//! assert_eq!(password, b"arctangent");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Error, Result, MD5};
+
+/// The length of authenticators and of the blocks of hidden passwords.
+const BLOCK_LENGTH: usize = 16;
+
+/// The maximum length of the `User-Password` attribute value.
+const MAX_PASSWORD_LENGTH: usize = 128;
+
+/// Calculates the Response Authenticator of a packet sent by the server, `MD5(Code+ID+Length+RequestAuth+Attributes+Secret)`.
+///
+/// The length is the length of the whole response packet, and the attributes are the encoded attributes of the response.
+#[must_use]
+pub fn response_authenticator(
+    code: u8,
+    id: u8,
+    length: u16,
+    request_authenticator: &[u8; 16],
+    attributes: &[u8],
+    secret: &[u8],
+) -> [u8; 16] {
+    let mut hash = MD5::new();
+    hash.update([code, id]);
+    hash.update(length.to_be_bytes());
+    hash.update(request_authenticator);
+    hash.update(attributes);
+    hash.update(secret);
+    hash.digest().into_inner()
+}
+
+/// Hides the password for the `User-Password` attribute of an `Access-Request`.
+///
+/// The password is padded with zeros to a multiple of 16 bytes, and every block is combined with the MD5 digest of the secret followed by the previous hidden block, starting with the Request Authenticator.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidRadiusPassword`] if the password is longer than 128 bytes.
+pub fn hide_password(password: &[u8], secret: &[u8], request_authenticator: &[u8; 16]) -> Result<Vec<u8>> {
+    if password.len() > MAX_PASSWORD_LENGTH {
+        let length = password.len();
+        return Err(Error::InvalidRadiusPassword { length });
+    }
+    let length = password.len().div_ceil(BLOCK_LENGTH).max(1) * BLOCK_LENGTH;
+    let mut hidden = password.to_vec();
+    hidden.resize(length, 0);
+    let mut previous = *request_authenticator;
+    for block in hidden.chunks_mut(BLOCK_LENGTH) {
+        xor(block, secret, &previous);
+        previous.copy_from_slice(block);
+    }
+    Ok(hidden)
+}
+
+/// Reveals the password of the `User-Password` attribute hidden by [`hide_password`], removing the padding zeros.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidRadiusPassword`] if the length of the attribute is not a multiple of 16 bytes between 16 and 128.
+pub fn unhide_password(hidden: &[u8], secret: &[u8], request_authenticator: &[u8; 16]) -> Result<Vec<u8>> {
+    if hidden.is_empty() || hidden.len() > MAX_PASSWORD_LENGTH || !hidden.len().is_multiple_of(BLOCK_LENGTH) {
+        let length = hidden.len();
+        return Err(Error::InvalidRadiusPassword { length });
+    }
+    let mut password = hidden.to_vec();
+    let mut previous = request_authenticator;
+    for (block, hidden) in password.chunks_mut(BLOCK_LENGTH).zip(hidden.chunks(BLOCK_LENGTH)) {
+        xor(block, secret, previous);
+        previous = hidden.try_into().expect("block has 16 bytes");
+    }
+    let length = password
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .map_or(0, |index| index + 1);
+    password.truncate(length);
+    Ok(password)
+}
+
+/// Combines the block with the digest of the secret followed by the previous block.
+fn xor(block: &mut [u8], secret: &[u8], previous: &[u8; 16]) {
+    let mut hash = MD5::new();
+    hash.update(secret);
+    hash.update(previous);
+    let digest = hash.digest();
+    block
+        .iter_mut()
+        .zip(digest.as_bytes())
+        .for_each(|(byte, key)| *byte ^= key);
+}
@@ -0,0 +1,56 @@
+use std::fs::File;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::{io, thread};
+
+use crate::block::read_full;
+
+/// The number of buffers, one being filled while the other one is hashed.
+const BUFFERS: usize = 2;
+
+/// A buffer and the length of its data, which is zero at the end of the file.
+type Chunk = io::Result<(Vec<u8>, usize)>;
+
+/// Hashes the file with reads on a separate thread, so reading the next chunk overlaps with hashing the current one.
+///
+/// Returns the number of bytes read, or `None` when the thread can't be spawned, in which case nothing is hashed.
+pub(crate) fn hash(file: &File, buffer_size: usize, update: &mut impl FnMut(&[u8])) -> io::Result<Option<u64>> {
+    let buffer_size = buffer_size.max(1);
+    let (filled_sender, filled) = mpsc::sync_channel(BUFFERS);
+    let (empty, empty_receiver) = mpsc::sync_channel(BUFFERS);
+    for _ in 0..BUFFERS {
+        empty
+            .send(vec![0; buffer_size])
+            .expect("channel has room for all buffers");
+    }
+    // the reader stops once the channels are dropped at the end of the closure, before the thread is joined
+    thread::scope(move |scope| {
+        let reader = thread::Builder::new().spawn_scoped(scope, move || read(file, &empty_receiver, &filled_sender));
+        if reader.is_err() {
+            return Ok(None);
+        }
+        let mut bytes = 0;
+        for chunk in &filled {
+            let (buffer, length) = chunk?;
+            if length == 0 {
+                break;
+            }
+            update(&buffer[..length]);
+            bytes += length as u64;
+            if empty.send(buffer).is_err() {
+                break;
+            }
+        }
+        Ok(Some(bytes))
+    })
+}
+
+/// Fills the empty buffers with the following chunks of the file until its end or an error.
+fn read(mut file: &File, empty: &Receiver<Vec<u8>>, filled: &SyncSender<Chunk>) {
+    for mut buffer in empty {
+        let result = read_full(&mut file, &mut buffer);
+        let end = !matches!(result, Ok(length) if length > 0);
+        if filled.send(result.map(|length| (buffer, length))).is_err() || end {
+            break;
+        }
+    }
+}
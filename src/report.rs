@@ -0,0 +1,77 @@
+//! Reports of checksum calculation of paths.
+
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::error::Operation;
+use crate::Digest;
+
+/// The outcome of a checksum calculation produced by the [`chksum_with_report`](crate::chksum_with_report) function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub(crate) digest: Digest,
+    pub(crate) statistics: Statistics,
+    pub(crate) skipped: Vec<Skipped>,
+}
+
+impl Report {
+    /// Returns the calculated digest.
+    #[must_use]
+    pub const fn digest(&self) -> &Digest {
+        &self.digest
+    }
+
+    /// Returns the statistics of the calculation.
+    #[must_use]
+    pub const fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Returns entries skipped due to I/O errors, recorded with the [`OnError::Collect`](crate::options::OnError::Collect) policy.
+    #[must_use]
+    pub fn skipped(&self) -> &[Skipped] {
+        &self.skipped
+    }
+
+    /// Consumes the report, returning the calculated digest.
+    #[must_use]
+    pub fn into_digest(self) -> Digest {
+        self.digest
+    }
+}
+
+/// Statistics of a checksum calculation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Statistics {
+    /// The number of processed files.
+    pub files: u64,
+    /// The number of processed directories.
+    pub directories: u64,
+    /// The number of hashed bytes.
+    pub bytes: u64,
+    /// The number of hashed bytes synthesized from holes of sparse files, without reading them from the disk.
+    pub sparse_bytes: u64,
+    /// The largest read buffer size chosen for a file.
+    pub buffer_size: usize,
+    /// The number of entries skipped due to I/O errors.
+    pub skipped: u64,
+    /// The number of special files skipped by the [`SpecialFiles::Skip`](crate::options::SpecialFiles::Skip) policy.
+    pub special_files: u64,
+    /// The number of files whose digests were reused from other hard links, without reading them.
+    pub hardlinks: u64,
+    /// The number of files whose digests were taken from the [`DigestCache`](crate::DigestCache), without reading them.
+    pub cached: u64,
+}
+
+/// An entry skipped due to an I/O error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Skipped {
+    /// The path of the entry.
+    pub path: PathBuf,
+    /// The operation which failed.
+    pub operation: Operation,
+    /// The kind of the I/O error.
+    pub kind: ErrorKind,
+}
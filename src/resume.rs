@@ -0,0 +1,233 @@
+//! Resumable hashing of large files.
+//!
+//! The [`hash_file_resumable`](crate::hash_file_resumable) function periodically reports a [`ResumeState`] with the position and the intermediate hash state, which can be persisted with [`ResumeState::to_bytes`]. After an interruption, the [`resume`](fn@crate::resume) function continues from the saved state instead of reading the file from the beginning. Along with the position, the state records a fingerprint of the file (its length, modification time and, on Unix, device and inode numbers), so a file changed in the meantime is rejected with [`Error::ResumeMismatch`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//! use md5::resume::ResumeState;
+//!
+//! # fn wrapper(path: &Path, saved: Option<Vec<u8>>) -> Result<()> {
+//! let save = |state: &ResumeState| {
+//!     let bytes = state.to_bytes();
+//!     // persist the bytes
+//! };
+//! let digest = match saved.as_deref().and_then(ResumeState::from_bytes) {
+//!     Some(state) => md5::resume(path, &state, save)?,
+//!     None => md5::hash_file_resumable(path, save)?,
+//! };
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{File, Metadata};
+use std::io::{Seek, SeekFrom};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use crate::block::read_full;
+use crate::compress::{compress, BLOCK_LENGTH, STATE};
+use crate::error::{Context, Operation};
+use crate::{Digest, Error, Result};
+
+/// The number of bytes between two reported states.
+pub const CHECKPOINT_INTERVAL: u64 = 64 * 1024 * 1024;
+
+/// The size of the read buffer, a multiple of the block size.
+const BUFFER_LENGTH: usize = 1024 * 1024;
+
+/// The version of the serialized state.
+const VERSION: u8 = 1;
+
+/// The length of the serialized state.
+const SERIALIZED_LENGTH: usize = 1 + 8 + 16 + 8 + 8 + 4 + 8 + 8;
+
+/// A checkpoint of a resumable file hashing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResumeState {
+    offset: u64,
+    state: [u32; 4],
+    fingerprint: Fingerprint,
+}
+
+impl ResumeState {
+    /// Returns the number of bytes hashed so far.
+    #[must_use]
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Serializes the state to bytes.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let Fingerprint {
+            length,
+            modified: (seconds, nanoseconds),
+            device,
+            inode,
+        } = self.fingerprint;
+        let mut bytes = Vec::with_capacity(SERIALIZED_LENGTH);
+        bytes.push(VERSION);
+        bytes.extend(self.offset.to_le_bytes());
+        self.state.iter().for_each(|word| bytes.extend(word.to_le_bytes()));
+        bytes.extend(length.to_le_bytes());
+        bytes.extend(seconds.to_le_bytes());
+        bytes.extend(nanoseconds.to_le_bytes());
+        bytes.extend(device.to_le_bytes());
+        bytes.extend(inode.to_le_bytes());
+        bytes
+    }
+
+    /// Deserializes the state from bytes produced by the [`ResumeState::to_bytes`] method.
+    ///
+    /// Returns `None` when the bytes are not a valid state.
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != SERIALIZED_LENGTH || bytes[0] != VERSION {
+            return None;
+        }
+        let mut bytes = &bytes[1..];
+        let offset = u64::from_le_bytes(split(&mut bytes));
+        let state = [(); 4].map(|()| u32::from_le_bytes(split(&mut bytes)));
+        let length = u64::from_le_bytes(split(&mut bytes));
+        let seconds = u64::from_le_bytes(split(&mut bytes));
+        let nanoseconds = u32::from_le_bytes(split(&mut bytes));
+        let device = u64::from_le_bytes(split(&mut bytes));
+        let inode = u64::from_le_bytes(split(&mut bytes));
+        if offset % BLOCK_LENGTH as u64 != 0 || offset > length {
+            return None;
+        }
+        let fingerprint = Fingerprint {
+            length,
+            modified: (seconds, nanoseconds),
+            device,
+            inode,
+        };
+        Some(Self {
+            offset,
+            state,
+            fingerprint,
+        })
+    }
+}
+
+/// Splits the array of the given length off the front of the bytes.
+fn split<const N: usize>(bytes: &mut &[u8]) -> [u8; N] {
+    let (head, tail) = bytes.split_at(N);
+    *bytes = tail;
+    head.try_into().expect("length of the bytes is checked")
+}
+
+/// The identity of a file, used to detect changes between interruption and resumption.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Fingerprint {
+    length: u64,
+    modified: (u64, u32),
+    device: u64,
+    inode: u64,
+}
+
+impl Fingerprint {
+    fn new(metadata: &Metadata) -> Self {
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or((0, 0), |modified| (modified.as_secs(), modified.subsec_nanos()));
+        #[cfg(unix)]
+        let (device, inode) = {
+            use std::os::unix::fs::MetadataExt;
+
+            (metadata.dev(), metadata.ino())
+        };
+        #[cfg(not(unix))]
+        let (device, inode) = (0, 0);
+        Self {
+            length: metadata.len(),
+            modified,
+            device,
+            inode,
+        }
+    }
+}
+
+/// Hashes the given file from the beginning, reporting a state every [`CHECKPOINT_INTERVAL`] bytes.
+pub(crate) fn hash(path: &Path, checkpoint: impl FnMut(&ResumeState)) -> Result<Digest> {
+    let file = File::open(path).context(path, Operation::Open)?;
+    let metadata = file.metadata().context(path, Operation::Metadata)?;
+    let state = ResumeState {
+        offset: 0,
+        state: STATE,
+        fingerprint: Fingerprint::new(&metadata),
+    };
+    run(path, file, state, checkpoint)
+}
+
+/// Continues hashing of the given file from the given state.
+pub(crate) fn resume(path: &Path, state: &ResumeState, checkpoint: impl FnMut(&ResumeState)) -> Result<Digest> {
+    let mut file = File::open(path).context(path, Operation::Open)?;
+    let metadata = file.metadata().context(path, Operation::Metadata)?;
+    if Fingerprint::new(&metadata) != state.fingerprint {
+        let path = path.to_path_buf();
+        return Err(Error::ResumeMismatch { path });
+    }
+    file.seek(SeekFrom::Start(state.offset))
+        .context(path, Operation::Read)?;
+    run(path, file, state.clone(), checkpoint)
+}
+
+fn run(
+    path: &Path,
+    mut file: File,
+    mut state: ResumeState,
+    mut checkpoint: impl FnMut(&ResumeState),
+) -> Result<Digest> {
+    let mut buffer = vec![0; BUFFER_LENGTH];
+    let mut next_checkpoint = state.offset + CHECKPOINT_INTERVAL;
+    loop {
+        let length = read_full(&mut file, &mut buffer).context(path, Operation::Read)?;
+        let blocks = length / BLOCK_LENGTH * BLOCK_LENGTH;
+        buffer[..blocks]
+            .chunks_exact(BLOCK_LENGTH)
+            .for_each(|block| compress(&mut state.state, block));
+        state.offset += blocks as u64;
+        if length < buffer.len() {
+            return Ok(finalize(state.state, state.offset, &buffer[blocks..length]));
+        }
+        if state.offset >= next_checkpoint {
+            checkpoint(&state);
+            next_checkpoint = state.offset + CHECKPOINT_INTERVAL;
+        }
+    }
+}
+
+/// Pads the remaining data and produces the digest.
+fn finalize(mut state: [u32; 4], offset: u64, remainder: &[u8]) -> Digest {
+    let length = (offset + remainder.len() as u64).wrapping_mul(8);
+    // the remainder is shorter than a block, so the padding takes one or two blocks
+    let mut tail = [0; BLOCK_LENGTH * 2];
+    tail[..remainder.len()].copy_from_slice(remainder);
+    tail[remainder.len()] = 0x80;
+    let end = if remainder.len() + 9 <= BLOCK_LENGTH {
+        BLOCK_LENGTH
+    } else {
+        BLOCK_LENGTH * 2
+    };
+    tail[end - 8..end].copy_from_slice(&length.to_le_bytes());
+    tail[..end]
+        .chunks_exact(BLOCK_LENGTH)
+        .for_each(|block| compress(&mut state, block));
+
+    let mut digest = [0; 16];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    Digest::new(digest)
+}
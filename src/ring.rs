@@ -0,0 +1,154 @@
+//! Assignment of keys to shards by their MD5 digests.
+//!
+//! The [`bucket`] function maps keys to a fixed number of buckets by the value of their digests, and the [`Ring`] places nodes on a consistent hash ring, where adding or removing a node moves only the keys of its neighbouring points. Both schemes are the ones used by existing MD5-based shard assignments, so keys keep their shards when a system migrates to this crate.
+//!
+//! The points of the ring are compatible with [`libketama`](https://github.com/RJ/ketama) and the memcached clients derived from it: each replica of a node is the digest of its name followed by a dash and the index of the replica, which gives four points as little-endian 32-bit integers, and keys are mapped to the first point not lower than the first four bytes of their digest.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_md5::ring::{self, Ring};
+//!
+//! assert_eq!(ring::bucket("user:42", 16), 14);
+//!
+//! let ring = Ring::builder()
+//!     .node("10.0.0.1:11211", "first")
+//!     .node("10.0.0.2:11211", "second")
+//!     .build();
+//! assert_eq!(ring.get("user:42"), Some(&"first"));
+//! ```
+
+/// The default number of replicas of each node, 160 points like in `libketama`.
+const REPLICAS: usize = 40;
+
+/// Returns the bucket of the key, the value of its digest as a big-endian integer modulo the number of buckets.
+///
+/// # Panics
+///
+/// Panics if the number of buckets is zero.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_md5::ring;
+///
+/// let bucket = ring::bucket("user:42", 16);
+/// assert!(bucket < 16);
+/// ```
+#[must_use]
+pub fn bucket(key: impl AsRef<[u8]>, buckets: usize) -> usize {
+    assert!(buckets > 0, "number of buckets must be non-zero");
+
+    let value = u128::from(crate::hash(key));
+    // the remainder is lower than the number of buckets
+    (value % buckets as u128) as usize
+}
+
+/// Returns the points of the digest, its four little-endian 32-bit integers.
+fn points_of(key: impl AsRef<[u8]>) -> [u32; 4] {
+    let digest = crate::hash(key).into_inner();
+    let mut points = [0; 4];
+    for (point, bytes) in points.iter_mut().zip(digest.chunks_exact(4)) {
+        *point = u32::from_le_bytes(bytes.try_into().expect("chunk has 4 bytes"));
+    }
+    points
+}
+
+/// A builder of a [`Ring`].
+#[derive(Clone, Debug)]
+pub struct RingBuilder<T> {
+    replicas: usize,
+    nodes: Vec<(String, T)>,
+}
+
+impl<T> RingBuilder<T> {
+    /// Creates a builder of an empty ring with 40 replicas of each node.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of replicas of each node, each giving four points on the ring.
+    #[must_use]
+    pub fn replicas(mut self, replicas: usize) -> Self {
+        self.replicas = replicas;
+        self
+    }
+
+    /// Adds the node with the given name, which determines its points on the ring.
+    #[must_use]
+    pub fn node(mut self, name: impl Into<String>, value: T) -> Self {
+        self.nodes.push((name.into(), value));
+        self
+    }
+
+    /// Builds the ring.
+    #[must_use]
+    pub fn build(self) -> Ring<T> {
+        let Self { replicas, nodes } = self;
+        let mut points = Vec::with_capacity(nodes.len() * replicas * 4);
+        let mut values = Vec::with_capacity(nodes.len());
+        for (index, (name, value)) in nodes.into_iter().enumerate() {
+            for replica in 0..replicas {
+                let replica = points_of(format!("{name}-{replica}"));
+                points.extend(replica.map(|point| (point, index)));
+            }
+            values.push(value);
+        }
+        // the order of equal points follows the order of nodes
+        points.sort_by_key(|&(point, _)| point);
+        Ring { points, values }
+    }
+}
+
+impl<T> Default for RingBuilder<T> {
+    fn default() -> Self {
+        Self {
+            replicas: REPLICAS,
+            nodes: Vec::new(),
+        }
+    }
+}
+
+/// A consistent hash ring of nodes, built with the [`RingBuilder`].
+#[derive(Clone, Debug)]
+pub struct Ring<T> {
+    points: Vec<(u32, usize)>,
+    values: Vec<T>,
+}
+
+impl<T> Ring<T> {
+    /// Creates a builder of a ring.
+    #[must_use]
+    pub fn builder() -> RingBuilder<T> {
+        RingBuilder::new()
+    }
+
+    /// Returns the node of the key, or [`None`] if the ring has no points.
+    #[must_use]
+    pub fn get(&self, key: impl AsRef<[u8]>) -> Option<&T> {
+        let [point, ..] = points_of(key);
+        let index = self.points.partition_point(|&(other, _)| other < point);
+        // keys after the last point belong to the first one
+        let (_, node) = self.points.get(index).or_else(|| self.points.first())?;
+        self.values.get(*node)
+    }
+
+    /// Returns the nodes of the ring in the order of their addition.
+    #[must_use]
+    pub fn nodes(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the number of nodes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the ring has no nodes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
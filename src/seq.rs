@@ -0,0 +1,74 @@
+//! Sequences of inputs hashed into a single digest.
+
+use chksum_core::Chksumable;
+
+use crate::{Result, MD5};
+
+/// A sequence of [`Chksumable`] inputs which are hashed one after another, like their concatenation.
+///
+/// The trait is implemented for tuples of up to eight inputs of any types, as well as for arrays, slices and vectors of inputs of the same type.
+pub trait ChksumableSeq {
+    /// Updates the given hash instance with the data from all inputs, in order.
+    fn chksum_seq_with(&mut self, hash: &mut MD5) -> Result<()>;
+}
+
+macro_rules! impl_chksumable_seq_for_tuple {
+    ($($t:ident),+) => {
+        impl<$($t),+> ChksumableSeq for ($($t,)+)
+        where
+            $($t: Chksumable),+
+        {
+            #[allow(non_snake_case)]
+            fn chksum_seq_with(&mut self, hash: &mut MD5) -> Result<()> {
+                let ($($t,)+) = self;
+                $($t.chksum_with(hash)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_chksumable_seq_for_tuple!(A);
+impl_chksumable_seq_for_tuple!(A, B);
+impl_chksumable_seq_for_tuple!(A, B, C);
+impl_chksumable_seq_for_tuple!(A, B, C, D);
+impl_chksumable_seq_for_tuple!(A, B, C, D, E);
+impl_chksumable_seq_for_tuple!(A, B, C, D, E, F);
+impl_chksumable_seq_for_tuple!(A, B, C, D, E, F, G);
+impl_chksumable_seq_for_tuple!(A, B, C, D, E, F, G, H);
+
+impl<T> ChksumableSeq for [T]
+where
+    T: Chksumable,
+{
+    fn chksum_seq_with(&mut self, hash: &mut MD5) -> Result<()> {
+        self.iter_mut().try_for_each(|input| Ok(input.chksum_with(hash)?))
+    }
+}
+
+impl<T, const N: usize> ChksumableSeq for [T; N]
+where
+    T: Chksumable,
+{
+    fn chksum_seq_with(&mut self, hash: &mut MD5) -> Result<()> {
+        self.as_mut_slice().chksum_seq_with(hash)
+    }
+}
+
+impl<T> ChksumableSeq for Vec<T>
+where
+    T: Chksumable,
+{
+    fn chksum_seq_with(&mut self, hash: &mut MD5) -> Result<()> {
+        self.as_mut_slice().chksum_seq_with(hash)
+    }
+}
+
+impl<T> ChksumableSeq for &mut T
+where
+    T: ChksumableSeq + ?Sized,
+{
+    fn chksum_seq_with(&mut self, hash: &mut MD5) -> Result<()> {
+        (**self).chksum_seq_with(hash)
+    }
+}
@@ -0,0 +1,148 @@
+//! Sidecar checksum files, stored next to the files they describe.
+//!
+//! The sidecar of a file has the `.md5` extension appended to its name, e.g. `release.tar.gz.md5` for `release.tar.gz`. It holds either a bare hexadecimal digest or a checksum file in any format accepted by the [`Manifest::read`] method.
+//!
+//! Files can be checked against their sidecars with the [`verify_sidecar`](crate::verify_sidecar) function, and written together with them by the [`write_with_sidecar`](crate::write_with_sidecar) function.
+
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{process, str};
+
+use crate::error::{Context, Operation};
+use crate::manifest::{Entry, Manifest, Mode};
+use crate::{chksum_with, copy, Digest, Error, ExpectedDigest, Options, Result};
+
+/// The outcome of checking a file against its sidecar with the [`verify_sidecar`](crate::verify_sidecar) function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SidecarVerification {
+    /// The digest listed in the sidecar.
+    pub expected: Digest,
+    /// The digest of the file.
+    pub actual: Digest,
+}
+
+impl SidecarVerification {
+    /// Returns `true` if the file matches the digest listed in the sidecar.
+    ///
+    /// Digests are compared in constant time.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        crate::verify::eq(&self.expected, &self.actual)
+    }
+}
+
+/// Returns the path of the sidecar of the given file.
+#[must_use]
+pub fn sidecar_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut sidecar = OsString::from(path.as_ref());
+    sidecar.push(".md5");
+    PathBuf::from(sidecar)
+}
+
+pub(crate) fn verify(path: &Path) -> Result<SidecarVerification> {
+    let sidecar = sidecar_path(path);
+    let contents = fs::read(&sidecar).context(&sidecar, Operation::Read)?;
+    let expected = expected(path, &contents)?;
+    let actual = chksum_with(path, &Options::new().file_only(true))?;
+    Ok(SidecarVerification { expected, actual })
+}
+
+/// Parses the digest of the file listed in the contents of its sidecar.
+///
+/// Checksum files with multiple entries have to list the file by its name.
+fn expected(path: &Path, contents: &[u8]) -> Result<Digest> {
+    if let Ok(contents) = str::from_utf8(contents) {
+        let digest = contents.trim();
+        if !digest.contains(char::is_whitespace) {
+            return digest.to_digest();
+        }
+    }
+
+    let entries = Manifest::read(contents)?.into_entries();
+    let entry = match entries.as_slice() {
+        [entry] => Some(entry),
+        entries => {
+            entries
+                .iter()
+                .find(|entry| entry.path.file_name().is_some() && entry.path.file_name() == path.file_name())
+        },
+    };
+    entry.map(|entry| entry.digest).ok_or_else(|| {
+        let path = path.to_path_buf();
+        Error::NotInSidecar { path }
+    })
+}
+
+pub(crate) fn write(path: &Path, reader: impl Read) -> Result<Digest> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "path has no file name"))?;
+    let (_, digest) = write_atomically(path, |file| copy(reader, file))?;
+    let entry = Entry {
+        digest,
+        path: PathBuf::from(name),
+        mode: Mode::Text,
+        size: None,
+        modified: None,
+    };
+    let manifest: Manifest = [entry].into_iter().collect();
+    write_atomically(&sidecar_path(path), |file| manifest.write_gnu(file))?;
+    Ok(digest)
+}
+
+/// Writes the file through a temporary file in the same directory, which is synced and renamed over the given path.
+///
+/// The temporary file is removed if writing fails.
+pub(crate) fn write_atomically<T>(path: &Path, write: impl FnOnce(&mut BufWriter<File>) -> Result<T>) -> Result<T> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let directory = match path.parent() {
+        Some(parent) if parent != Path::new("") => parent,
+        _ => Path::new("."),
+    };
+    let mut name = OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(format!(
+        ".{}.{}.tmp",
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temporary = directory.join(name);
+
+    let file = File::options()
+        .write(true)
+        .create_new(true)
+        .open(&temporary)
+        .context(&temporary, Operation::Open)?;
+    let result = (|| -> Result<T> {
+        let mut writer = BufWriter::new(file);
+        let value = write(&mut writer)?;
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?
+            .sync_all()?;
+        fs::rename(&temporary, path)?;
+        Ok(value)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    let value = result?;
+    sync_directory(directory);
+    Ok(value)
+}
+
+/// Persists the renaming of a file by syncing its directory.
+///
+/// Errors are ignored, since some filesystems don't support syncing directories.
+#[cfg(unix)]
+fn sync_directory(directory: &Path) {
+    let _ = File::open(directory).and_then(|directory| directory.sync_all());
+}
+
+#[cfg(not(unix))]
+fn sync_directory(_: &Path) {}
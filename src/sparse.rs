@@ -0,0 +1,66 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+
+use rustix::fs::{seek, SeekFrom as SparseSeekFrom};
+use rustix::io::Errno;
+
+/// A buffer of zeros fed to the hash in place of holes.
+static ZEROS: [u8; 64 * 1024] = [0; 64 * 1024];
+
+/// Sizes of the data read from the disk and synthesized from holes.
+pub(crate) struct Sizes {
+    pub(crate) data: u64,
+    pub(crate) holes: u64,
+}
+
+/// Hashes the file by reading its data segments and synthesizing zeros for its holes.
+///
+/// Returns `None` when the filesystem doesn't support hole detection, in which case nothing is hashed.
+pub(crate) fn hash(
+    file: &mut File,
+    length: u64,
+    buffer_size: usize,
+    update: &mut impl FnMut(&[u8]),
+) -> io::Result<Option<Sizes>> {
+    let mut sizes = Sizes { data: 0, holes: 0 };
+    let mut position = 0;
+    while position < length {
+        let data = match seek(&*file, SparseSeekFrom::Data(position)) {
+            Ok(data) => data.min(length),
+            // there is no more data after the position, the rest of file is a hole
+            Err(Errno::NXIO) => length,
+            Err(Errno::INVAL | Errno::NOTSUP | Errno::OPNOTSUPP) if position == 0 => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        sizes.holes += zeros(data - position, update);
+        if data == length {
+            break;
+        }
+
+        let hole = seek(&*file, SparseSeekFrom::Hole(data))?.min(length);
+        file.seek(SeekFrom::Start(data))?;
+        let mut reader = BufReader::with_capacity(buffer_size, file.take(hole - data));
+        loop {
+            let buffer = reader.fill_buf()?;
+            let length = buffer.len();
+            if length == 0 {
+                break;
+            }
+            update(buffer);
+            sizes.data += length as u64;
+            reader.consume(length);
+        }
+        position = hole;
+    }
+    Ok(Some(sizes))
+}
+
+fn zeros(mut length: u64, update: &mut impl FnMut(&[u8])) -> u64 {
+    let total = length;
+    while length > 0 {
+        let size = length.min(ZEROS.len() as u64);
+        update(&ZEROS[..size as usize]);
+        length -= size;
+    }
+    total
+}
@@ -0,0 +1,423 @@
+//! This module is optional and can be enabled using the `tower` Cargo feature.
+//!
+//! The [`ContentMd5Layer`] middleware sets the `Content-MD5` header of responses, defined by [RFC 1864](https://www.rfc-editor.org/rfc/rfc1864), and can reject requests whose bodies don't match the header. It works with any [`tower`] service handling [`http`] requests, including `axum` routers via `Router::layer`.
+//!
+//! The header is sent before the body, so by default the whole response body is buffered to calculate it. With [`Mode::Trailer`], the body is streamed through the hash and the value is sent in a trailer instead, which requires HTTP/2 or chunked HTTP/1.1 responses.
+//!
+//! Bodies are buffered up to a size limit, 256 KiB by default like the payload limit of `actix-web` extractors. Responses exceeding the limit are streamed without the header, and requests exceeding it are rejected with `413 Payload Too Large`.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `tower` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["tower"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features tower
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::convert::Infallible;
+//!
+//! use bytes::Bytes;
+//! use chksum_md5::tower::ContentMd5Layer;
+//! use http::{Request, Response};
+//! use http_body_util::Full;
+//! use tower::{service_fn, Layer, ServiceExt};
+//!
+//! # async fn wrapper() -> Result<(), Infallible> {
+//! let service = ContentMd5Layer::new().layer(service_fn(|_: Request<Full<Bytes>>| {
+//!     async { Ok::<_, Infallible>(Response::new(Full::new(Bytes::from("example data")))) }
+//! }));
+//! let response = service.oneshot(Request::new(Full::default())).await?;
+//! assert_eq!(
+//!     response.headers()["content-md5"],
+//!     "XHHbsodjDWXKk3ZMNNmqDQ=="
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use http::header::{HeaderName, HeaderValue, TRAILER};
+use http::{HeaderMap, Request, Response, StatusCode};
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::BodyExt;
+use pin_project_lite::pin_project;
+use tower::{Layer, Service};
+
+use crate::{Digest, MD5};
+
+/// The name of the `Content-MD5` header.
+pub const CONTENT_MD5: HeaderName = HeaderName::from_static("content-md5");
+
+/// The default limit of buffered bodies, 256 KiB.
+const LIMIT: usize = 256 * 1024;
+
+/// The way of sending the `Content-MD5` value of responses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Buffers the whole body and sends the value in the header.
+    #[default]
+    Buffer,
+    /// Streams the body and sends the value in a trailer, announced by the `Trailer` header.
+    Trailer,
+}
+
+/// A [`Layer`] wrapping services with the [`ContentMd5`] middleware.
+#[derive(Clone, Copy, Debug)]
+pub struct ContentMd5Layer {
+    mode: Mode,
+    limit: usize,
+    validate_requests: bool,
+}
+
+impl ContentMd5Layer {
+    /// Creates a layer setting the header of responses in the [`Mode::Buffer`] mode, without validating requests.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the way of sending the value of responses.
+    #[must_use]
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the limit of the size of bodies buffered to calculate their digests.
+    ///
+    /// Responses buffered in the [`Mode::Buffer`] mode are sent without the header once they exceed the limit, streaming the rest of their bodies.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets whether bodies of requests with the `Content-MD5` header are validated.
+    ///
+    /// These bodies are buffered before calling the inner service, which receives them from memory; requests with a malformed header or a mismatching body are rejected with `400 Bad Request`, and requests with bodies exceeding the [`limit`](Self::limit) with `413 Payload Too Large`.
+    #[must_use]
+    pub fn validate_requests(mut self, validate_requests: bool) -> Self {
+        self.validate_requests = validate_requests;
+        self
+    }
+}
+
+impl Default for ContentMd5Layer {
+    fn default() -> Self {
+        Self {
+            mode: Mode::default(),
+            limit: LIMIT,
+            validate_requests: false,
+        }
+    }
+}
+
+impl<S> Layer<S> for ContentMd5Layer {
+    type Service = ContentMd5<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ContentMd5 { inner, layer: *self }
+    }
+}
+
+/// The middleware created by the [`ContentMd5Layer`].
+///
+/// Errors of the inner service are passed through, so the middleware is infallible if the inner service is. Responses without a body, like responses to `HEAD` requests, are passed as they are. Failures of reading bodies are turned into responses: `400 Bad Request` for requests and `500 Internal Server Error` for responses buffered in the [`Mode::Buffer`] mode.
+#[derive(Clone, Debug)]
+pub struct ContentMd5<S> {
+    inner: S,
+    layer: ContentMd5Layer,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ContentMd5<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    ReqBody: Body + From<Bytes> + Send + 'static,
+    ReqBody::Data: Send,
+    ResBody: Body + Send + 'static,
+    ResBody::Data: Send,
+{
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+    type Response = Response<ContentMd5Body<ResBody>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // the service driven to readiness handles the request, and its clone the following ones
+        let clone = self.inner.clone();
+        let mut inner = mem::replace(&mut self.inner, clone);
+        let ContentMd5Layer {
+            mode,
+            limit,
+            validate_requests,
+        } = self.layer;
+        Box::pin(async move {
+            let request = if validate_requests && request.headers().contains_key(CONTENT_MD5) {
+                match validate(request, limit).await {
+                    Ok(request) => request,
+                    Err(status) => return Ok(ContentMd5Body::status(status)),
+                }
+            } else {
+                request
+            };
+            let response = inner.call(request).await?;
+            if response.body().is_end_stream() {
+                return Ok(response.map(ContentMd5Body::stream));
+            }
+            let response = match mode {
+                Mode::Buffer => buffer(response, limit).await,
+                Mode::Trailer => trailer(response),
+            };
+            Ok(response)
+        })
+    }
+}
+
+/// Buffers the body of the request and checks it against the header, returning the status of the rejection if it doesn't match or exceeds the limit.
+async fn validate<B>(request: Request<B>, limit: usize) -> Result<Request<B>, StatusCode>
+where
+    B: Body + From<Bytes>,
+{
+    let expected = request
+        .headers()
+        .get(CONTENT_MD5)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Digest::from_content_md5(value).ok())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    let (parts, body) = request.into_parts();
+    let data = match read(body, limit).await {
+        Ok(Buffered::Complete { data, .. }) => data,
+        Ok(Buffered::Exceeded { .. }) => return Err(StatusCode::PAYLOAD_TOO_LARGE),
+        Err(_) => return Err(StatusCode::BAD_REQUEST),
+    };
+    if !crate::verify::eq(&crate::hash(&data), &expected) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(Request::from_parts(parts, B::from(data)))
+}
+
+/// Buffers the body of the response and sets the header, unless the body exceeds the limit.
+async fn buffer<B: Body>(response: Response<B>, limit: usize) -> Response<ContentMd5Body<B>> {
+    let (mut parts, body) = response.into_parts();
+    let (data, trailers) = match read(body, limit).await {
+        Ok(Buffered::Complete { data, trailers }) => (data, trailers),
+        Ok(Buffered::Exceeded { data, rest }) => {
+            // the data read so far is sent before the rest of the body
+            let body = ContentMd5Body {
+                kind: Kind::Exceeded {
+                    data: Some(data).filter(|data| !data.is_empty()),
+                    rest,
+                },
+            };
+            return Response::from_parts(parts, body);
+        },
+        Err(_) => return ContentMd5Body::status(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    parts.headers.insert(CONTENT_MD5, header_value(crate::hash(&data)));
+    let body = ContentMd5Body {
+        kind: Kind::Buffered {
+            data: Some(data).filter(|data| !data.is_empty()),
+            trailers,
+        },
+    };
+    Response::from_parts(parts, body)
+}
+
+/// A body read up to a limit.
+enum Buffered<B> {
+    /// The whole body.
+    Complete { data: Bytes, trailers: Option<HeaderMap> },
+    /// The data read until the body exceeded the limit, and the rest of the body.
+    Exceeded { data: Bytes, rest: Pin<Box<B>> },
+}
+
+/// Reads the body until its end or until it exceeds the limit.
+async fn read<B: Body>(body: B, limit: usize) -> Result<Buffered<B>, B::Error> {
+    let mut body = Box::pin(body);
+    let mut data = BytesMut::new();
+    if body.size_hint().lower() > limit as u64 {
+        let data = data.freeze();
+        return Ok(Buffered::Exceeded { data, rest: body });
+    }
+    let mut trailers: Option<HeaderMap> = None;
+    while let Some(frame) = body.frame().await {
+        match frame?.into_data() {
+            Ok(chunk) => {
+                data.put(chunk);
+                if data.len() > limit {
+                    let data = data.freeze();
+                    return Ok(Buffered::Exceeded { data, rest: body });
+                }
+            },
+            Err(frame) => {
+                if let Ok(frame) = frame.into_trailers() {
+                    trailers.get_or_insert_with(HeaderMap::new).extend(frame);
+                }
+            },
+        }
+    }
+    let data = data.freeze();
+    Ok(Buffered::Complete { data, trailers })
+}
+
+/// Announces the trailer and streams the body of the response through the hash.
+fn trailer<B: Body>(response: Response<B>) -> Response<ContentMd5Body<B>> {
+    let (mut parts, body) = response.into_parts();
+    parts.headers.remove(CONTENT_MD5);
+    parts.headers.append(TRAILER, HeaderValue::from_static("Content-MD5"));
+    let body = ContentMd5Body {
+        kind: Kind::Stream {
+            inner: body,
+            hash: Some(MD5::new()),
+        },
+    };
+    Response::from_parts(parts, body)
+}
+
+/// Returns the value of the header for the digest.
+fn header_value(digest: Digest) -> HeaderValue {
+    HeaderValue::from_str(&digest.to_content_md5()).expect("base64 is a valid header value")
+}
+
+pin_project! {
+    /// The body of responses of the [`ContentMd5`] middleware.
+    pub struct ContentMd5Body<B> {
+        #[pin]
+        kind: Kind<B>,
+    }
+}
+
+pin_project! {
+    #[project = KindProjection]
+    enum Kind<B> {
+        Stream {
+            #[pin]
+            inner: B,
+            hash: Option<MD5>,
+        },
+        Buffered {
+            data: Option<Bytes>,
+            trailers: Option<HeaderMap>,
+        },
+        Exceeded {
+            data: Option<Bytes>,
+            rest: Pin<Box<B>>,
+        },
+    }
+}
+
+impl<B> ContentMd5Body<B> {
+    /// Passes the body as it is.
+    fn stream(inner: B) -> Self {
+        let kind = Kind::Stream { inner, hash: None };
+        Self { kind }
+    }
+
+    /// Creates an empty response with the given status.
+    fn status(status: StatusCode) -> Response<Self> {
+        let kind = Kind::Buffered {
+            data: None,
+            trailers: None,
+        };
+        let mut response = Response::new(Self { kind });
+        *response.status_mut() = status;
+        response
+    }
+}
+
+impl<B: Body> Body for ContentMd5Body<B> {
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match self.project().kind.project() {
+            KindProjection::Stream { inner, hash } => {
+                let Some(frame) = ready!(inner.poll_frame(cx)) else {
+                    // the trailer is sent in its own frame if the body has no trailers
+                    let trailers = hash.take().map(|hash| {
+                        let mut trailers = HeaderMap::new();
+                        trailers.insert(CONTENT_MD5, header_value(hash.digest()));
+                        Ok(Frame::trailers(trailers))
+                    });
+                    return Poll::Ready(trailers);
+                };
+                let mut frame = match frame {
+                    Ok(frame) => frame.map_data(|mut data| data.copy_to_bytes(data.remaining())),
+                    Err(error) => return Poll::Ready(Some(Err(error))),
+                };
+                if let Some(data) = frame.data_ref() {
+                    if let Some(hash) = hash {
+                        hash.update(data);
+                    }
+                } else if let Some(trailers) = frame.trailers_mut() {
+                    if let Some(hash) = hash.take() {
+                        trailers.insert(CONTENT_MD5, header_value(hash.digest()));
+                    }
+                }
+                Poll::Ready(Some(Ok(frame)))
+            },
+            KindProjection::Buffered { data, trailers } => {
+                let frame = match data.take() {
+                    Some(data) => Some(Frame::data(data)),
+                    None => trailers.take().map(Frame::trailers),
+                };
+                Poll::Ready(frame.map(Ok))
+            },
+            KindProjection::Exceeded { data, rest } => {
+                if let Some(data) = data.take() {
+                    return Poll::Ready(Some(Ok(Frame::data(data))));
+                }
+                let frame = ready!(rest.as_mut().poll_frame(cx));
+                Poll::Ready(
+                    frame.map(|frame| {
+                        frame.map(|frame| frame.map_data(|mut data| data.copy_to_bytes(data.remaining())))
+                    }),
+                )
+            },
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.kind {
+            Kind::Stream { inner, hash } => inner.is_end_stream() && hash.is_none(),
+            Kind::Buffered { data, trailers } => data.is_none() && trailers.is_none(),
+            Kind::Exceeded { data, rest } => data.is_none() && rest.is_end_stream(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.kind {
+            Kind::Stream { inner, .. } => inner.size_hint(),
+            Kind::Buffered { data, .. } => SizeHint::with_exact(data.as_ref().map_or(0, |data| data.len() as u64)),
+            Kind::Exceeded { data, rest } => {
+                let length = data.as_ref().map_or(0, |data| data.len() as u64);
+                let hint = rest.size_hint();
+                let mut size_hint = SizeHint::new();
+                size_hint.set_lower(hint.lower() + length);
+                if let Some(upper) = hint.upper() {
+                    size_hint.set_upper(upper + length);
+                }
+                size_hint
+            },
+        }
+    }
+}
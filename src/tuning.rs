@@ -0,0 +1,118 @@
+use std::io::{self, Read};
+use std::time::Instant;
+
+use crate::block::read_full;
+
+/// The number of reads whose throughput is measured before the buffer size settles.
+const PROBES: usize = 8;
+
+/// The factor by which the throughput has to grow to keep a new buffer size.
+const GAIN: f64 = 1.1;
+
+/// The outcome of hashing with a tuned buffer.
+pub(crate) struct Tuned {
+    /// The number of bytes read.
+    pub(crate) bytes: u64,
+    /// The largest buffer size used.
+    pub(crate) buffer_size: usize,
+}
+
+/// Hashes the reader, doubling or halving the buffer within the bounds while the throughput of the first reads grows.
+pub(crate) fn hash(
+    mut reader: impl Read,
+    size: usize,
+    min: usize,
+    max: usize,
+    update: &mut impl FnMut(&[u8]),
+) -> io::Result<Tuned> {
+    let mut tuner = Tuner::new(size, min, max);
+    let mut size = tuner.best;
+    let mut buffer = vec![0; size];
+    let mut tuned = Tuned {
+        bytes: 0,
+        buffer_size: size,
+    };
+    loop {
+        if buffer.len() < size {
+            buffer.resize(size, 0);
+        }
+        let start = Instant::now();
+        let length = read_full(&mut reader, &mut buffer[..size])?;
+        update(&buffer[..length]);
+        tuned.bytes += length as u64;
+        tuned.buffer_size = tuned.buffer_size.max(size);
+        if length < size {
+            return Ok(tuned);
+        }
+        if tuner.phase != Phase::Settled {
+            let throughput = length as f64 / start.elapsed().as_secs_f64().max(1e-9);
+            size = tuner.next(size, throughput);
+        }
+    }
+}
+
+/// The direction in which the buffer size is changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Phase {
+    Growing,
+    Shrinking,
+    Settled,
+}
+
+/// A hill climbing of the buffer size, which keeps changing it in one direction while the throughput grows.
+struct Tuner {
+    min: usize,
+    max: usize,
+    phase: Phase,
+    best: usize,
+    throughput: Option<f64>,
+    probes: usize,
+}
+
+impl Tuner {
+    fn new(size: usize, min: usize, max: usize) -> Self {
+        let min = min.max(1);
+        let max = max.max(min);
+        Self {
+            min,
+            max,
+            phase: Phase::Growing,
+            best: size.clamp(min, max),
+            throughput: None,
+            probes: 0,
+        }
+    }
+
+    /// Records the throughput of a read with the given buffer size, returning the size of the next read.
+    fn next(&mut self, size: usize, throughput: f64) -> usize {
+        self.probes += 1;
+        if self.throughput.is_none_or(|best| throughput > best * GAIN) {
+            self.best = size;
+            self.throughput = Some(throughput);
+        } else if self.phase == Phase::Growing && self.probes == 2 {
+            // a larger buffer didn't pay off, so smaller ones are tried instead
+            self.phase = Phase::Shrinking;
+        } else {
+            self.phase = Phase::Settled;
+        }
+        let mut next = self.step();
+        if next == self.best && self.phase == Phase::Growing && self.probes == 1 {
+            self.phase = Phase::Shrinking;
+            next = self.step();
+        }
+        if next == self.best || self.probes >= PROBES {
+            self.phase = Phase::Settled;
+            return self.best;
+        }
+        next
+    }
+
+    /// Returns the size next to the best one in the current direction.
+    fn step(&self) -> usize {
+        match self.phase {
+            Phase::Growing => self.best.saturating_mul(2).min(self.max),
+            Phase::Shrinking => (self.best / 2).max(self.min),
+            Phase::Settled => self.best,
+        }
+    }
+}
@@ -0,0 +1,164 @@
+#![allow(unsafe_code)]
+
+use std::fs::File;
+use std::io::{self, ErrorKind};
+use std::mem;
+use std::os::unix::fs::FileExt;
+use std::os::unix::io::AsRawFd;
+
+use io_uring::{opcode, types, IoUring};
+
+/// The number of reads queued at once.
+const DEPTH: usize = 4;
+
+/// The largest length of a single read.
+const MAX_READ_LENGTH: usize = 1 << 30;
+
+/// Hashes the file with reads queued ahead of the hash, so reading the next chunks overlaps with hashing the current one.
+///
+/// Returns the number of bytes read, or `None` when `io_uring` is unavailable, e.g. disabled by the kernel or a seccomp filter, in which case nothing is hashed.
+pub(crate) fn hash(
+    file: &File,
+    length: u64,
+    buffer_size: usize,
+    update: &mut impl FnMut(&[u8]),
+) -> io::Result<Option<u64>> {
+    let ring = match IoUring::new(DEPTH as u32) {
+        Ok(ring) => ring,
+        Err(error) if matches!(error.kind(), ErrorKind::Unsupported | ErrorKind::PermissionDenied) => {
+            return Ok(None);
+        },
+        Err(error) => return Err(error),
+    };
+    let buffer_size = buffer_size.clamp(1, MAX_READ_LENGTH);
+    let mut reads = Reads {
+        ring,
+        buffers: (0..DEPTH).map(|_| vec![0; buffer_size]).collect(),
+        registered: false,
+        in_flight: 0,
+    };
+    reads.register();
+    let result = reads.hash(file, length, update);
+    if let Err(error) = reads.drain() {
+        // the kernel may still write to the buffers of unfinished reads
+        mem::forget(mem::take(&mut reads.buffers));
+        return Err(error);
+    }
+    let mut position = result?;
+
+    // the rest of the file is read after a short read, or if the file has grown
+    let buffer = &mut reads.buffers[0];
+    loop {
+        let read = match file.read_at(buffer, position) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        update(&buffer[..read]);
+        position += read as u64;
+    }
+    Ok(Some(position))
+}
+
+/// The reads of chunks of a file into a fixed set of buffers.
+struct Reads {
+    ring: IoUring,
+    buffers: Vec<Vec<u8>>,
+    registered: bool,
+    in_flight: usize,
+}
+
+impl Reads {
+    /// Registers the buffers with the kernel, which saves mapping them for every read.
+    ///
+    /// Unregistered buffers are used when the registration fails, e.g. because of the limit of locked memory.
+    fn register(&mut self) {
+        let iovecs: Vec<libc::iovec> = self
+            .buffers
+            .iter_mut()
+            .map(|buffer| {
+                libc::iovec {
+                    iov_base: buffer.as_mut_ptr().cast(),
+                    iov_len: buffer.len(),
+                }
+            })
+            .collect();
+        // SAFETY: the buffers are neither moved nor dropped until the ring is dropped
+        self.registered = unsafe { self.ring.submitter().register_buffers(&iovecs) }.is_ok();
+    }
+
+    /// Hashes the chunks of the file up to the given length in order, returning the position where the queued reads stopped.
+    fn hash(&mut self, file: &File, length: u64, update: &mut impl FnMut(&[u8])) -> io::Result<u64> {
+        let buffer_size = self.buffers[0].len() as u64;
+        let chunks = length.div_ceil(buffer_size);
+        let mut results: [Option<i32>; DEPTH] = [None; DEPTH];
+        let mut submitted = 0;
+        for chunk in 0..chunks {
+            while submitted < chunks && submitted < chunk + DEPTH as u64 {
+                self.submit(file, submitted, length)?;
+                submitted += 1;
+            }
+            let slot = (chunk % DEPTH as u64) as usize;
+            while results[slot].is_none() {
+                self.complete(&mut results)?;
+            }
+            let result = results[slot].take().expect("result is completed");
+            let read = usize::try_from(result).map_err(|_| io::Error::from_raw_os_error(-result))?;
+            update(&self.buffers[slot][..read]);
+            let position = chunk * buffer_size;
+            let expected = (length - position).min(buffer_size);
+            if (read as u64) < expected {
+                return Ok(position + read as u64);
+            }
+        }
+        Ok(length)
+    }
+
+    /// Queues the read of the chunk into the buffer of its slot.
+    fn submit(&mut self, file: &File, chunk: u64, length: u64) -> io::Result<()> {
+        let slot = (chunk % DEPTH as u64) as usize;
+        let buffer = &mut self.buffers[slot];
+        let position = chunk * buffer.len() as u64;
+        let size = (length - position).min(buffer.len() as u64) as u32;
+        let fd = types::Fd(file.as_raw_fd());
+        let entry = if self.registered {
+            opcode::ReadFixed::new(fd, buffer.as_mut_ptr(), size, slot as u16)
+                .offset(position)
+                .build()
+        } else {
+            opcode::Read::new(fd, buffer.as_mut_ptr(), size)
+                .offset(position)
+                .build()
+        };
+        let entry = entry.user_data(slot as u64);
+        // SAFETY: the buffer is not accessed until the read completes, and the reads are drained before it is dropped
+        unsafe { self.ring.submission().push(&entry) }.map_err(|_| io::Error::other("submission queue is full"))?;
+        self.in_flight += 1;
+        self.ring.submit()?;
+        Ok(())
+    }
+
+    /// Waits for at least one read to complete, storing the results in the slots of their buffers.
+    fn complete(&mut self, results: &mut [Option<i32>; DEPTH]) -> io::Result<()> {
+        match self.ring.submit_and_wait(1) {
+            Ok(_) => {},
+            Err(error) if error.kind() == ErrorKind::Interrupted => {},
+            Err(error) => return Err(error),
+        }
+        for entry in self.ring.completion() {
+            results[entry.user_data() as usize] = Some(entry.result());
+            self.in_flight -= 1;
+        }
+        Ok(())
+    }
+
+    /// Waits for all queued reads to complete, discarding their results.
+    fn drain(&mut self) -> io::Result<()> {
+        let mut results = [None; DEPTH];
+        while self.in_flight > 0 {
+            self.complete(&mut results)?;
+        }
+        Ok(())
+    }
+}
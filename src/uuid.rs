@@ -0,0 +1,37 @@
+//! Name-based UUIDs of version 3, as specified in [RFC 4122](https://www.rfc-editor.org/rfc/rfc4122).
+//!
+//! A version 3 UUID is the MD5 digest of a namespace UUID followed by a name, with the version and variant bits set. The same name in the same namespace always maps to the same UUID, see the [`uuid_v3`](crate::uuid_v3) function.
+//!
+//! UUIDs are represented by their 16 bytes in the network byte order, which is the representation accepted by `uuid::Uuid::from_bytes` of the `uuid` crate.
+
+use crate::MD5;
+
+/// The namespace of fully qualified domain names.
+pub const NAMESPACE_DNS: [u8; 16] = [
+    0x6B, 0xA7, 0xB8, 0x10, 0x9D, 0xAD, 0x11, 0xD1, 0x80, 0xB4, 0x00, 0xC0, 0x4F, 0xD4, 0x30, 0xC8,
+];
+
+/// The namespace of URLs.
+pub const NAMESPACE_URL: [u8; 16] = [
+    0x6B, 0xA7, 0xB8, 0x11, 0x9D, 0xAD, 0x11, 0xD1, 0x80, 0xB4, 0x00, 0xC0, 0x4F, 0xD4, 0x30, 0xC8,
+];
+
+/// The namespace of ISO object identifiers.
+pub const NAMESPACE_OID: [u8; 16] = [
+    0x6B, 0xA7, 0xB8, 0x12, 0x9D, 0xAD, 0x11, 0xD1, 0x80, 0xB4, 0x00, 0xC0, 0x4F, 0xD4, 0x30, 0xC8,
+];
+
+/// The namespace of X.500 distinguished names.
+pub const NAMESPACE_X500: [u8; 16] = [
+    0x6B, 0xA7, 0xB8, 0x14, 0x9D, 0xAD, 0x11, 0xD1, 0x80, 0xB4, 0x00, 0xC0, 0x4F, 0xD4, 0x30, 0xC8,
+];
+
+pub(crate) fn v3(namespace: [u8; 16], name: &[u8]) -> [u8; 16] {
+    let mut hash = MD5::new();
+    hash.update(namespace);
+    hash.update(name);
+    let mut uuid = hash.digest().into_inner();
+    uuid[6] = (uuid[6] & 0x0F) | 0x30;
+    uuid[8] = (uuid[8] & 0x3F) | 0x80;
+    uuid
+}
@@ -0,0 +1,34 @@
+use std::fs::File;
+use std::io::{self, ErrorKind, IoSliceMut, Read};
+
+/// The number of buffers filled by a single read.
+const BUFFERS: usize = 8;
+
+/// Hashes the file with vectored reads, each filling a pool of buffers with a single system call.
+///
+/// Returns the number of bytes read.
+pub(crate) fn hash(mut file: &File, buffer_size: usize, update: &mut impl FnMut(&[u8])) -> io::Result<u64> {
+    let buffer_size = buffer_size.max(1);
+    let mut buffers = vec![vec![0; buffer_size]; BUFFERS];
+    let mut bytes = 0;
+    loop {
+        let mut slices: Vec<IoSliceMut> = buffers.iter_mut().map(|buffer| IoSliceMut::new(buffer)).collect();
+        let read = match file.read_vectored(&mut slices) {
+            Ok(0) => return Ok(bytes),
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        // the buffers are filled in order
+        let mut rest = read;
+        for buffer in &buffers {
+            let length = rest.min(buffer.len());
+            if length == 0 {
+                break;
+            }
+            update(&buffer[..length]);
+            rest -= length;
+        }
+        bytes += read as u64;
+    }
+}
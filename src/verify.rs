@@ -0,0 +1,84 @@
+//! Verification of inputs against expected digests.
+
+use std::hint::black_box;
+
+use crate::{hash, Digest, Result};
+
+/// An expected digest, given either directly, as raw bytes, or in the hexadecimal representation.
+///
+/// Hexadecimal representations are case-insensitive; a string which is not a valid digest causes an [`Error::InvalidDigest`](crate::Error::InvalidDigest) error.
+pub trait ExpectedDigest {
+    /// Converts the value to a digest.
+    fn to_digest(&self) -> Result<Digest>;
+}
+
+impl ExpectedDigest for Digest {
+    fn to_digest(&self) -> Result<Digest> {
+        Ok(*self)
+    }
+}
+
+impl ExpectedDigest for hash::Digest {
+    fn to_digest(&self) -> Result<Digest> {
+        Ok(Digest::from(*self))
+    }
+}
+
+impl ExpectedDigest for [u8; hash::DIGEST_LENGTH_BYTES] {
+    fn to_digest(&self) -> Result<Digest> {
+        Ok(Digest::new(*self))
+    }
+}
+
+impl ExpectedDigest for str {
+    fn to_digest(&self) -> Result<Digest> {
+        let digest = hash::Digest::try_from(self)?;
+        Ok(Digest::from(digest))
+    }
+}
+
+impl ExpectedDigest for String {
+    fn to_digest(&self) -> Result<Digest> {
+        self.as_str().to_digest()
+    }
+}
+
+impl<T> ExpectedDigest for &T
+where
+    T: ExpectedDigest + ?Sized,
+{
+    fn to_digest(&self) -> Result<Digest> {
+        (**self).to_digest()
+    }
+}
+
+/// The outcome of copying a file with the [`copy_verified`](crate::copy_verified) function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct VerifiedCopy {
+    /// The number of copied bytes.
+    pub length: u64,
+    /// The digest of the source file, computed while copying.
+    pub source: Digest,
+    /// The digest of the destination file, computed by reading it again, if requested.
+    pub destination: Option<Digest>,
+}
+
+impl VerifiedCopy {
+    /// Returns `true` if the destination was read again and matches the source.
+    #[must_use]
+    pub fn is_verified(&self) -> bool {
+        self.destination
+            .is_some_and(|destination| eq(&self.source, &destination))
+    }
+}
+
+/// Compares digests in constant time, so the comparison doesn't reveal how many leading bytes match.
+pub(crate) fn eq(a: &Digest, b: &Digest) -> bool {
+    let difference = a
+        .as_bytes()
+        .iter()
+        .zip(b.as_bytes())
+        .fold(0, |difference, (a, b)| difference | (a ^ b));
+    black_box(difference) == 0
+}
@@ -0,0 +1,480 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs::{self, DirEntry, File};
+use std::io::{self, BufRead, BufReader, IsTerminal, Read};
+use std::mem;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
+#[cfg(all(feature = "direct-io", target_os = "linux"))]
+use crate::direct;
+use crate::error::{Context, Operation};
+use crate::options::{BufferSize, OnError, Options, SpecialFiles};
+use crate::report::{Report, Skipped, Statistics};
+#[cfg(all(
+    feature = "sparse",
+    any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd"
+    )
+))]
+use crate::sparse;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+use crate::uring;
+use crate::{read_ahead, tuning, vectored, Digest, Error, Result, MD5};
+
+/// Calculates the checksum of the given path, traversing directories recursively.
+pub(crate) fn chksum(path: &Path, options: &Options) -> Result<Report> {
+    let result = report(path, options);
+    #[cfg(feature = "metrics")]
+    if let Err(error) = &result {
+        crate::metrics::error(error);
+    }
+    result
+}
+
+fn report(path: &Path, options: &Options) -> Result<Report> {
+    let root = extended(path);
+    if options.file_only && fs::metadata(&root).context(path, Operation::Metadata)?.is_dir() {
+        let path = path.to_path_buf();
+        return Err(Error::IsADirectory { path });
+    }
+    // the digest of a single file is the digest of its contents only without hard link deduplication
+    let cached_file = match &options.cache {
+        Some(cache) if !options.dedup_hardlinks => {
+            let metadata = fs::metadata(&root).context(path, Operation::Metadata)?;
+            if metadata.is_file() {
+                if let Some(digest) = cache.get(&root, &metadata) {
+                    let statistics = Statistics {
+                        files: 1,
+                        cached: 1,
+                        ..Statistics::default()
+                    };
+                    return Ok(Report {
+                        digest,
+                        statistics,
+                        skipped: Vec::new(),
+                    });
+                }
+                Some((cache, metadata))
+            } else {
+                None
+            }
+        },
+        _ => None,
+    };
+    let mut walker = Walker {
+        options,
+        hash: MD5::new(),
+        statistics: Statistics::default(),
+        skipped: Vec::new(),
+        inodes: HashMap::new(),
+        ancestors: Vec::new(),
+    };
+    // the error policy does not apply to the given path itself
+    walker.visit_entry(&root)?;
+    let Walker {
+        hash,
+        statistics,
+        skipped,
+        ..
+    } = walker;
+    let digest = hash.digest();
+    if let Some((cache, metadata)) = cached_file {
+        cache.insert(&root, &metadata, digest);
+    }
+    Ok(Report {
+        digest,
+        statistics,
+        skipped,
+    })
+}
+
+/// Lists the files under the given path with their sizes, in the traversal order of the [`chksum`] function.
+pub(crate) fn files(path: &Path, options: &Options) -> Result<Vec<(PathBuf, u64)>> {
+    fn collect(
+        path: &Path,
+        options: &Options,
+        ancestors: &mut Vec<PathBuf>,
+        files: &mut Vec<(PathBuf, u64)>,
+    ) -> Result<()> {
+        let metadata = fs::metadata(path).context(path, Operation::Metadata)?;
+        if metadata.is_dir() {
+            let dir_entries = read_dir(path, options, ancestors)?;
+            let result = dir_entries
+                .into_iter()
+                .try_for_each(|dir_entry| collect(&dir_entry.path(), options, ancestors, files));
+            ancestors.pop();
+            result
+        } else {
+            files.push((path.to_path_buf(), metadata.len()));
+            Ok(())
+        }
+    }
+
+    let root = extended(path);
+    let mut files = Vec::new();
+    collect(&root, options, &mut Vec::new(), &mut files)?;
+    if let Cow::Owned(root) = root {
+        // report paths in the form given by the caller
+        for (file, _) in &mut files {
+            if let Ok(relative) = file.strip_prefix(&root) {
+                *file = path.join(relative);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Converts the given path to the extended-length form, which lifts the `MAX_PATH` limit of Windows.
+///
+/// Relative paths are resolved against the current directory and UNC shares are converted to the `\\?\UNC\` form. Verbatim and device paths are used as they are.
+#[cfg(windows)]
+fn extended(path: &Path) -> Cow<'_, Path> {
+    use std::env;
+    use std::ffi::OsString;
+    use std::path::{Component, Prefix};
+
+    let absolute = if path.is_absolute() {
+        Cow::Borrowed(path)
+    } else {
+        match env::current_dir() {
+            Ok(current_dir) => Cow::Owned(current_dir.join(path)),
+            Err(_) => return Cow::Borrowed(path),
+        }
+    };
+    let mut components = absolute.components();
+    let mut extended = match components.next() {
+        Some(Component::Prefix(prefix)) => {
+            match prefix.kind() {
+                Prefix::Disk(disk) => PathBuf::from(format!(r"\\?\{}:\", char::from(disk))),
+                Prefix::UNC(server, share) => {
+                    let mut extended = OsString::from(r"\\?\UNC\");
+                    extended.push(server);
+                    extended.push(r"\");
+                    extended.push(share);
+                    extended.push(r"\");
+                    PathBuf::from(extended)
+                },
+                _ => return absolute,
+            }
+        },
+        _ => return absolute,
+    };
+    // extended-length paths are not normalized by Windows, so dot components are resolved here
+    for component in components {
+        match component {
+            Component::Normal(name) => extended.push(name),
+            Component::ParentDir => {
+                extended.pop();
+            },
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => {},
+        }
+    }
+    Cow::Owned(extended)
+}
+
+#[cfg(not(windows))]
+fn extended(path: &Path) -> Cow<'_, Path> {
+    Cow::Borrowed(path)
+}
+
+/// Reads sorted entries of the given directory and pushes its canonical path to the ancestors.
+///
+/// Fails with [`Error::SymlinkCycle`] when the directory is already one of the ancestors.
+fn read_dir(path: &Path, options: &Options, ancestors: &mut Vec<PathBuf>) -> Result<Vec<DirEntry>> {
+    let canonical = fs::canonicalize(path).context(path, Operation::Metadata)?;
+    if ancestors.contains(&canonical) {
+        let path = path.to_path_buf();
+        return Err(Error::SymlinkCycle { path });
+    }
+    let dir_entries: io::Result<Vec<DirEntry>> = fs::read_dir(path).context(path, Operation::ReadDir)?.collect();
+    let mut dir_entries = dir_entries.context(path, Operation::ReadDir)?;
+    dir_entries.sort_by_cached_key(|dir_entry| sort_key(&dir_entry.file_name(), options));
+    ancestors.push(canonical);
+    Ok(dir_entries)
+}
+
+/// Returns the key which orders entries of a directory by the raw bytes of their names.
+///
+/// Since every directory is sorted separately, the files of a tree are ordered by the components of their relative paths on every platform.
+#[allow(unused_variables)]
+fn sort_key(name: &OsStr, options: &Options) -> Vec<u8> {
+    #[cfg(feature = "unicode-normalization")]
+    if options.normalize_unicode {
+        use unicode_normalization::UnicodeNormalization;
+
+        if let Some(name) = name.to_str() {
+            return name.nfc().collect::<String>().into_bytes();
+        }
+    }
+    name.as_encoded_bytes().to_vec()
+}
+
+struct Walker<'a> {
+    options: &'a Options,
+    hash: MD5,
+    statistics: Statistics,
+    skipped: Vec<Skipped>,
+    inodes: HashMap<(u64, u64), Digest>,
+    ancestors: Vec<PathBuf>,
+}
+
+impl Walker<'_> {
+    fn visit(&mut self, path: &Path) -> Result<()> {
+        if self.options.on_error == OnError::Fail {
+            return self.visit_entry(path);
+        }
+
+        // a failed entry can leave partially read data in the hash, so it is rolled back
+        let hash = self.hash.clone();
+        let statistics = self.statistics.clone();
+        match self.visit_entry(path) {
+            Err(Error::Path {
+                path,
+                operation,
+                source,
+            }) => {
+                self.hash = hash;
+                self.statistics = statistics;
+                self.statistics.skipped += 1;
+                if self.options.on_error == OnError::Collect {
+                    self.skipped.push(Skipped {
+                        path,
+                        operation,
+                        kind: source.kind(),
+                    });
+                }
+                Ok(())
+            },
+            result => result,
+        }
+    }
+
+    fn visit_entry(&mut self, path: &Path) -> Result<()> {
+        let metadata = fs::metadata(path).context(path, Operation::Metadata)?;
+        if metadata.is_dir() {
+            self.visit_directory(path)
+        } else {
+            // everything treat as a file when it is not a directory
+            self.visit_file(path, &metadata)
+        }
+    }
+
+    fn visit_directory(&mut self, path: &Path) -> Result<()> {
+        let dir_entries = read_dir(path, self.options, &mut self.ancestors)?;
+        self.statistics.directories += 1;
+        let result = dir_entries
+            .into_iter()
+            .try_for_each(|dir_entry| self.visit(&dir_entry.path()));
+        self.ancestors.pop();
+        result
+    }
+
+    fn visit_file(&mut self, path: &Path, metadata: &fs::Metadata) -> Result<()> {
+        // special files are detected before opening them, which blocks for FIFOs without writers
+        if !metadata.is_file() {
+            match self.options.special_files {
+                SpecialFiles::Read | SpecialFiles::ReadLimit(_) => {},
+                SpecialFiles::Skip => {
+                    self.statistics.special_files += 1;
+                    return Ok(());
+                },
+                SpecialFiles::Error => {
+                    let path = path.to_path_buf();
+                    return Err(Error::SpecialFile { path });
+                },
+            }
+        }
+
+        if !self.options.dedup_hardlinks {
+            let mut hash = mem::take(&mut self.hash);
+            let result = self.read_file(path, metadata, &mut hash);
+            self.hash = hash;
+            return result;
+        }
+
+        let inode = inode(metadata);
+        if let Some(digest) = inode.and_then(|inode| self.inodes.get(&inode)) {
+            self.hash.update(digest.as_bytes());
+            self.statistics.files += 1;
+            self.statistics.hardlinks += 1;
+            return Ok(());
+        }
+        let cache = self.options.cache.as_ref().filter(|_| metadata.is_file());
+        let digest = match cache.and_then(|cache| cache.get(path, metadata)) {
+            Some(digest) => {
+                self.statistics.files += 1;
+                self.statistics.cached += 1;
+                digest
+            },
+            None => {
+                let mut hash = MD5::new();
+                self.read_file(path, metadata, &mut hash)?;
+                let digest = hash.digest();
+                if let Some(cache) = cache {
+                    cache.insert(path, metadata, digest);
+                }
+                digest
+            },
+        };
+        if let Some(inode) = inode {
+            self.inodes.insert(inode, digest);
+        }
+        self.hash.update(digest.as_bytes());
+        Ok(())
+    }
+
+    fn read_file(&mut self, path: &Path, metadata: &fs::Metadata, hash: &mut MD5) -> Result<()> {
+        let file = File::open(path).context(path, Operation::Open)?;
+        if file.is_terminal() {
+            return Err(Error::IsTerminal);
+        }
+
+        #[cfg(feature = "metrics")]
+        let start = Instant::now();
+        #[cfg(feature = "indicatif")]
+        let progress = self
+            .options
+            .progress
+            .as_ref()
+            .map(|progress| progress.file(path, metadata.len()));
+        // the progress advances with every chunk, whichever backend reads the file
+        let mut update = |data: &[u8]| {
+            hash.update(data);
+            #[cfg(feature = "indicatif")]
+            if let Some(progress) = &progress {
+                progress.inc(data.len() as u64);
+            }
+        };
+        let sizes = self.read(file, path, metadata, &mut update)?;
+
+        self.statistics.bytes += sizes.data + sizes.holes;
+        self.statistics.sparse_bytes += sizes.holes;
+        self.statistics.buffer_size = self.statistics.buffer_size.max(sizes.buffer_size);
+        self.statistics.files += 1;
+        #[cfg(feature = "metrics")]
+        crate::metrics::file(sizes.data + sizes.holes, start.elapsed());
+        Ok(())
+    }
+
+    /// Reads the file with the backend selected by the options, passing its data to the update function.
+    #[allow(unused_mut)]
+    fn read(
+        &self,
+        mut file: File,
+        path: &Path,
+        metadata: &fs::Metadata,
+        update: &mut impl FnMut(&[u8]),
+    ) -> Result<Sizes> {
+        let buffer_size = self.options.buffer_size.resolve(metadata);
+        let read = |data| {
+            Sizes {
+                data,
+                holes: 0,
+                buffer_size,
+            }
+        };
+
+        #[cfg(all(feature = "direct-io", target_os = "linux"))]
+        if self.options.direct_io && metadata.is_file() {
+            if let Some(bytes) = direct::hash(path, buffer_size, update).context(path, Operation::Read)? {
+                return Ok(read(bytes));
+            }
+        }
+
+        #[cfg(all(
+            feature = "sparse",
+            any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "freebsd"
+            )
+        ))]
+        if self.options.sparse && metadata.is_file() {
+            let sizes = sparse::hash(&mut file, metadata.len(), buffer_size, update).context(path, Operation::Read)?;
+            if let Some(sizes) = sizes {
+                let holes = sizes.holes;
+                return Ok(Sizes {
+                    holes,
+                    ..read(sizes.data)
+                });
+            }
+        }
+
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        if self.options.io_uring && metadata.is_file() {
+            let bytes = uring::hash(&file, metadata.len(), buffer_size, update).context(path, Operation::Read)?;
+            if let Some(bytes) = bytes {
+                return Ok(read(bytes));
+            }
+        }
+
+        if self.options.read_ahead && metadata.is_file() && metadata.len() > buffer_size as u64 {
+            if let Some(bytes) = read_ahead::hash(&file, buffer_size, update).context(path, Operation::Read)? {
+                return Ok(read(bytes));
+            }
+        }
+
+        if self.options.vectored_reads && metadata.is_file() {
+            let bytes = vectored::hash(&file, buffer_size, update).context(path, Operation::Read)?;
+            return Ok(read(bytes));
+        }
+
+        let limit = match self.options.special_files {
+            SpecialFiles::ReadLimit(limit) if !metadata.is_file() => limit,
+            _ => u64::MAX,
+        };
+        if let BufferSize::Tuned { min, max } = self.options.buffer_size {
+            let tuned = tuning::hash(file.take(limit), buffer_size, min, max, update).context(path, Operation::Read)?;
+            let buffer_size = tuned.buffer_size;
+            return Ok(Sizes {
+                buffer_size,
+                ..read(tuned.bytes)
+            });
+        }
+        let mut reader = BufReader::with_capacity(buffer_size, file.take(limit));
+        let mut bytes = 0;
+        loop {
+            let buffer = reader.fill_buf().context(path, Operation::Read)?;
+            let length = buffer.len();
+            if length == 0 {
+                break;
+            }
+            update(buffer);
+            bytes += length as u64;
+            reader.consume(length);
+        }
+        Ok(read(bytes))
+    }
+}
+
+/// The amount of data read from a file.
+struct Sizes {
+    /// The number of bytes read.
+    data: u64,
+    /// The number of bytes of holes skipped instead of being read.
+    holes: u64,
+    /// The largest buffer size used.
+    buffer_size: usize,
+}
+
+/// Returns the device and inode numbers of files with multiple hard links.
+#[cfg(unix)]
+fn inode(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    (metadata.nlink() > 1).then(|| (metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn inode(_: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
@@ -0,0 +1,193 @@
+//! This module is optional and can be enabled using the `watch` Cargo feature.
+//!
+//! The [`watch`] function monitors a file or a directory tree and calculates digest of every file which is created or modified, which enables lightweight integrity monitoring. Bursts of changes of the same file are debounced, so the file is hashed once it stays unchanged for the configured period.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `watch` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["watch"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features watch
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//! use md5::watch::WatchOptions;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let options = WatchOptions::new();
+//! let watcher = md5::watch::watch(path, &options, |path, digest| {
+//!     match digest {
+//!         Ok(digest) => println!("{digest}  {}", path.display()),
+//!         Err(error) => eprintln!("{}: {error}", path.display()),
+//!     }
+//! })?;
+//! // the watching stops when the watcher is dropped
+//! drop(watcher);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use notify::event::EventKind;
+use notify::{recommended_watcher, Event, RecommendedWatcher, RecursiveMode, Watcher as _};
+
+use crate::{chksum_with, Digest, Options, Result};
+
+/// The default debounce period.
+pub const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Options for the [`watch`] function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WatchOptions {
+    debounce: Duration,
+    recursive: bool,
+    options: Options,
+}
+
+impl WatchOptions {
+    /// Creates new default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the period for which a file must stay unchanged before it is hashed.
+    #[must_use]
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Sets whether subdirectories of a watched directory are watched too.
+    #[must_use]
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Sets the options used for hashing changed files.
+    #[must_use]
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            debounce: DEBOUNCE,
+            recursive: true,
+            options: Options::default(),
+        }
+    }
+}
+
+/// A handle of the running watch created by the [`watch`] function.
+///
+/// The watching stops when the handle is dropped.
+#[derive(Debug)]
+pub struct Watcher {
+    watcher: Option<RecommendedWatcher>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        // dropping the watcher disconnects the channel, which stops the thread
+        self.watcher.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Watches the given path and calls the callback with the digest of every created or modified file.
+///
+/// The callback is called from a background thread. Files which cannot be hashed, e.g. because they were removed in the meantime, are reported with an error.
+pub fn watch<F>(path: impl AsRef<Path>, options: &WatchOptions, callback: F) -> Result<Watcher>
+where
+    F: FnMut(PathBuf, Result<Digest>) + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = recommended_watcher(move |event: notify::Result<Event>| {
+        if let Ok(event) = event {
+            if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                for path in event.paths {
+                    let _ = sender.send(path);
+                }
+            }
+        }
+    })
+    .map_err(io::Error::other)?;
+    let mode = if options.recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    watcher.watch(path.as_ref(), mode).map_err(io::Error::other)?;
+
+    let WatchOptions { debounce, options, .. } = options.clone();
+    let thread = thread::spawn(move || debounced(&receiver, debounce, &options, callback));
+    Ok(Watcher {
+        watcher: Some(watcher),
+        thread: Some(thread),
+    })
+}
+
+fn debounced<F>(receiver: &Receiver<PathBuf>, debounce: Duration, options: &Options, mut callback: F)
+where
+    F: FnMut(PathBuf, Result<Digest>),
+{
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+    loop {
+        let received = match pending.values().min() {
+            Some(changed) => {
+                let timeout = (*changed + debounce).saturating_duration_since(Instant::now());
+                receiver.recv_timeout(timeout)
+            },
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(path) => {
+                pending.insert(path, Instant::now());
+            },
+            Err(RecvTimeoutError::Timeout) => {},
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        let now = Instant::now();
+        let mut ready: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, changed)| now.duration_since(**changed) >= debounce)
+            .map(|(path, _)| path.clone())
+            .collect();
+        ready.sort();
+        for path in ready {
+            pending.remove(&path);
+            if path.is_dir() {
+                continue;
+            }
+            let digest = chksum_with(&path, options);
+            callback(path, digest);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! This module is optional and can be enabled using the `web` Cargo feature on the `wasm32` targets.
+//!
+//! The [`async_chksum_blob`] function calculates the digest of a [`Blob`], like a [`File`](https://developer.mozilla.org/en-US/docs/Web/API/File) picked by the user, and the [`async_chksum_stream`] function the digest of a [`ReadableStream`] of bytes, e.g. the body of a `fetch` response. The content is streamed through the hash chunk by chunk, so browser applications can verify uploads on the client side without loading them into memory.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `web` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-md5 = { version = "0.1.0", features = ["web"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-md5 --features web
+//! ```
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! # use chksum_md5::Result;
+//! use chksum_md5 as md5;
+//! use web_sys::Blob;
+//!
+//! # async fn wrapper(blob: &Blob) -> Result<()> {
+//! let (length, digest) = md5::web::async_chksum_blob(blob).await?;
+//! assert_eq!(length, 12);
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "5c71dbb287630d65ca93764c34d9aa0d"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io;
+
+use futures_util::StreamExt;
+use js_sys::wasm_bindgen::{JsCast, JsValue};
+use js_sys::Uint8Array;
+use web_sys::{Blob, ReadableStream};
+
+use crate::{Digest, Result, MD5};
+
+/// Computes the hash of the content of the blob.
+///
+/// Returns the number of read bytes together with the digest.
+pub async fn async_chksum_blob(blob: &Blob) -> Result<(u64, Digest)> {
+    async_chksum_stream(blob.stream()).await
+}
+
+/// Computes the hash of the chunks of the stream, which have to be [`Uint8Array`]s.
+///
+/// Returns the number of read bytes together with the digest.
+///
+/// # Errors
+///
+/// Returns an error if the stream is locked by another reader, fails, or yields a chunk other than a [`Uint8Array`].
+pub async fn async_chksum_stream(stream: ReadableStream) -> Result<(u64, Digest)> {
+    let mut stream = wasm_streams::ReadableStream::from_raw(stream)
+        .try_into_stream()
+        .map_err(|(error, _)| other(error.into()))?;
+    let mut hash = MD5::new();
+    let mut length = 0;
+    let mut buffer = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk
+            .map_err(other)?
+            .dyn_into::<Uint8Array>()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "chunk is not a Uint8Array"))?;
+        buffer.resize(chunk.length() as usize, 0);
+        chunk.copy_to(&mut buffer);
+        hash.update(&buffer);
+        length += buffer.len() as u64;
+    }
+    Ok((length, hash.digest()))
+}
+
+/// Converts a JavaScript error, which can't be sent between threads, to an I/O error with its message.
+fn other(error: JsValue) -> io::Error {
+    let message = error
+        .dyn_ref::<js_sys::Error>()
+        .map(|error| String::from(error.message()))
+        .or_else(|| error.as_string())
+        .unwrap_or_else(|| format!("{error:?}"));
+    io::Error::other(message)
+}
@@ -0,0 +1,143 @@
+#[cfg(feature = "actix-web")]
+use actix_web::test::{self, TestRequest};
+#[cfg(feature = "actix-web")]
+use actix_web::web::{self, Bytes};
+#[cfg(feature = "actix-web")]
+use actix_web::{http::StatusCode, App, HttpMessage, HttpRequest, HttpResponse};
+#[cfg(feature = "actix-web")]
+use chksum_md5::actix_web::{ContentMd5, OnFailure, ValidationFailure};
+
+#[cfg(feature = "actix-web")]
+async fn echo(request: HttpRequest, body: Bytes) -> HttpResponse {
+    let failure = request.extensions().get::<ValidationFailure>().copied();
+    HttpResponse::Ok()
+        .insert_header(("x-failure", format!("{failure:?}")))
+        .body(body)
+}
+
+#[cfg_attr(not(feature = "actix-web"), ignore)]
+#[tokio::test]
+async fn responses() {
+    #[cfg(feature = "actix-web")]
+    {
+        let app = test::init_service(
+            App::new()
+                .wrap(ContentMd5::new().limit(16))
+                .route("/", web::post().to(echo))
+                .route(
+                    "/stream",
+                    web::get().to(|| {
+                        async {
+                            HttpResponse::Ok().streaming(futures_util::stream::iter([Ok::<_, actix_web::Error>(
+                                Bytes::from("example data"),
+                            )]))
+                        }
+                    }),
+                ),
+        )
+        .await;
+
+        let request = TestRequest::post().uri("/").set_payload("example data").to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(
+            response.headers().get("content-md5").map(|value| value.as_bytes()),
+            Some(&b"XHHbsodjDWXKk3ZMNNmqDQ=="[..])
+        );
+        assert_eq!(test::read_body(response).await, "example data");
+
+        // bodies larger than the limit are sent without the header
+        let request = TestRequest::post()
+            .uri("/")
+            .set_payload("example data, longer than the limit")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(!response.headers().contains_key("content-md5"));
+
+        let request = TestRequest::get().uri("/stream").to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(!response.headers().contains_key("content-md5"));
+        assert_eq!(test::read_body(response).await, "example data");
+    }
+}
+
+#[cfg_attr(not(feature = "actix-web"), ignore)]
+#[tokio::test]
+async fn rejected_requests() {
+    #[cfg(feature = "actix-web")]
+    {
+        let app = test::init_service(
+            App::new()
+                .wrap(ContentMd5::new().validate_requests(true).limit(16))
+                .route("/", web::post().to(echo)),
+        )
+        .await;
+
+        for (payload, content_md5, status) in [
+            ("example data", "XHHbsodjDWXKk3ZMNNmqDQ==", StatusCode::OK),
+            ("changed data", "XHHbsodjDWXKk3ZMNNmqDQ==", StatusCode::BAD_REQUEST),
+            ("example data", "invalid", StatusCode::BAD_REQUEST),
+            (
+                "example data, longer than the limit",
+                "XHHbsodjDWXKk3ZMNNmqDQ==",
+                StatusCode::PAYLOAD_TOO_LARGE,
+            ),
+        ] {
+            let request = TestRequest::post()
+                .uri("/")
+                .insert_header(("content-md5", content_md5))
+                .set_payload(payload)
+                .to_request();
+            let response = test::call_service(&app, request).await;
+            assert_eq!(response.status(), status, "{payload} with {content_md5}");
+        }
+
+        let request = TestRequest::post().uri("/").set_payload("example data").to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(test::read_body(response).await, "example data");
+    }
+}
+
+#[cfg_attr(not(feature = "actix-web"), ignore)]
+#[tokio::test]
+async fn continued_requests() {
+    #[cfg(feature = "actix-web")]
+    {
+        let app = test::init_service(
+            App::new()
+                .wrap(
+                    ContentMd5::new()
+                        .validate_requests(true)
+                        .limit(16)
+                        .on_failure(OnFailure::Continue),
+                )
+                .route("/", web::post().to(echo)),
+        )
+        .await;
+
+        for (payload, content_md5, failure) in [
+            ("example data", "XHHbsodjDWXKk3ZMNNmqDQ==", "None"),
+            ("changed data", "XHHbsodjDWXKk3ZMNNmqDQ==", "Some(Mismatch)"),
+            ("example data", "invalid", "Some(Malformed)"),
+            (
+                "example data, longer than the limit",
+                "XHHbsodjDWXKk3ZMNNmqDQ==",
+                "Some(TooLarge)",
+            ),
+        ] {
+            let request = TestRequest::post()
+                .uri("/")
+                .insert_header(("content-md5", content_md5))
+                .set_payload(payload)
+                .to_request();
+            let response = test::call_service(&app, request).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers().get("x-failure").map(|value| value.as_bytes()),
+                Some(failure.as_bytes())
+            );
+            // the service receives the whole body
+            assert_eq!(test::read_body(response).await, payload);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::io::Error as IoError;
+#[cfg(feature = "zip")]
+use std::io::{Cursor, Write};
+#[cfg(feature = "tar")]
+use std::path::PathBuf;
+
+#[cfg(feature = "tar")]
+use chksum_md5::archive::tar_entries;
+#[cfg(feature = "zip")]
+use chksum_md5::archive::zip_entries;
+#[cfg(feature = "tar")]
+use chksum_md5::hash;
+use chksum_md5::Error as ChksumError;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[cfg(feature = "zip")]
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+}
+
+#[cfg_attr(not(feature = "zip"), ignore)]
+#[test]
+fn zip_archive_entries() -> Result<(), Error> {
+    #[cfg(feature = "zip")]
+    {
+        use zip::write::SimpleFileOptions;
+        use zip::{CompressionMethod, ZipWriter};
+
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        writer.start_file("data.txt", options)?;
+        writer.write_all(b"data")?;
+        writer.add_directory("directory/", options)?;
+        writer.start_file("directory/empty.txt", options)?;
+        let archive = writer.finish()?;
+
+        let entries = zip_entries(archive)?;
+        let entries: Vec<_> = entries
+            .into_iter()
+            .map(|(name, digest)| (name, digest.to_hex_lowercase()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (
+                    String::from("data.txt"),
+                    String::from("8d777f385d3dfec8815d20f7496026dc")
+                ),
+                (
+                    String::from("directory/empty.txt"),
+                    String::from("d41d8cd98f00b204e9800998ecf8427e")
+                ),
+            ]
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "zip"), ignore)]
+#[test]
+fn zip_invalid_archive() {
+    #[cfg(feature = "zip")]
+    {
+        let result = zip_entries(Cursor::new(b"not an archive"));
+        assert!(matches!(result, Err(ChksumError::Io(_))));
+    }
+}
+
+#[cfg_attr(not(feature = "tar"), ignore)]
+#[test]
+fn tar_archive_entries() -> Result<(), Error> {
+    #[cfg(feature = "tar")]
+    {
+        use tar::{Builder, EntryType, Header};
+
+        let mut builder = Builder::new(Vec::new());
+        let mut header = Header::new_gnu();
+        header.set_size(4);
+        header.set_cksum();
+        builder.append_data(&mut header, "data.txt", &b"data"[..])?;
+        let mut header = Header::new_gnu();
+        header.set_entry_type(EntryType::Directory);
+        header.set_size(0);
+        header.set_cksum();
+        builder.append_data(&mut header, "directory/", &b""[..])?;
+        let mut header = Header::new_gnu();
+        header.set_size(0);
+        header.set_cksum();
+        builder.append_data(&mut header, "directory/empty.txt", &b""[..])?;
+        let archive = builder.into_inner()?;
+
+        let digests = tar_entries(&archive[..])?;
+        let entries: Vec<_> = digests
+            .entries()
+            .iter()
+            .map(|(path, digest)| (path.clone(), digest.to_hex_lowercase()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (
+                    PathBuf::from("data.txt"),
+                    String::from("8d777f385d3dfec8815d20f7496026dc")
+                ),
+                (
+                    PathBuf::from("directory/empty.txt"),
+                    String::from("d41d8cd98f00b204e9800998ecf8427e")
+                ),
+            ]
+        );
+        assert_eq!(*digests.digest(), hash(&archive));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_md5::{azure, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[test]
+fn block_and_blob_values() -> Result<(), Error> {
+    let values = azure::content_md5s(&b"example data"[..], 4)?;
+    assert_eq!(
+        values.blocks(),
+        [
+            "ZUgW+F3+IRVnTnEVx9HqUQ==",
+            "zjf/7qaHzy71l8H8fDk0yA==",
+            "jXd/OF09/siBXSD3SWAm3A=="
+        ]
+    );
+    assert_eq!(values.blob(), "XHHbsodjDWXKk3ZMNNmqDQ==");
+
+    let values = azure::content_md5s(&b""[..], 4)?;
+    assert!(values.blocks().is_empty());
+    assert_eq!(values.blob(), "1B2M2Y8AsgTpgAmY7PhCfg==");
+
+    Ok(())
+}
+
+#[test]
+fn verify_files() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("blob");
+    file.write_binary(b"example data")?;
+
+    let blob = "XHHbsodjDWXKk3ZMNNmqDQ==";
+    assert!(azure::verify(file.path(), blob, &[])?);
+    assert!(azure::verify(
+        file.path(),
+        blob,
+        &[(8, "tEAZEqJT+mmG/S0gQzrXeg=="), (4, "jXd/OF09/siBXSD3SWAm3A==")]
+    )?);
+
+    // blocks in the wrong order
+    assert!(!azure::verify(
+        file.path(),
+        blob,
+        &[(4, "jXd/OF09/siBXSD3SWAm3A=="), (8, "tEAZEqJT+mmG/S0gQzrXeg==")]
+    )?);
+    // blocks not covering the file
+    assert!(!azure::verify(file.path(), blob, &[(8, "tEAZEqJT+mmG/S0gQzrXeg==")])?);
+    assert!(!azure::verify(
+        file.path(),
+        blob,
+        &[(u64::MAX, "tEAZEqJT+mmG/S0gQzrXeg=="), (2, blob)]
+    )?);
+    // a different blob
+    assert!(!azure::verify(file.path(), "jXd/OF09/siBXSD3SWAm3A==", &[])?);
+
+    assert!(matches!(
+        azure::verify(file.path(), "5c71dbb287630d65ca93764c34d9aa0d", &[]),
+        Err(ChksumError::InvalidContentMd5 { .. })
+    ));
+    assert!(matches!(
+        azure::verify(temp_dir.path(), blob, &[]),
+        Err(ChksumError::IsADirectory { .. })
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use std::io::{Cursor, Error as IoError};
+
+use chksum_md5::{block_digests, diff_blocks, hash, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[test]
+fn blocks() -> Result<(), Error> {
+    let digests = block_digests(Cursor::new(b"example data"), 5)?;
+    assert_eq!(digests.block_size(), 5);
+    assert_eq!(digests.blocks(), [hash(b"examp"), hash(b"le da"), hash(b"ta")]);
+    assert_eq!(digests.digest().to_hex_lowercase(), "5c71dbb287630d65ca93764c34d9aa0d");
+
+    let digests = block_digests(Cursor::new(b"data"), 2)?;
+    assert_eq!(digests.into_blocks(), [hash(b"da"), hash(b"ta")]);
+
+    Ok(())
+}
+
+#[test]
+fn empty() -> Result<(), Error> {
+    let digests = block_digests(Cursor::new(b""), 4096)?;
+    assert!(digests.blocks().is_empty());
+    assert_eq!(digests.digest().to_hex_lowercase(), "d41d8cd98f00b204e9800998ecf8427e");
+
+    Ok(())
+}
+
+#[test]
+#[should_panic = "block size must be non-zero"]
+fn zero_block_size() {
+    let _ = block_digests(Cursor::new(b"data"), 0);
+}
+
+#[test]
+fn differing_blocks() -> Result<(), Error> {
+    let indices = diff_blocks(Cursor::new(b"example data"), Cursor::new(b"example data"), 4)?;
+    assert!(indices.is_empty());
+
+    let indices = diff_blocks(Cursor::new(b"example data"), Cursor::new(b"exAmple dat"), 4)?;
+    assert_eq!(indices, [0, 2]);
+
+    let indices = diff_blocks(Cursor::new(b"example"), Cursor::new(b"example data"), 4)?;
+    assert_eq!(indices, [1, 2]);
+
+    let a = block_digests(Cursor::new(b"data"), 2)?;
+    let b = block_digests(Cursor::new(b"dada"), 2)?;
+    assert_eq!(a.diff(&b), [1]);
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+#[cfg(feature = "bytes")]
+use bytes::{Buf, Bytes, BytesMut};
+#[cfg(feature = "bytes")]
+use chksum_md5 as md5;
+
+#[cfg_attr(not(feature = "bytes"), ignore)]
+#[test]
+fn empty_bytes() {
+    #[cfg(feature = "bytes")]
+    {
+        let digest = md5::bytes::hash(Bytes::new()).to_hex_lowercase();
+        assert_eq!(digest, "d41d8cd98f00b204e9800998ecf8427e");
+    }
+}
+
+#[cfg_attr(not(feature = "bytes"), ignore)]
+#[test]
+fn non_empty_bytes() {
+    #[cfg(feature = "bytes")]
+    {
+        let digest = md5::bytes::hash(Bytes::from_static(b"data")).to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+        let digest = md5::bytes::hash(BytesMut::from(&b"data"[..])).to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    }
+}
+
+#[cfg_attr(not(feature = "bytes"), ignore)]
+#[test]
+fn chained_bytes() {
+    #[cfg(feature = "bytes")]
+    {
+        let data = Bytes::from_static(b"da")
+            .chain(&b"t"[..])
+            .chain(Bytes::from_static(b"a"));
+        let digest = md5::bytes::hash(data).to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    }
+}
@@ -0,0 +1,214 @@
+use std::io::Error as IoError;
+#[cfg(unix)]
+use std::path::Path;
+#[cfg(unix)]
+use std::process::Command;
+
+use assert_fs::fixture::FixtureError;
+#[allow(unused_imports)]
+use assert_fs::prelude::{FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_md5::{chksum_with_report, DigestCache, Error as ChksumError, Options};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// Sets the modification time of the file to the given `[[CC]YY]MMDDhhmm[.ss]` time.
+#[cfg(unix)]
+fn backdate(path: &Path, time: &str) -> Result<(), Error> {
+    let status = Command::new("touch")
+        .env("TZ", "UTC")
+        .args(["-t", time])
+        .arg(path)
+        .status()?;
+    assert!(status.success());
+    Ok(())
+}
+
+#[cfg_attr(not(unix), ignore)]
+#[test]
+fn unchanged_files_are_not_read() -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"data")?;
+        backdate(file.path(), "202001020304.05")?;
+
+        let cache = DigestCache::new();
+        let options = Options::new().cache(cache.clone());
+        let report = chksum_with_report(file.path(), &options)?;
+        assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(report.statistics().cached, 0);
+        assert_eq!(report.statistics().bytes, 4);
+        assert_eq!(cache.len(), 1);
+
+        let report = chksum_with_report(file.path(), &options)?;
+        assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(report.statistics().cached, 1);
+        assert_eq!(report.statistics().files, 1);
+        assert_eq!(report.statistics().bytes, 0);
+
+        // a changed size invalidates the entry even with the same modification time
+        file.write_binary(b"example data")?;
+        backdate(file.path(), "202001020304.05")?;
+        let report = chksum_with_report(file.path(), &options)?;
+        assert_eq!(report.digest().to_hex_lowercase(), "5c71dbb287630d65ca93764c34d9aa0d");
+        assert_eq!(report.statistics().cached, 0);
+
+        // a changed modification time invalidates the entry even with the same size
+        file.write_binary(b"EXAMPLE DATA")?;
+        backdate(file.path(), "202001020304.06")?;
+        let report = chksum_with_report(file.path(), &options)?;
+        assert_ne!(report.digest().to_hex_lowercase(), "5c71dbb287630d65ca93764c34d9aa0d");
+        assert_eq!(report.statistics().cached, 0);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn recently_modified_files_are_not_cached() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(b"data")?;
+
+    let cache = DigestCache::new();
+    let options = Options::new().cache(cache.clone());
+    let report = chksum_with_report(file.path(), &options)?;
+    assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    assert!(cache.is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(not(unix), ignore)]
+#[test]
+fn directories_with_dedup_hardlinks() -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("a.txt").write_binary(b"data")?;
+        temp_dir.child("b").create_dir_all()?;
+        temp_dir.child("b").child("c.txt").write_binary(b"example data")?;
+        backdate(temp_dir.child("a.txt").path(), "202001020304.05")?;
+        backdate(temp_dir.child("b").child("c.txt").path(), "202001020304.05")?;
+
+        let cache = DigestCache::new();
+        let options = Options::new().dedup_hardlinks(true).cache(cache.clone());
+        let uncached = chksum_with_report(temp_dir.path(), &options)?;
+        assert_eq!(uncached.statistics().cached, 0);
+        assert_eq!(cache.len(), 2);
+
+        let cached = chksum_with_report(temp_dir.path(), &options)?;
+        assert_eq!(cached.digest(), uncached.digest());
+        assert_eq!(cached.statistics().files, 2);
+        assert_eq!(cached.statistics().cached, 2);
+        assert_eq!(cached.statistics().bytes, 0);
+
+        // without hard link deduplication the contents of every file are hashed
+        let options = Options::new().cache(cache.clone());
+        let report = chksum_with_report(temp_dir.path(), &options)?;
+        assert_eq!(report.statistics().cached, 0);
+        assert_eq!(report.statistics().bytes, 16);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(unix), ignore)]
+#[test]
+fn save_and_load() -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"data")?;
+        backdate(file.path(), "196912312359.59")?;
+        let cache_file = temp_dir.child("cache");
+
+        let cache = DigestCache::new();
+        chksum_with_report(file.path(), &Options::new().cache(cache.clone()))?;
+        cache.save(cache_file.path())?;
+
+        let cache = DigestCache::load(cache_file.path())?;
+        assert_eq!(cache.len(), 1);
+        let report = chksum_with_report(file.path(), &Options::new().cache(cache))?;
+        assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(report.statistics().cached, 1);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn load_missing_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+
+    let cache = DigestCache::load(temp_dir.child("cache").path())?;
+    assert!(cache.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn load_invalid_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let cache_file = temp_dir.child("cache");
+    cache_file.write_binary(b"data")?;
+
+    assert!(DigestCache::load(cache_file.path()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn load_truncated_path() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let cache_file = temp_dir.child("cache");
+    // the length of the path claims almost 4 GiB, while only a few bytes follow
+    let mut data = b"chksum-md5 cache\x01".to_vec();
+    data.extend_from_slice(&u32::MAX.to_le_bytes());
+    data.extend_from_slice(b"file.txt");
+    cache_file.write_binary(&data)?;
+
+    let error = DigestCache::load(cache_file.path()).expect_err("cache file is invalid");
+    assert!(matches!(error, ChksumError::Io(error) if error.kind() == std::io::ErrorKind::InvalidData));
+
+    Ok(())
+}
+
+#[test]
+fn concurrent_saves() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let cache_file = temp_dir.child("cache");
+
+    let cache = DigestCache::new();
+    std::thread::scope(|scope| {
+        let saves: Vec<_> = (0..8).map(|_| scope.spawn(|| cache.save(cache_file.path()))).collect();
+        saves
+            .into_iter()
+            .try_for_each(|save| save.join().expect("thread doesn't panic"))
+    })?;
+    assert!(DigestCache::load(cache_file.path())?.is_empty());
+    // no temporary file is left behind
+    assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn clones_share_entries() {
+    let cache = DigestCache::new();
+    let clone = cache.clone();
+    assert_eq!(cache, clone);
+    assert_ne!(cache, DigestCache::new());
+    assert_eq!(Options::new().cache(cache.clone()), Options::new().cache(clone));
+}
@@ -0,0 +1,50 @@
+#![allow(unsafe_code)]
+
+#[cfg(feature = "capi")]
+use std::ffi::{c_char, CStr};
+#[cfg(feature = "capi")]
+use std::ptr;
+
+#[cfg(feature = "capi")]
+use chksum_md5::capi::{
+    chksum_md5_finalize,
+    chksum_md5_free,
+    chksum_md5_hex,
+    chksum_md5_new,
+    chksum_md5_reset,
+    chksum_md5_update,
+};
+
+#[cfg_attr(not(feature = "capi"), ignore)]
+#[test]
+fn streaming() {
+    #[cfg(feature = "capi")]
+    {
+        let hash = chksum_md5_new();
+        let mut digest = [0; 16];
+        let mut hex: [c_char; 33] = [1; 33];
+        // SAFETY: the hash is valid until it is released, and the buffers have the required lengths
+        unsafe {
+            chksum_md5_update(hash, ptr::null(), 0);
+            chksum_md5_update(hash, b"example ".as_ptr(), 8);
+            chksum_md5_update(hash, b"data".as_ptr(), 4);
+            chksum_md5_finalize(hash, digest.as_mut_ptr());
+            chksum_md5_hex(hash, hex.as_mut_ptr());
+            assert_eq!(digest, *chksum_md5::hash(b"example data").as_bytes());
+            assert_eq!(
+                CStr::from_ptr(hex.as_ptr()).to_str(),
+                Ok("5c71dbb287630d65ca93764c34d9aa0d")
+            );
+
+            chksum_md5_reset(hash);
+            chksum_md5_hex(hash, hex.as_mut_ptr());
+            assert_eq!(
+                CStr::from_ptr(hex.as_ptr()).to_str(),
+                Ok("d41d8cd98f00b204e9800998ecf8427e")
+            );
+
+            chksum_md5_free(hash);
+            chksum_md5_free(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use chksum_md5::chap;
+
+const CHALLENGE: [u8; 16] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+];
+
+#[test]
+fn responses() {
+    let response = chap::response(1, b"secret", &CHALLENGE);
+    assert_eq!(response.to_hex_lowercase(), "740e86463bda3a4d7017d6e0fba0699d");
+    // the identifier comes first, so it is not interchangeable with a byte of the secret
+    assert_ne!(chap::response(2, b"secret", &CHALLENGE), response);
+}
+
+#[test]
+fn verify_responses() {
+    let response = chap::response(1, b"secret", &CHALLENGE);
+    assert!(chap::verify(1, b"secret", &CHALLENGE, response.as_bytes()));
+    assert!(!chap::verify(2, b"secret", &CHALLENGE, response.as_bytes()));
+    assert!(!chap::verify(1, b"other secret", &CHALLENGE, response.as_bytes()));
+    assert!(!chap::verify(1, b"secret", &CHALLENGE[..8], response.as_bytes()));
+    assert!(!chap::verify(1, b"secret", &CHALLENGE, &response.as_bytes()[..15]));
+    assert!(!chap::verify(1, b"secret", &CHALLENGE, b""));
+}
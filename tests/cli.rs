@@ -0,0 +1,136 @@
+use std::io::Error as IoError;
+#[cfg(feature = "cli")]
+use std::process::{Command, Output};
+
+use assert_fs::fixture::FixtureError;
+#[cfg(feature = "cli")]
+use assert_fs::prelude::{FileWriteBin, FileWriteStr, PathChild, PathCreateDir};
+#[cfg(feature = "cli")]
+use assert_fs::TempDir;
+use chksum_md5::Error as ChksumError;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[cfg(feature = "cli")]
+fn run(temp_dir: &TempDir, arguments: &[&str]) -> Result<Output, Error> {
+    let output = Command::new(env!("CARGO_BIN_EXE_chksum-md5"))
+        .args(arguments)
+        .current_dir(temp_dir.path())
+        .output()?;
+    Ok(output)
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn print_digests() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("file.txt").write_binary(b"data")?;
+
+        let output = run(&temp_dir, &["file.txt"])?;
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"8d777f385d3dfec8815d20f7496026dc  file.txt\n");
+
+        let output = run(&temp_dir, &["--binary", "file.txt"])?;
+        assert_eq!(output.stdout, b"8d777f385d3dfec8815d20f7496026dc *file.txt\n");
+
+        let output = run(&temp_dir, &["--tag", "file.txt"])?;
+        assert_eq!(output.stdout, b"MD5 (file.txt) = 8d777f385d3dfec8815d20f7496026dc\n");
+
+        let output = run(&temp_dir, &["-z", "file.txt"])?;
+        assert_eq!(output.stdout, b"8d777f385d3dfec8815d20f7496026dc  file.txt\0");
+
+        let output = run(&temp_dir, &["file.txt", "missing.txt"])?;
+        assert!(!output.status.success());
+        assert_eq!(output.stdout, b"8d777f385d3dfec8815d20f7496026dc  file.txt\n");
+        assert!(!output.stderr.is_empty());
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn print_directories() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("dir").create_dir_all()?;
+        temp_dir.child("dir/file.txt").write_binary(b"data")?;
+
+        let output = run(&temp_dir, &["dir"])?;
+        assert!(output.status.success());
+        let digest = chksum_md5::chksum(temp_dir.child("dir").path())?;
+        assert_eq!(String::from_utf8_lossy(&output.stdout), format!("{digest:x}  dir\n"));
+
+        let output = run(&temp_dir, &["--recursive", "dir"])?;
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"8d777f385d3dfec8815d20f7496026dc  dir/file.txt\n");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn check_digests() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("a.txt").write_binary(b"data")?;
+        temp_dir.child("b.txt").write_binary(b"example data")?;
+        temp_dir.child("checksums.md5").write_str(concat!(
+            "8d777f385d3dfec8815d20f7496026dc  a.txt\n",
+            "5c71dbb287630d65ca93764c34d9aa0d  b.txt\n",
+        ))?;
+
+        let output = run(&temp_dir, &["-c", "checksums.md5"])?;
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"a.txt: OK\nb.txt: OK\n");
+
+        temp_dir.child("a.txt").write_binary(b"changed")?;
+        let output = run(&temp_dir, &["-c", "checksums.md5"])?;
+        assert!(!output.status.success());
+        assert_eq!(output.stdout, b"a.txt: FAILED\nb.txt: OK\n");
+        assert_eq!(
+            String::from_utf8_lossy(&output.stderr),
+            "chksum-md5: WARNING: 1 computed checksum did NOT match\n"
+        );
+
+        let output = run(&temp_dir, &["-c", "--quiet", "checksums.md5"])?;
+        assert_eq!(output.stdout, b"a.txt: FAILED\n");
+
+        let output = run(&temp_dir, &["-c", "--status", "checksums.md5"])?;
+        assert!(!output.status.success());
+        assert!(output.stdout.is_empty());
+        assert!(output.stderr.is_empty());
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "cli"), ignore)]
+#[test]
+fn invalid_options() -> Result<(), Error> {
+    #[cfg(feature = "cli")]
+    {
+        let temp_dir = TempDir::new()?;
+
+        let output = run(&temp_dir, &["--unknown"])?;
+        assert_eq!(output.status.code(), Some(2));
+
+        let output = run(&temp_dir, &["-c", "--tag", "checksums.md5"])?;
+        assert_eq!(output.status.code(), Some(2));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+#[cfg(feature = "codec")]
+use bytes::{BufMut, Bytes, BytesMut};
+#[cfg(feature = "codec")]
+use chksum_md5::codec::FrameCodec;
+#[cfg(feature = "codec")]
+use chksum_md5::hash;
+use chksum_md5::Error as ChksumError;
+#[cfg(feature = "codec")]
+use tokio_util::codec::{Decoder, Encoder};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+}
+
+#[cfg_attr(not(feature = "codec"), ignore)]
+#[test]
+fn frames() -> Result<(), Error> {
+    #[cfg(feature = "codec")]
+    {
+        let mut codec = FrameCodec::new();
+        let mut buffer = BytesMut::new();
+        codec.encode(Bytes::from("example data"), &mut buffer)?;
+        codec.encode(Bytes::new(), &mut buffer)?;
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(b"\0\0\0\x0cexample data");
+        expected.extend_from_slice(hash(b"example data").as_bytes());
+        expected.extend_from_slice(b"\0\0\0\0");
+        expected.extend_from_slice(hash(b"").as_bytes());
+        assert_eq!(&buffer[..], expected);
+
+        // frames are decoded once they are complete
+        let mut input = BytesMut::new();
+        for &byte in &expected[..4 + 12 + 16 - 1] {
+            input.put_u8(byte);
+            assert_eq!(codec.decode(&mut input)?, None);
+        }
+        input.extend_from_slice(&expected[4 + 12 + 16 - 1..]);
+        assert_eq!(codec.decode(&mut input)?.as_deref(), Some(&b"example data"[..]));
+        assert_eq!(codec.decode(&mut input)?.as_deref(), Some(&b""[..]));
+        assert_eq!(codec.decode(&mut input)?, None);
+        assert!(input.is_empty());
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "codec"), ignore)]
+#[test]
+fn corrupted_frames() -> Result<(), Error> {
+    #[cfg(feature = "codec")]
+    {
+        let mut codec = FrameCodec::new();
+        let mut buffer = BytesMut::new();
+        codec.encode(Bytes::from("example data"), &mut buffer)?;
+        buffer[4] ^= 1;
+        assert!(matches!(
+            codec.decode(&mut buffer),
+            Err(ChksumError::FrameDigestMismatch)
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "codec"), ignore)]
+#[test]
+fn frame_length_limit() -> Result<(), Error> {
+    #[cfg(feature = "codec")]
+    {
+        let mut codec = FrameCodec::new().max_frame_length(4);
+        assert_eq!(codec.get_max_frame_length(), 4);
+        let mut buffer = BytesMut::new();
+        codec.encode(Bytes::from("data"), &mut buffer)?;
+        assert!(matches!(
+            codec.encode(Bytes::from("example data"), &mut buffer),
+            Err(ChksumError::FrameTooLong { length: 12 })
+        ));
+
+        // the length is checked before the payload is received
+        let mut input = BytesMut::from(&b"\0\0\0\x0cexample"[..]);
+        assert!(matches!(
+            codec.decode(&mut input),
+            Err(ChksumError::FrameTooLong { length: 12 })
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use std::io::{Error, Write};
+
+use chksum_md5::compat as md5;
+
+#[test]
+fn computed_digests() {
+    let digest = md5::compute(b"example data");
+    assert_eq!(format!("{digest:x}"), "5c71dbb287630d65ca93764c34d9aa0d");
+    assert_eq!(format!("{digest:#x}"), "0x5c71dbb287630d65ca93764c34d9aa0d");
+    assert_eq!(format!("{digest:X}"), "5C71DBB287630D65CA93764C34D9AA0D");
+    assert_eq!(format!("{digest:?}"), "5c71dbb287630d65ca93764c34d9aa0d");
+    assert_eq!(digest[0], 0x5C);
+    assert_eq!(digest.len(), 16);
+
+    let digest = md5::compute("");
+    assert_eq!(format!("{digest:x}"), "d41d8cd98f00b204e9800998ecf8427e");
+}
+
+#[test]
+fn contexts() -> Result<(), Error> {
+    let mut context = md5::Context::new();
+    context.consume(b"example ");
+    context.consume("data");
+    assert_eq!(context.compute(), md5::compute(b"example data"));
+
+    let mut context = md5::Context::new();
+    context.write_all(b"example data")?;
+    assert_eq!(md5::Digest::from(context), md5::compute(b"example data"));
+
+    Ok(())
+}
+
+#[test]
+fn conversions() {
+    let digest = md5::compute(b"example data");
+    let converted = chksum_md5::Digest::from(digest);
+    assert_eq!(converted, chksum_md5::hash(b"example data"));
+    assert_eq!(md5::Digest::from(converted), digest);
+
+    let bytes: [u8; 16] = digest.into();
+    assert_eq!(md5::Digest(bytes), digest);
+}
@@ -0,0 +1,60 @@
+use std::io::Error as IoError;
+#[cfg(feature = "gzip")]
+use std::io::Write;
+
+use chksum_md5::Error as ChksumError;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use chksum_md5::{chksum_decompressed, Compression};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[cfg_attr(not(feature = "gzip"), ignore)]
+#[test]
+fn gzip_content() -> Result<(), Error> {
+    #[cfg(feature = "gzip")]
+    {
+        use flate2::write::GzEncoder;
+
+        let compressed = {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(b"da")?;
+            let mut compressed = encoder.finish()?;
+            // multi-member file like in case of `cat a.gz b.gz`
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(b"ta")?;
+            compressed.extend(encoder.finish()?);
+            compressed
+        };
+
+        let digest = chksum_decompressed(&compressed[..], Compression::Gzip)?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+        let result = chksum_decompressed(&b"not compressed"[..], Compression::Gzip);
+        assert!(matches!(result, Err(ChksumError::Io(_))));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "zstd"), ignore)]
+#[test]
+fn zstd_content() -> Result<(), Error> {
+    #[cfg(feature = "zstd")]
+    {
+        let compressed = zstd::encode_all(&b"data"[..], 0)?;
+
+        let digest = chksum_decompressed(&compressed[..], Compression::Zstd)?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+        let result = chksum_decompressed(&b"not compressed"[..], Compression::Zstd);
+        assert!(matches!(result, Err(ChksumError::Io(_))));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use chksum_md5::crypt::{self, Scheme};
+use chksum_md5::Error as ChksumError;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+}
+
+#[test]
+fn known_hashes() -> Result<(), Error> {
+    let cases = [
+        (
+            "password",
+            "saltsalt",
+            Scheme::Md5,
+            "$1$saltsalt$qjXMvbEw8oaL.CzflDtaK/",
+        ),
+        (
+            "password",
+            "saltsalt",
+            Scheme::Apr1,
+            "$apr1$saltsalt$yAAkm4libquA.ZWLHbSBq/",
+        ),
+        ("password", "abc", Scheme::Md5, "$1$abc$BXBqpb9BZcZhXLgbee.0s/"),
+        ("password", "abc", Scheme::Apr1, "$apr1$abc$mehJE/UcwZsj.w5DYe.b5."),
+        ("password", "", Scheme::Md5, "$1$$I2o9Z7NcvQAKp7wyCTlia0"),
+        ("password", "", Scheme::Apr1, "$apr1$$qjtLUZpoiD4RwXIYf4qVb0"),
+        ("", "12345678", Scheme::Md5, "$1$12345678$xek.CpjQUVgdf/P2N9KQf/"),
+        (
+            "a very long password that spans more than a single block",
+            "xxxxxxxx",
+            Scheme::Apr1,
+            "$apr1$xxxxxxxx$9Z4lP7KHU3kiRd9G6iuXr/",
+        ),
+    ];
+    for (password, salt, scheme, expected) in cases {
+        assert_eq!(crypt::hash_with_salt(password, salt, scheme)?, expected);
+        assert!(crypt::verify(password, expected)?);
+        assert!(!crypt::verify("wrong password", expected)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn generated_salts() -> Result<(), Error> {
+    let salt = crypt::generate_salt();
+    assert_eq!(salt.len(), 8);
+    assert_ne!(salt, crypt::generate_salt());
+
+    let hash = crypt::hash("password", Scheme::Apr1);
+    assert!(hash.starts_with("$apr1$"));
+    assert_eq!(hash.len(), 6 + 8 + 1 + 22);
+    assert!(crypt::verify("password", &hash)?);
+    assert_ne!(hash, crypt::hash("password", Scheme::Apr1));
+
+    Ok(())
+}
+
+#[test]
+fn long_salts_are_truncated_when_verifying() -> Result<(), Error> {
+    assert!(crypt::verify("password", "$1$saltsaltsalt$qjXMvbEw8oaL.CzflDtaK/")?);
+
+    Ok(())
+}
+
+#[test]
+fn invalid_salts() {
+    for salt in ["saltsaltsalt", "salt$", "sa lt"] {
+        assert!(matches!(
+            crypt::hash_with_salt("password", salt, Scheme::Md5),
+            Err(ChksumError::InvalidSalt { .. })
+        ));
+    }
+}
+
+#[test]
+fn invalid_hashes() {
+    let hashes = [
+        "",
+        "qjXMvbEw8oaL.CzflDtaK/",
+        "$5$saltsalt$qjXMvbEw8oaL.CzflDtaK/",
+        "$1$saltsalt",
+        "$1$saltsalt$qjXMvbEw8oaL.CzflDtaK",
+        "$1$saltsalt$qjXMvbEw8oaL.CzflDtaK/=",
+        "$1$salt$salt$qjXMvbEw8oaL.CzflDtaK/",
+    ];
+    for hash in hashes {
+        assert!(
+            matches!(
+                crypt::verify("password", hash),
+                Err(ChksumError::InvalidPasswordHash { .. })
+            ),
+            "{hash}"
+        );
+    }
+}
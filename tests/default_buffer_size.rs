@@ -0,0 +1,42 @@
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_md5::options::{self, BufferSize, Options};
+use chksum_md5::{chksum_with_report, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+// the default is process-wide, so it is changed by a single test of this file
+#[test]
+fn default_buffer_size() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"data")?;
+
+    assert_eq!(options::default_buffer_size(), BufferSize::Adaptive);
+    let adaptive = Options::new();
+
+    options::set_default_buffer_size(BufferSize::Fixed(4 * 1024 * 1024));
+    assert_eq!(BufferSize::default(), BufferSize::Fixed(4 * 1024 * 1024));
+    let report = chksum_with_report(temp_dir.path(), &Options::new())?;
+    assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    assert_eq!(report.statistics().buffer_size, 4 * 1024 * 1024);
+
+    // existing and explicitly configured options keep their strategy
+    let report = chksum_with_report(temp_dir.path(), &adaptive)?;
+    assert!(report.statistics().buffer_size < 4 * 1024 * 1024);
+    let options = Options::new().buffer_size(BufferSize::Fixed(1));
+    let report = chksum_with_report(temp_dir.path(), &options)?;
+    assert_eq!(report.statistics().buffer_size, 1);
+
+    Ok(())
+}
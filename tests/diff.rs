@@ -0,0 +1,65 @@
+use std::io::Error as IoError;
+use std::path::PathBuf;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_md5::{compare_trees, Error as ChksumError, Options};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[test]
+fn structured_diff() -> Result<(), Error> {
+    let a = TempDir::new()?;
+    a.child("same.txt").write_binary(b"data")?;
+    a.child("dir").create_dir_all()?;
+    a.child("dir").child("content.txt").write_binary(b"data")?;
+    a.child("dir").child("size.txt").write_binary(b"data")?;
+    a.child("a.txt").write_binary(b"data")?;
+
+    let b = TempDir::new()?;
+    b.child("same.txt").write_binary(b"data")?;
+    b.child("dir").create_dir_all()?;
+    b.child("dir").child("content.txt").write_binary(b"atad")?;
+    b.child("dir").child("size.txt").write_binary(b"more data")?;
+    b.child("b.txt").write_binary(b"data")?;
+
+    let options = Options::new();
+    let diff = compare_trees(a.path(), b.path(), &options)?;
+    assert_eq!(diff.only_in_a(), [PathBuf::from("a.txt")]);
+    assert_eq!(diff.only_in_b(), [PathBuf::from("b.txt")]);
+    assert_eq!(
+        diff.differs(),
+        [
+            PathBuf::from("dir").join("content.txt"),
+            PathBuf::from("dir").join("size.txt")
+        ]
+    );
+    assert_eq!(diff.identical(), [PathBuf::from("same.txt")]);
+    assert!(!diff.is_identical());
+
+    Ok(())
+}
+
+#[test]
+fn identical_trees() -> Result<(), Error> {
+    let a = TempDir::new()?;
+    a.child("file.txt").write_binary(b"data")?;
+    let b = TempDir::new()?;
+    b.child("file.txt").write_binary(b"data")?;
+
+    let options = Options::new();
+    let diff = compare_trees(a.path(), b.path(), &options)?;
+    assert!(diff.is_identical());
+    assert_eq!(diff.identical(), [PathBuf::from("file.txt")]);
+
+    Ok(())
+}
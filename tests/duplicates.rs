@@ -0,0 +1,75 @@
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+#[cfg(feature = "rayon")]
+use chksum_md5::par_find_duplicates;
+use chksum_md5::{find_duplicates, Error as ChksumError, Options};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+fn tree() -> Result<TempDir, Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"data")?;
+    temp_dir.child("b").create_dir_all()?;
+    temp_dir.child("b").child("c.txt").write_binary(b"data")?;
+    temp_dir.child("b").child("d.txt").write_binary(b"atad")?;
+    temp_dir.child("b").child("e.txt").touch()?;
+    temp_dir.child("f.txt").write_binary(b"other")?;
+    Ok(temp_dir)
+}
+
+#[test]
+fn groups_identical_files() -> Result<(), Error> {
+    let temp_dir = tree()?;
+
+    let options = Options::new();
+    let duplicates = find_duplicates(temp_dir.path(), &options)?;
+    assert_eq!(
+        duplicates,
+        vec![vec![
+            temp_dir.child("a.txt").to_path_buf(),
+            temp_dir.child("b").child("c.txt").to_path_buf(),
+        ]]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn no_duplicates() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"data")?;
+    temp_dir.child("b.txt").write_binary(b"atad")?;
+
+    let options = Options::new();
+    let duplicates = find_duplicates(temp_dir.path(), &options)?;
+    assert!(duplicates.is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "rayon"), ignore)]
+#[test]
+fn parallel_groups_identical_files() -> Result<(), Error> {
+    #[cfg(feature = "rayon")]
+    {
+        let temp_dir = tree()?;
+
+        let options = Options::new();
+        let duplicates = par_find_duplicates(temp_dir.path(), &options)?;
+        assert_eq!(duplicates, find_duplicates(temp_dir.path(), &options)?);
+        assert_eq!(duplicates.len(), 1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use chksum_md5::dyn_hash::DynHash;
+use chksum_md5::hmac::{self, Hmac};
+use chksum_md5::{self as md5, MD5};
+
+#[test]
+fn boxed_hashes() {
+    let mut hashes: Vec<Box<dyn DynHash>> = vec![Box::new(MD5::new()), Box::new(Hmac::new(b"key"))];
+    for hash in &mut hashes {
+        hash.update(b"example ");
+        hash.update(b"data");
+        assert_eq!(hash.output_size(), 16);
+    }
+    assert_eq!(&*hashes[0].digest(), md5::hash(b"example data").as_bytes());
+    assert_eq!(&*hashes[1].digest(), hmac::hash(b"key", b"example data").as_bytes());
+}
+
+#[test]
+fn clone_and_reset() {
+    let mut hash: Box<dyn DynHash> = Box::new(MD5::new());
+    hash.update(b"example ");
+    let mut clone = hash.clone();
+    clone.update(b"data");
+    assert_eq!(&*clone.digest(), md5::hash(b"example data").as_bytes());
+    assert_eq!(&*hash.digest(), md5::hash(b"example ").as_bytes());
+
+    assert_eq!(&*clone.digest_reset(), md5::hash(b"example data").as_bytes());
+    assert_eq!(&*clone.digest(), md5::hash(b"").as_bytes());
+    hash.reset();
+    assert_eq!(&*hash.digest(), md5::hash(b"").as_bytes());
+}
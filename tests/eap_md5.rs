@@ -0,0 +1,74 @@
+use chksum_md5::{eap_md5, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+}
+
+/// Builds an EAP-MD5 `Request` packet with the given identifier, challenge value and name.
+fn request(identifier: u8, value: &[u8], name: &[u8]) -> Vec<u8> {
+    let length = u16::try_from(6 + value.len() + name.len()).unwrap();
+    let mut packet = vec![1, identifier];
+    packet.extend_from_slice(&length.to_be_bytes());
+    packet.push(4);
+    packet.push(u8::try_from(value.len()).unwrap());
+    packet.extend_from_slice(value);
+    packet.extend_from_slice(name);
+    packet
+}
+
+#[test]
+fn parse_and_respond() -> Result<(), Error> {
+    let value: Vec<u8> = (0..16).collect();
+    let packet = request(0x2A, &value, b"server");
+    let challenge = eap_md5::parse_challenge(&packet)?;
+    assert_eq!(challenge.identifier, 0x2A);
+    assert_eq!(challenge.value, value);
+    assert_eq!(challenge.name, b"server");
+
+    let response = eap_md5::response(challenge.identifier, b"password", challenge.value);
+    assert_eq!(response.to_hex_lowercase(), "3e0949a5572c2d338c182b7f6377471d");
+
+    Ok(())
+}
+
+#[test]
+fn trailing_padding_is_ignored() -> Result<(), Error> {
+    let mut packet = request(1, &[0x5A; 16], b"");
+    packet.extend_from_slice(&[0; 20]);
+    let challenge = eap_md5::parse_challenge(&packet)?;
+    assert_eq!(challenge.value, [0x5A; 16]);
+    assert!(challenge.name.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn invalid_packets() {
+    let valid = request(1, &[0x5A; 16], b"server");
+    let mut response = valid.clone();
+    response[0] = 2;
+    let mut identity = valid.clone();
+    identity[4] = 1;
+    let mut long = valid.clone();
+    long[3] += 1;
+    let mut value_size = valid.clone();
+    value_size[5] = 23;
+    let packets = [
+        Vec::new(),
+        valid[..5].to_vec(),
+        valid[..valid.len() - 1].to_vec(),
+        response,
+        identity,
+        long,
+        value_size,
+        request(1, &[], b"server"),
+    ];
+    for packet in packets {
+        assert!(
+            matches!(eap_md5::parse_challenge(&packet), Err(ChksumError::InvalidEapPacket)),
+            "{packet:?}"
+        );
+    }
+}
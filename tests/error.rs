@@ -0,0 +1,108 @@
+use std::io::{Error as IoError, ErrorKind};
+
+use assert_fs::fixture::FixtureError;
+#[cfg(unix)]
+use assert_fs::prelude::PathCreateDir;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_md5::error::Operation;
+use chksum_md5::{chksum_with, Error as ChksumError, Options};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[test]
+fn missing_path() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let path = temp_dir.child("missing.txt");
+
+    let options = Options::new();
+    let error = chksum_with(path.path(), &options).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!("cannot read metadata of {}", path.path().display())
+    );
+    match error {
+        ChksumError::Path {
+            path: error_path,
+            operation,
+            source,
+        } => {
+            assert_eq!(error_path, path.path());
+            assert_eq!(operation, Operation::Metadata);
+            assert_eq!(source.kind(), ErrorKind::NotFound);
+        },
+        error => panic!("unexpected error: {error:?}"),
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn broken_symlink_in_tree() -> Result<(), Error> {
+    use std::os::unix::fs::symlink;
+
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"data")?;
+    temp_dir.child("b").create_dir_all()?;
+    let link = temp_dir.child("b").child("link");
+    symlink(temp_dir.child("missing.txt").path(), link.path())?;
+
+    let options = Options::new();
+    let error = chksum_with(temp_dir.path(), &options).unwrap_err();
+    assert!(matches!(
+        error,
+        ChksumError::Path { ref path, operation: Operation::Metadata, .. } if path == link.path()
+    ));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_cycle() -> Result<(), Error> {
+    use std::os::unix::fs::symlink;
+
+    use chksum_md5::find_duplicates;
+
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"data")?;
+    temp_dir.child("b").create_dir_all()?;
+    let link = temp_dir.child("b").child("link");
+    symlink(temp_dir.path(), link.path())?;
+
+    let options = Options::new();
+    let error = chksum_with(temp_dir.path(), &options).unwrap_err();
+    assert!(matches!(error, ChksumError::SymlinkCycle { ref path } if path == link.path()));
+    let error = find_duplicates(temp_dir.path(), &options).unwrap_err();
+    assert!(matches!(error, ChksumError::SymlinkCycle { ref path } if path == link.path()));
+
+    Ok(())
+}
+
+#[test]
+fn file_only() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let path = temp_dir.child("file.txt");
+    path.write_binary(b"data")?;
+
+    let options = Options::new().file_only(true);
+    let digest = chksum_with(path.path(), &options)?;
+    assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    let error = chksum_with(temp_dir.path(), &options).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!("{} is a directory", temp_dir.path().display())
+    );
+    assert!(matches!(error, ChksumError::IsADirectory { ref path } if path == temp_dir.path()));
+
+    Ok(())
+}
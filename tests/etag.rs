@@ -0,0 +1,89 @@
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_md5::{etag, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+const MIB: usize = 1024 * 1024;
+
+#[test]
+fn multipart_etags() -> Result<(), Error> {
+    let data = b"example data";
+    assert_eq!(etag::s3_multipart(&data[..], 5)?, "d136b75e4bb4e75096c7dd6667fd9be2-3");
+    assert_eq!(etag::s3_multipart(&data[..], 4)?, "77daba33dc4b4cc17e387357c634a645-3");
+    assert_eq!(etag::s3_multipart(&data[..], 12)?, "ee0e86bdb46a9046da76942e807bba7c-1");
+    assert_eq!(etag::s3_multipart(&data[..], 64)?, "ee0e86bdb46a9046da76942e807bba7c-1");
+    assert_eq!(etag::s3_multipart(&b""[..], 5)?, "59adb24ef3cdbe0297f05b395827453f-1");
+
+    let data = vec![0; 12 * MIB];
+    assert_eq!(
+        etag::s3_multipart(&data[..], 8 * MIB as u64)?,
+        "ebe97f2a4738800fe71edbe389c000a6-2"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn verify_files() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let small = temp_dir.child("small.txt");
+    small.write_binary(b"example data")?;
+    let large = temp_dir.child("large.bin");
+    large.write_binary(&vec![0; 12 * MIB])?;
+
+    assert!(etag::verify(small.path(), "5c71dbb287630d65ca93764c34d9aa0d")?);
+    assert!(etag::verify(small.path(), "\"5C71DBB287630D65CA93764C34D9AA0D\"")?);
+    assert!(etag::verify(small.path(), "ee0e86bdb46a9046da76942e807bba7c-1")?);
+    assert!(!etag::verify(small.path(), "8d777f385d3dfec8815d20f7496026dc")?);
+
+    // part sizes of 8 MiB and 5 MiB are inferred from the number of parts
+    assert!(etag::verify(large.path(), "\"ebe97f2a4738800fe71edbe389c000a6-2\"")?);
+    let etag = etag::s3_multipart(&vec![0; 12 * MIB][..], 5 * MIB as u64)?;
+    assert!(etag.ends_with("-3"));
+    assert!(etag::verify(large.path(), &etag)?);
+    assert!(!etag::verify(large.path(), "ebe97f2a4738800fe71edbe389c000a6-3")?);
+    // no part size splits 12 bytes into 13 non-empty parts
+    assert!(!etag::verify(small.path(), "ebe97f2a4738800fe71edbe389c000a6-13")?);
+
+    Ok(())
+}
+
+#[test]
+fn invalid_etags() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_binary(b"example data")?;
+
+    for etag in [
+        "",
+        "5c71dbb2",
+        "5c71dbb287630d65ca93764c34d9aa0d-",
+        "5c71dbb287630d65ca93764c34d9aa0d-0",
+        "5c71dbb287630d65ca93764c34d9aa0d-+1",
+        "x-1",
+    ] {
+        let result = etag::verify(file.path(), etag);
+        assert!(
+            matches!(result, Err(ChksumError::InvalidEtag { etag: invalid }) if invalid == etag),
+            "{etag}"
+        );
+    }
+
+    temp_dir.child("dir").create_dir_all()?;
+    let result = etag::verify(temp_dir.child("dir").path(), "5c71dbb287630d65ca93764c34d9aa0d-1");
+    assert!(matches!(result, Err(ChksumError::IsADirectory { .. })));
+
+    Ok(())
+}
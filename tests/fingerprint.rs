@@ -0,0 +1,68 @@
+use chksum_md5::fingerprint::Fingerprint;
+use chksum_md5::hash_chunks;
+
+#[test]
+fn encoding() {
+    assert_eq!(1u16.fingerprint(), hash_chunks([[0x01, 0x00]]));
+    assert_eq!(1usize.fingerprint(), 1u64.fingerprint());
+    assert_eq!((-1isize).fingerprint(), (-1i64).fingerprint());
+    assert_eq!(true.fingerprint(), 1u8.fingerprint());
+    assert_eq!('a'.fingerprint(), 97u32.fingerprint());
+    assert_eq!("data".fingerprint(), hash_chunks([&4u64.to_le_bytes()[..], b"data"]));
+    assert_eq!(String::from("data").fingerprint(), "data".fingerprint());
+    assert_eq!(
+        vec![1u8, 2].fingerprint(),
+        hash_chunks([&2u64.to_le_bytes()[..], &[1, 2]])
+    );
+    assert_eq!([1u8, 2].fingerprint(), vec![1u8, 2].fingerprint());
+    assert_eq!(None::<u8>.fingerprint(), 0u8.fingerprint());
+    assert_eq!(Some(2u8).fingerprint(), hash_chunks([[1, 2]]));
+    assert_eq!(Box::new(2u8).fingerprint(), 2u8.fingerprint());
+}
+
+#[test]
+fn length_prefixes() {
+    assert_ne!(("ab", "c").fingerprint(), ("a", "bc").fingerprint());
+    assert_ne!(
+        (vec![1u8], vec![2u8]).fingerprint(),
+        (vec![1u8, 2], Vec::<u8>::new()).fingerprint()
+    );
+}
+
+#[cfg_attr(not(feature = "derive"), ignore)]
+#[test]
+fn derive() {
+    #[cfg(feature = "derive")]
+    {
+        use chksum_md5::Hashable;
+
+        #[derive(Hashable)]
+        struct Entry {
+            name: String,
+            size: u64,
+            tags: Vec<String>,
+        }
+
+        #[derive(Hashable)]
+        struct Wrapper<T>(T, bool);
+
+        #[derive(Hashable)]
+        struct Unit;
+
+        let entry = Entry {
+            name: String::from("example"),
+            size: 4,
+            tags: vec![String::from("data")],
+        };
+        assert_eq!(entry.fingerprint(), ("example", 4u64, ["data"]).fingerprint());
+        assert_eq!(
+            Wrapper(entry, true).fingerprint(),
+            (("example", 4u64, ["data"]), true).fingerprint()
+        );
+        assert_eq!(Unit.fingerprint(), ().fingerprint());
+        assert_eq!(
+            Unit.fingerprint().to_hex_lowercase(),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+    }
+}
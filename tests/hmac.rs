@@ -0,0 +1,126 @@
+use chksum_md5::hmac::{self, Hmac};
+use chksum_md5::Error as ChksumError;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[test]
+fn rfc_2202_test_vectors() {
+    let cases: [(&[u8], &[u8], &str); 7] = [
+        (&[0x0B; 16], b"Hi There", "9294727a3638bb1c13f48ef8158bfc9d"),
+        (
+            b"Jefe",
+            b"what do ya want for nothing?",
+            "750c783e6ab0b503eaa86e310a5db738",
+        ),
+        (&[0xAA; 16], &[0xDD; 50], "56be34521d144c88dbb8c733f0e8b3f6"),
+        (
+            &[
+                0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x10, 0x11,
+                0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19,
+            ],
+            &[0xCD; 50],
+            "697eaf0aca3a3aea3a75164746ffaa79",
+        ),
+        (&[0x0C; 16], b"Test With Truncation", "56461ef2342edc00f9bab995690efd4c"),
+        (
+            &[0xAA; 80],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+            "6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd",
+        ),
+        (
+            &[0xAA; 80],
+            b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data",
+            "6f630fad67cda0ee1fb1f562db3aa53e",
+        ),
+    ];
+    for (key, data, expected) in cases {
+        assert_eq!(hmac::hash(key, data).to_hex_lowercase(), expected);
+    }
+}
+
+#[test]
+fn incremental_updates() -> Result<(), Error> {
+    let mut hmac = Hmac::new(b"Jefe");
+    hmac.update(b"what do ya ");
+    hmac.update(b"want for nothing?");
+    assert_eq!(hmac.digest().to_hex_lowercase(), "750c783e6ab0b503eaa86e310a5db738");
+    assert!(hmac.verify("750C783E6AB0B503EAA86E310A5DB738")?);
+    assert!(!hmac.verify(hmac::hash(b"other key", b"what do ya want for nothing?"))?);
+
+    // resetting keeps the key
+    hmac.reset();
+    hmac.update(b"what do ya want for nothing?");
+    assert_eq!(hmac.digest().to_hex_lowercase(), "750c783e6ab0b503eaa86e310a5db738");
+
+    Ok(())
+}
+
+#[test]
+fn debug_redacts_state() {
+    let hmac = Hmac::new(b"key");
+    assert_eq!(format!("{hmac:?}"), "Hmac { .. }");
+}
+
+#[test]
+fn empty_key_and_data() {
+    assert_eq!(Hmac::default().digest(), Hmac::new(b"").digest());
+    assert_eq!(
+        Hmac::default().digest().to_hex_lowercase(),
+        "74e6f7298a9c2d168935f58c001bad88"
+    );
+}
+
+#[cfg_attr(not(feature = "reader"), ignore)]
+#[test]
+fn reader() -> Result<(), Error> {
+    #[cfg(feature = "reader")]
+    {
+        use std::io::Read;
+
+        let mut reader = hmac::reader::new(&b"example data"[..], b"secret");
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        assert_eq!(buffer, b"example data");
+        assert_eq!(reader.digest().to_hex_lowercase(), "e096114728a7f2caa9650419ccd91ba9");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "writer"), ignore)]
+#[test]
+fn writer() -> Result<(), Error> {
+    #[cfg(feature = "writer")]
+    {
+        use std::io::Write;
+
+        let mut writer = hmac::writer::with_hmac(Vec::new(), Hmac::new(b"secret"));
+        writer.write_all(b"example ")?;
+        writer.write_all(b"data")?;
+        assert_eq!(writer.digest().to_hex_lowercase(), "e096114728a7f2caa9650419ccd91ba9");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(all(feature = "reader", feature = "async-runtime-tokio")), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_reader() -> Result<(), Error> {
+    #[cfg(all(feature = "reader", feature = "async-runtime-tokio"))]
+    {
+        use tokio::io::AsyncReadExt;
+
+        let mut reader = hmac::reader::async_new(&b"example data"[..], b"secret");
+        let mut buffer = [0; 5];
+        while reader.read(&mut buffer).await? > 0 {}
+        assert_eq!(reader.digest().to_hex_lowercase(), "e096114728a7f2caa9650419ccd91ba9");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use std::convert::Infallible;
+#[cfg(feature = "http-body")]
+use std::io::{self, ErrorKind};
+#[cfg(feature = "http-body")]
+use std::pin::{pin, Pin};
+#[cfg(feature = "http-body")]
+use std::sync::mpsc;
+#[cfg(feature = "http-body")]
+use std::task::{Context, Poll};
+
+#[cfg(feature = "http-body")]
+use bytes::Bytes;
+#[cfg(feature = "http-body")]
+use chksum_md5::http_body::HashedBody;
+#[cfg(feature = "http-body")]
+use chksum_md5::{hash, Digest};
+#[cfg(feature = "http-body")]
+use http_body::{Body, Frame};
+#[cfg(feature = "http-body")]
+use http_body_util::{BodyExt, Empty, Full};
+
+/// A body yielding a data frame followed by an error.
+#[cfg(feature = "http-body")]
+struct FailingBody {
+    sent: bool,
+}
+
+#[cfg(feature = "http-body")]
+impl Body for FailingBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, io::Error>>> {
+        if self.sent {
+            return Poll::Ready(Some(Err(io::Error::from(ErrorKind::ConnectionReset))));
+        }
+        self.sent = true;
+        Poll::Ready(Some(Ok(Frame::data(Bytes::from("example ")))))
+    }
+}
+
+#[cfg_attr(not(feature = "http-body"), ignore)]
+#[tokio::test]
+async fn digest_on_end_of_stream() -> Result<(), Infallible> {
+    #[cfg(feature = "http-body")]
+    {
+        let (sender, receiver) = mpsc::channel::<Digest>();
+        let mut body = HashedBody::new(Full::new(Bytes::from("example data"))).on_end(move |digest| {
+            sender.send(digest).expect("receiver is alive");
+        });
+        assert_eq!(body.digest(), None);
+        let frame = body.frame().await.expect("body has a frame")?;
+        assert_eq!(frame.into_data().ok(), Some(Bytes::from("example data")));
+        assert!(body.is_end_stream());
+        assert_eq!(body.digest(), Some(hash(b"example data")));
+        assert_eq!(receiver.try_recv().ok(), Some(hash(b"example data")));
+
+        // bodies which are not `Unpin` are pinned to be polled
+        let body = HashedBody::new(Full::new(Bytes::from("example data")).with_trailers(async { None }));
+        let mut body = pin!(body);
+        while body.frame().await.transpose()?.is_some() {}
+        assert_eq!(body.digest(), Some(hash(b"example data")));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "http-body"), ignore)]
+#[tokio::test]
+async fn empty_bodies() {
+    #[cfg(feature = "http-body")]
+    {
+        let mut body = HashedBody::new(Empty::<Bytes>::new());
+        // empty bodies report the end of the stream only after the hash is finished
+        assert!(!body.is_end_stream());
+        assert!(body.frame().await.is_none());
+        assert!(body.is_end_stream());
+        assert_eq!(body.digest(), Some(hash(b"")));
+    }
+}
+
+#[cfg_attr(not(feature = "http-body"), ignore)]
+#[tokio::test]
+async fn failing_bodies() {
+    #[cfg(feature = "http-body")]
+    {
+        let mut body = HashedBody::new(FailingBody { sent: false }).on_end(|_| panic!("body failed"));
+        assert!(matches!(body.frame().await, Some(Ok(_))));
+        assert!(matches!(body.frame().await, Some(Err(_))));
+        assert_eq!(body.digest(), None);
+    }
+}
@@ -0,0 +1,45 @@
+use chksum_md5::http_digest::{self, Qop};
+
+#[test]
+fn rfc_7616_example() {
+    let ha1 = http_digest::ha1("Mufasa", "http-auth@example.org", "Circle of Life");
+    let ha2 = http_digest::ha2("GET", "/dir/index.html");
+    let response = http_digest::response(
+        &ha1,
+        "7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v",
+        Some((Qop::Auth, 1, "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ")),
+        &ha2,
+    );
+    assert_eq!(response, "8ca523f5e9506fed4657c9700eebdbec");
+}
+
+#[test]
+fn rfc_2617_example() {
+    let ha1 = http_digest::ha1("Mufasa", "testrealm@host.com", "Circle Of Life");
+    let ha2 = http_digest::ha2("GET", "/dir/index.html");
+    let nonce = "dcd98b7102dd2f0e8b11d0f600bfb0c093";
+    assert_eq!(
+        http_digest::response(&ha1, nonce, Some((Qop::Auth, 1, "0a4f113b")), &ha2),
+        "6629fae49393a05397450978507c4ef1"
+    );
+    // without the quality of protection, as in RFC 2069
+    assert_eq!(
+        http_digest::response(&ha1, nonce, None, &ha2),
+        "670fd8c2df070c60b045671b8b24ff02"
+    );
+}
+
+#[test]
+fn sessions_with_integrity_protection() {
+    let nonce = "dcd98b7102dd2f0e8b11d0f600bfb0c093";
+    let ha1 = http_digest::ha1("Mufasa", "testrealm@host.com", "Circle Of Life");
+    let ha1 = http_digest::session_ha1(&ha1, nonce, "0a4f113b");
+    assert_eq!(ha1, "5edb191b66dce1584c16cb7e7346fcee");
+    let ha2 = http_digest::ha2_auth_int("POST", "/dir/index.html", b"body");
+    assert_eq!(ha2, "73904f3305e25c215a248d03ce3a3bd6");
+    assert_eq!(
+        http_digest::response(&ha1, nonce, Some((Qop::AuthInt, 10, "0a4f113b")), &ha2),
+        "0ba93fab18bc53e6a9d50010d8c14a9a"
+    );
+    assert_eq!(Qop::AuthInt.to_string(), "auth-int");
+}
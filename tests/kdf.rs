@@ -0,0 +1,100 @@
+use chksum_md5::{kdf, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+}
+
+const SALT: [u8; 8] = [0x78, 0x57, 0x8E, 0x5A, 0x5D, 0x63, 0xCB, 0x06];
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[test]
+fn pbkdf1() -> Result<(), Error> {
+    assert_eq!(
+        hex(&kdf::pbkdf1(b"password", &SALT, 1000, 16)?),
+        "c11246e6b87e77a09ab0643de76e1ea7"
+    );
+    assert_eq!(hex(&kdf::pbkdf1("password", &SALT, 1, 8)?), "095b04db55e31b8d");
+    assert!(kdf::pbkdf1(b"password", &SALT, 1, 0)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn pbkdf1_long_keys() {
+    assert!(matches!(
+        kdf::pbkdf1(b"password", &SALT, 1000, 17),
+        Err(ChksumError::InvalidKeyLength { length: 17 })
+    ));
+}
+
+#[test]
+#[should_panic = "number of iterations must be non-zero"]
+fn pbkdf1_zero_iterations() {
+    let _ = kdf::pbkdf1(b"password", &SALT, 0, 16);
+}
+
+#[test]
+fn evp_bytes_to_key() {
+    // openssl enc -aes-256-cbc -md md5 -pass pass:password -S 0102030405060708 -P
+    let salt = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+    let (key, iv) = kdf::evp_bytes_to_key(b"password", Some(&salt), 32, 16);
+    assert_eq!(
+        hex(&key),
+        "e7b0971e52ca5cc8d0539fb3412f6316f7ba2e6ee293d9f3457b99436b51ce02"
+    );
+    assert_eq!(hex(&iv), "8d450e2ed75a84a923d4eac9fe49226b");
+
+    // openssl enc -des-ede3-cbc -md md5 -pass pass:secret -S a1b2c3d4e5f60718 -P
+    let salt = [0xA1, 0xB2, 0xC3, 0xD4, 0xE5, 0xF6, 0x07, 0x18];
+    let (key, iv) = kdf::evp_bytes_to_key("secret", Some(&salt), 24, 8);
+    assert_eq!(hex(&key), "0f973356e2eec66bacd3397c5d1d787437a6a3417d6f7663");
+    assert_eq!(hex(&iv), "7e2f22c9becba5eb");
+
+    // openssl enc -aes-128-cbc -md md5 -pass pass:password -nosalt -P
+    let (key, iv) = kdf::evp_bytes_to_key(b"password", None, 16, 16);
+    assert_eq!(hex(&key), "5f4dcc3b5aa765d61d8327deb882cf99");
+    assert_eq!(hex(&iv), "2b95990a9151374abd8ff8c5a7a0fe08");
+
+    let (key, iv) = kdf::evp_bytes_to_key(b"password", None, 0, 0);
+    assert!(key.is_empty() && iv.is_empty());
+}
+
+#[test]
+fn p_md5() {
+    assert_eq!(
+        hex(&kdf::p_md5(b"secret", b"labelseed", 40)),
+        concat!(
+            "c9f83dd7c93d4cd66efd480b81cc985c",
+            "6ace462395307437f1081bdddf89384d",
+            "3ec7e41e12bb09e5",
+        )
+    );
+    assert_eq!(hex(&kdf::p_md5(b"", b"", 16)), "608bb4abecd20d0a1c20021758e0ebc7");
+    assert!(kdf::p_md5(b"secret", b"seed", 0).is_empty());
+}
+
+#[test]
+fn p_md5_tls_prf_test_vector() {
+    // the MD5 half of the commonly used TLS 1.0 PRF test vector
+    let secret = [0xAB; 48];
+    let mut seed = b"PRF Testvector".to_vec();
+    seed.extend_from_slice(&[0xCD; 64]);
+    let output = kdf::p_md5(&secret[..24], &seed, 104);
+    assert_eq!(
+        hex(&output),
+        concat!(
+            "500294341c32c132efcc6926557b9886",
+            "94f51d87311c0dbcca302923c7481b60",
+            "e2678ab924a974ddd2db6fd46d804923",
+            "1b66a30e6a5fd5e3772409bbad50dcd5",
+            "3be747d0cafcc63d179d81642bbc637a",
+            "a124543d772fb5780542ce270b4560d3",
+            "94af7173b0254bbc",
+        )
+    );
+}
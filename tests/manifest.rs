@@ -0,0 +1,552 @@
+use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_md5::manifest::{check, create, verify, verify_with, CheckOptions, Manifest, Mode, ParseError, Status};
+use chksum_md5::options::BufferSize;
+use chksum_md5::{hash, Digest, Error as ChksumError, Options};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+fn listing(manifest: &Manifest) -> Vec<(Digest, PathBuf)> {
+    manifest
+        .entries()
+        .iter()
+        .map(|entry| (entry.digest, entry.path.clone()))
+        .collect()
+}
+
+#[test]
+fn read_gnu() -> Result<(), Error> {
+    let data = b"# comment\n\
+        8d777f385d3dfec8815d20f7496026dc  data.txt\n\
+        \n\
+        D41D8CD98F00B204E9800998ECF8427E *dir/empty.bin\r\n\
+        5c71dbb287630d65ca93764c34d9aa0d  name with  spaces";
+    let manifest = Manifest::read_gnu(&data[..])?;
+    let [first, second, third] = manifest.entries() else {
+        panic!("three entries are listed");
+    };
+    assert_eq!(first.digest, hash(b"data"));
+    assert_eq!(first.path, Path::new("data.txt"));
+    assert_eq!(first.mode, Mode::Text);
+    assert_eq!(second.digest, hash(b""));
+    assert_eq!(second.path, Path::new("dir/empty.bin"));
+    assert_eq!(second.mode, Mode::Binary);
+    assert_eq!(third.digest, hash(b"example data"));
+    assert_eq!(third.path, Path::new("name with  spaces"));
+    assert_eq!(manifest.into_entries().len(), 3);
+
+    assert!(Manifest::read_gnu(&b""[..])?.entries().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn escaped_paths() -> Result<(), Error> {
+    let data = b"\\8d777f385d3dfec8815d20f7496026dc  back\\\\slash\\nnew\\rline\n";
+    let manifest = Manifest::read_gnu(&data[..])?;
+    assert_eq!(manifest.entries()[0].path, Path::new("back\\slash\nnew\rline"));
+
+    Ok(())
+}
+
+#[test]
+fn invalid_lines() {
+    let cases: [(&[u8], usize, ParseError); 6] = [
+        (
+            b"8d777f385d3dfec8815d20f7496026d  data.txt",
+            1,
+            ParseError::InvalidDigest,
+        ),
+        (
+            b"# comment\nzz777f385d3dfec8815d20f7496026dc  data.txt",
+            2,
+            ParseError::InvalidDigest,
+        ),
+        (b"8d777f", 1, ParseError::InvalidDigest),
+        (
+            b"8d777f385d3dfec8815d20f7496026dc data.txt",
+            1,
+            ParseError::InvalidSeparator,
+        ),
+        (b"8d777f385d3dfec8815d20f7496026dc  ", 1, ParseError::EmptyPath),
+        (
+            b"\\8d777f385d3dfec8815d20f7496026dc  data\\t",
+            1,
+            ParseError::InvalidEscape,
+        ),
+    ];
+    for (data, expected_line, expected_error) in cases {
+        let error = Manifest::read_gnu(data).unwrap_err();
+        assert!(
+            matches!(error, ChksumError::InvalidLine { line, error } if line == expected_line && error == expected_error),
+            "{error:?}"
+        );
+    }
+}
+
+#[test]
+fn create_and_write_gnu() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("b").create_dir_all()?;
+    temp_dir.child("b").child("c.txt").write_binary(b"data")?;
+    temp_dir.child("a.txt").touch()?;
+    temp_dir.child("d.txt").write_binary(b"example data")?;
+
+    let manifest = create(temp_dir.path(), &Options::new())?;
+    let mut output = Vec::new();
+    manifest.write_gnu(&mut output)?;
+    let expected = "d41d8cd98f00b204e9800998ecf8427e  a.txt\n8d777f385d3dfec8815d20f7496026dc  \
+                    b/c.txt\n5c71dbb287630d65ca93764c34d9aa0d  d.txt\n";
+    assert_eq!(String::from_utf8_lossy(&output), expected);
+    assert_eq!(listing(&Manifest::read_gnu(&output[..])?), listing(&manifest));
+
+    let manifest = create(temp_dir.child("d.txt").path(), &Options::new())?;
+    let mut output = Vec::new();
+    manifest.write_gnu(&mut output)?;
+    assert_eq!(output, b"5c71dbb287630d65ca93764c34d9aa0d  d.txt\n");
+
+    Ok(())
+}
+
+#[cfg_attr(not(unix), ignore)]
+#[test]
+fn write_gnu_escaped_paths() -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("new\nline").write_binary(b"data")?;
+        temp_dir.child("back\\slash").touch()?;
+
+        let manifest = create(temp_dir.path(), &Options::new())?;
+        let mut output = Vec::new();
+        manifest.write_gnu(&mut output)?;
+        let expected =
+            "\\d41d8cd98f00b204e9800998ecf8427e  back\\\\slash\n\\8d777f385d3dfec8815d20f7496026dc  new\\nline\n";
+        assert_eq!(String::from_utf8_lossy(&output), expected);
+        assert_eq!(listing(&Manifest::read_gnu(&output[..])?), listing(&manifest));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn read_bsd() -> Result<(), Error> {
+    let data = b"MD5 (data.txt) = 8d777f385d3dfec8815d20f7496026dc\n\
+        MD5 (odd) = name) = d41d8cd98f00b204e9800998ecf8427e\n\
+        \\MD5 (new\\nline) = 5c71dbb287630d65ca93764c34d9aa0d\n";
+    let manifest = Manifest::read_bsd(&data[..])?;
+    let [first, second, third] = manifest.entries() else {
+        panic!("three entries are listed");
+    };
+    assert_eq!(first.digest, hash(b"data"));
+    assert_eq!(first.path, Path::new("data.txt"));
+    assert_eq!(first.mode, Mode::Binary);
+    assert_eq!(second.digest, hash(b""));
+    assert_eq!(second.path, Path::new("odd) = name"));
+    assert_eq!(third.path, Path::new("new\nline"));
+
+    let error = Manifest::read_bsd(&b"SHA1 (data.txt) = 8d777f385d3dfec8815d20f7496026dc"[..]).unwrap_err();
+    assert!(matches!(
+        error,
+        ChksumError::InvalidLine {
+            line: 1,
+            error: ParseError::InvalidTag
+        }
+    ));
+    let error = Manifest::read_bsd(&b"MD5 (data.txt) 8d777f385d3dfec8815d20f7496026dc"[..]).unwrap_err();
+    assert!(matches!(
+        error,
+        ChksumError::InvalidLine {
+            line: 1,
+            error: ParseError::InvalidSeparator
+        }
+    ));
+    let error = Manifest::read_bsd(&b"MD5 (data.txt) = 8d777f"[..]).unwrap_err();
+    assert!(matches!(
+        error,
+        ChksumError::InvalidLine {
+            line: 1,
+            error: ParseError::InvalidDigest
+        }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn read_mixed_formats() -> Result<(), Error> {
+    let data = b"8d777f385d3dfec8815d20f7496026dc  a.txt\n\
+        MD5 (b.txt) = d41d8cd98f00b204e9800998ecf8427e\n";
+    let manifest = Manifest::read(&data[..])?;
+    let [first, second] = manifest.entries() else {
+        panic!("two entries are listed");
+    };
+    assert_eq!(first.path, Path::new("a.txt"));
+    assert_eq!(first.mode, Mode::Text);
+    assert_eq!(second.path, Path::new("b.txt"));
+    assert_eq!(second.mode, Mode::Binary);
+
+    Ok(())
+}
+
+#[test]
+fn write_bsd() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("b").create_dir_all()?;
+    temp_dir.child("b").child("c.txt").write_binary(b"data")?;
+    temp_dir.child("a.txt").touch()?;
+
+    let manifest = create(temp_dir.path(), &Options::new())?;
+    let mut output = Vec::new();
+    manifest.write_bsd(&mut output)?;
+    let expected = "MD5 (a.txt) = d41d8cd98f00b204e9800998ecf8427e\nMD5 (b/c.txt) = 8d777f385d3dfec8815d20f7496026dc\n";
+    assert_eq!(String::from_utf8_lossy(&output), expected);
+    assert_eq!(listing(&Manifest::read_bsd(&output[..])?), listing(&manifest));
+
+    Ok(())
+}
+
+#[cfg_attr(not(unix), ignore)]
+#[test]
+fn write_bsd_escaped_paths() -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("new\nline").write_binary(b"data")?;
+        temp_dir.child("back\\slash").touch()?;
+
+        let manifest = create(temp_dir.path(), &Options::new())?;
+        let mut output = Vec::new();
+        manifest.write_bsd(&mut output)?;
+        let expected = "\\MD5 (back\\\\slash) = d41d8cd98f00b204e9800998ecf8427e\n\\MD5 (new\\nline) = \
+                        8d777f385d3dfec8815d20f7496026dc\n";
+        assert_eq!(String::from_utf8_lossy(&output), expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn verify_files() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("ok.txt").write_binary(b"data")?;
+    temp_dir.child("changed.txt").write_binary(b"atad")?;
+    temp_dir.child("dir").create_dir_all()?;
+
+    let data = b"8d777f385d3dfec8815d20f7496026dc  ok.txt\n\
+        8d777f385d3dfec8815d20f7496026dc  changed.txt\n\
+        8d777f385d3dfec8815d20f7496026dc  missing.txt\n\
+        8d777f385d3dfec8815d20f7496026dc  dir\n";
+    let manifest = Manifest::read_gnu(&data[..])?;
+    let report = verify(&manifest, temp_dir.path(), &Options::new());
+    assert!(!report.is_ok());
+    assert_eq!(report.ok(), 1);
+    assert_eq!(report.mismatched(), 1);
+    assert_eq!(report.missing(), 1);
+    assert_eq!(report.unreadable(), 1);
+    let failures: Vec<_> = report.failures().map(|failure| failure.path.as_path()).collect();
+    assert_eq!(
+        failures,
+        [Path::new("changed.txt"), Path::new("missing.txt"), Path::new("dir")]
+    );
+    let [ok, changed, missing, dir] = report.results() else {
+        panic!("four entries are checked");
+    };
+    assert!(ok.is_ok());
+    assert_eq!(ok.path, Path::new("ok.txt"));
+    assert!(
+        matches!(changed.status, Status::Mismatch { expected, actual } if expected == hash(b"data") && actual == hash(b"atad"))
+    );
+    assert!(matches!(missing.status, Status::Missing));
+    assert!(matches!(
+        dir.status,
+        Status::Unreadable(ChksumError::IsADirectory { .. })
+    ));
+
+    let manifest = create(temp_dir.path(), &Options::new())?;
+    let report = verify(&manifest, temp_dir.path(), &Options::new());
+    assert!(report.is_ok());
+    assert_eq!(report.ok(), 2);
+    assert_eq!(report.failures().count(), 0);
+
+    Ok(())
+}
+
+#[cfg_attr(not(unix), ignore)]
+#[test]
+fn write_structured() -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        use std::process::Command;
+
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("a,b.txt").write_binary(b"data")?;
+        temp_dir.child("old.txt").touch()?;
+        let status = Command::new("touch")
+            .env("TZ", "UTC")
+            .args(["-t", "200102030405.06"])
+            .arg(temp_dir.child("a,b.txt").path())
+            .status()?;
+        assert!(status.success());
+        let status = Command::new("touch")
+            .env("TZ", "UTC")
+            .args(["-t", "196912312359.59"])
+            .arg(temp_dir.child("old.txt").path())
+            .status()?;
+        assert!(status.success());
+
+        let manifest = create(temp_dir.path(), &Options::new())?;
+        assert_eq!(manifest.entries()[0].size, Some(4));
+
+        let mut output = Vec::new();
+        manifest.write_json(&mut output)?;
+        let expected = concat!(
+            "[\n",
+            r#"  {"path":"a,b.txt","size":4,"digest":"8d777f385d3dfec8815d20f7496026dc","mtime":"2001-02-03T04:05:06Z"},"#,
+            "\n",
+            r#"  {"path":"old.txt","size":0,"digest":"d41d8cd98f00b204e9800998ecf8427e","mtime":"1969-12-31T23:59:59Z"}"#,
+            "\n]\n",
+        );
+        assert_eq!(String::from_utf8_lossy(&output), expected);
+
+        let mut output = Vec::new();
+        manifest.write_csv(&mut output)?;
+        let expected = concat!(
+            "path,size,digest,mtime\n",
+            "\"a,b.txt\",4,8d777f385d3dfec8815d20f7496026dc,2001-02-03T04:05:06Z\n",
+            "old.txt,0,d41d8cd98f00b204e9800998ecf8427e,1969-12-31T23:59:59Z\n",
+        );
+        assert_eq!(String::from_utf8_lossy(&output), expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn write_structured_without_metadata() -> Result<(), Error> {
+    let data = b"8d777f385d3dfec8815d20f7496026dc  say \"hi\".txt\n";
+    let manifest = Manifest::read_gnu(&data[..])?;
+
+    let mut output = Vec::new();
+    manifest.write_json(&mut output)?;
+    let expected = concat!(
+        "[\n",
+        r#"  {"path":"say \"hi\".txt","size":null,"digest":"8d777f385d3dfec8815d20f7496026dc","mtime":null}"#,
+        "\n]\n",
+    );
+    assert_eq!(String::from_utf8_lossy(&output), expected);
+
+    let mut output = Vec::new();
+    manifest.write_csv(&mut output)?;
+    let expected = "path,size,digest,mtime\n\"say \"\"hi\"\".txt\",,8d777f385d3dfec8815d20f7496026dc,\n";
+    assert_eq!(String::from_utf8_lossy(&output), expected);
+
+    let mut output = Vec::new();
+    Manifest::default().write_json(&mut output)?;
+    assert_eq!(output, b"[]\n");
+
+    Ok(())
+}
+
+#[test]
+fn diff() -> Result<(), Error> {
+    let old = b"8d777f385d3dfec8815d20f7496026dc  same.txt\n\
+        8d777f385d3dfec8815d20f7496026dc  changed.txt\n\
+        8d777f385d3dfec8815d20f7496026dc  removed.txt\n";
+    let new = b"MD5 (added.txt) = d41d8cd98f00b204e9800998ecf8427e\n\
+        MD5 (changed.txt) = 5c71dbb287630d65ca93764c34d9aa0d\n\
+        MD5 (same.txt) = 8d777f385d3dfec8815d20f7496026dc\n";
+    let old = Manifest::read(&old[..])?;
+    let new = Manifest::read(&new[..])?;
+
+    let diff = old.diff(&new);
+    assert!(!diff.is_empty());
+    let [added] = diff.added() else {
+        panic!("one entry is added");
+    };
+    assert_eq!(added.path, Path::new("added.txt"));
+    assert_eq!(added.digest, hash(b""));
+    let [removed] = diff.removed() else {
+        panic!("one entry is removed");
+    };
+    assert_eq!(removed.path, Path::new("removed.txt"));
+    let [changed] = diff.changed() else {
+        panic!("one entry is changed");
+    };
+    assert_eq!(changed.path, Path::new("changed.txt"));
+    assert_eq!(changed.old, hash(b"data"));
+    assert_eq!(changed.new, hash(b"example data"));
+
+    let reversed = new.diff(&old);
+    assert_eq!(reversed.added(), diff.removed());
+    assert_eq!(reversed.changed()[0].new, hash(b"data"));
+    assert!(old.diff(&old).is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_create_and_verify() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use chksum_md5::manifest::{async_create, async_verify};
+
+        let temp_dir = TempDir::new()?;
+        for index in 0..16 {
+            temp_dir
+                .child(format!("{index:02}.txt"))
+                .write_binary(format!("{index}").as_bytes())?;
+        }
+        temp_dir.child("dir").create_dir_all()?;
+        temp_dir.child("dir").child("data.txt").write_binary(b"data")?;
+
+        let options = Options::new();
+        let manifest = async_create(temp_dir.path(), &options, 4).await?;
+        assert_eq!(manifest, create(temp_dir.path(), &options)?);
+        assert_eq!(manifest.entries().len(), 17);
+
+        let report = async_verify(&manifest, temp_dir.path(), &options, 3).await;
+        assert_eq!(report.results().len(), 17);
+        assert!(report.is_ok());
+        assert!(report
+            .results()
+            .iter()
+            .zip(manifest.entries())
+            .all(|(result, entry)| result.path == entry.path));
+
+        temp_dir.child("03.txt").write_binary(b"changed")?;
+        let report = async_verify(&manifest, temp_dir.path(), &options, 1).await;
+        assert!(matches!(report.results()[3].status, Status::Mismatch { .. }));
+        assert_eq!(report.ok(), 16);
+        assert_eq!(report.mismatched(), 1);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(unix), ignore)]
+#[test]
+fn round_trip_escaped_paths() -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        let temp_dir = TempDir::new()?;
+        let names = ["trailing\\", "literal\\n", "carriage\rreturn", "\n", "\\\\", "plain"];
+        for name in names {
+            temp_dir.child(name).write_binary(name.as_bytes())?;
+        }
+
+        let manifest = create(temp_dir.path(), &Options::new())?;
+        assert_eq!(manifest.entries().len(), names.len());
+
+        let mut output = Vec::new();
+        manifest.write_gnu(&mut output)?;
+        assert_eq!(listing(&Manifest::read_gnu(&output[..])?), listing(&manifest));
+        assert_eq!(listing(&Manifest::read(&output[..])?), listing(&manifest));
+        // only the plain name is written without escaping
+        let escaped = output
+            .split(|&byte| byte == b'\n')
+            .filter(|line| line.starts_with(b"\\"))
+            .count();
+        assert_eq!(escaped, names.len() - 1);
+
+        let mut output = Vec::new();
+        manifest.write_bsd(&mut output)?;
+        assert_eq!(listing(&Manifest::read_bsd(&output[..])?), listing(&manifest));
+        assert_eq!(listing(&Manifest::read(&output[..])?), listing(&manifest));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn check_modes() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("ok.txt").write_binary(b"data")?;
+
+    let data = b"8d777f385d3dfec8815d20f7496026dc  ok.txt\n\
+        invalid line\n\
+        8d777f385d3dfec8815d20f7496026dc  missing.txt\n";
+
+    let report = check(&data[..], temp_dir.path(), &Options::new(), &CheckOptions::new())?;
+    assert!(!report.is_ok());
+    assert_eq!(report.ok(), 1);
+    assert_eq!(report.missing(), 1);
+    assert_eq!(report.skipped(), 0);
+    assert_eq!(report.malformed(), [2]);
+
+    let check_options = CheckOptions::new().ignore_missing(true);
+    let report = check(&data[..], temp_dir.path(), &Options::new(), &check_options)?;
+    assert!(report.is_ok());
+    assert_eq!(report.ok(), 1);
+    assert_eq!(report.missing(), 0);
+    assert_eq!(report.skipped(), 1);
+    assert!(matches!(report.results()[1].status, Status::Skipped));
+    assert_eq!(report.failures().count(), 0);
+
+    let check_options = CheckOptions::new().strict(true).ignore_missing(true);
+    let error = check(&data[..], temp_dir.path(), &Options::new(), &check_options).unwrap_err();
+    assert!(matches!(
+        error,
+        ChksumError::InvalidLine {
+            line: 2,
+            error: ParseError::InvalidDigest
+        }
+    ));
+
+    let manifest = Manifest::read_gnu(&b"8d777f385d3dfec8815d20f7496026dc  missing.txt\n"[..])?;
+    let report = verify_with(&manifest, temp_dir.path(), &Options::new(), &check_options);
+    assert!(report.is_ok());
+    assert_eq!(report.skipped(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn text_mode() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("crlf.txt").write_binary(b"da\r\nta\r\n\r\r")?;
+    temp_dir.child("lf.txt").write_binary(b"da\nta\n\r\r")?;
+
+    let expected = hash(b"da\nta\n\r\r").to_hex_lowercase();
+    let data = format!("{expected}  crlf.txt\n{expected}  lf.txt\n");
+    let manifest = Manifest::read_gnu(data.as_bytes())?;
+
+    let report = verify(&manifest, temp_dir.path(), &Options::new());
+    assert_eq!(report.ok(), 1);
+    assert!(matches!(report.results()[0].status, Status::Mismatch { .. }));
+
+    let check_options = CheckOptions::new().normalize_text(true);
+    let report = verify_with(&manifest, temp_dir.path(), &Options::new(), &check_options);
+    assert!(report.is_ok());
+
+    // CRLF line endings split between buffers are converted too
+    let options = Options::new().buffer_size(BufferSize::Fixed(1));
+    let report = verify_with(&manifest, temp_dir.path(), &options, &check_options);
+    assert!(report.is_ok());
+
+    // binary mode entries are hashed as they are
+    let manifest = manifest.with_mode(Mode::Binary);
+    let mut output = Vec::new();
+    manifest.write_gnu(&mut output)?;
+    assert_eq!(
+        String::from_utf8_lossy(&output),
+        format!("{expected} *crlf.txt\n{expected} *lf.txt\n")
+    );
+    let report = verify_with(&manifest, temp_dir.path(), &options, &check_options);
+    assert_eq!(report.mismatched(), 1);
+
+    Ok(())
+}
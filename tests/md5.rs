@@ -1,15 +1,52 @@
+use std::borrow::Cow;
+use std::ffi::{CString, OsStr, OsString};
 use std::fs::{read_dir, File};
-use std::io::Error as IoError;
+use std::io::{Cursor, Error as IoError, Read};
+#[cfg(feature = "async-runtime-tokio")]
+use std::pin::Pin;
+#[cfg(unix)]
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+use std::sync::Arc;
 
 use assert_fs::fixture::FixtureError;
 use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild};
 use assert_fs::TempDir;
+#[cfg(feature = "rayon")]
+use chksum_md5::par_chksum_many;
+use chksum_md5::uuid::{NAMESPACE_DNS, NAMESPACE_OID, NAMESPACE_URL, NAMESPACE_X500};
+use chksum_md5::{
+    apop,
+    chksum,
+    chksum_hex,
+    chksum_many,
+    chksum_reader,
+    chksum_seq,
+    content_md5,
+    copy,
+    copy_verified,
+    gravatar_hash,
+    gravatar_url,
+    hash,
+    hash_batch,
+    hash_c_str,
+    hash_chunks,
+    hash_cursor,
+    hash_hex,
+    hash_os_str,
+    uuid_v3,
+    Digest,
+    Error as ChksumError,
+    MD5,
+};
 #[cfg(feature = "async-runtime-tokio")]
-use chksum_md5::async_chksum;
-use chksum_md5::{chksum, Error as ChksumError};
+use chksum_md5::{async_chksum, async_chksum_hex, async_chksum_reader, async_copy};
 #[cfg(feature = "async-runtime-tokio")]
 use tokio::fs::{read_dir as tokio_read_dir, File as TokioFile};
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt};
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, thiserror::Error)]
 enum Error {
     #[error(transparent)]
@@ -565,6 +602,11 @@ fn non_empty_file_as_file() -> Result<(), Error> {
     let digest = chksum(file)?.to_hex_lowercase();
     assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
 
+    let mut file = File::open(child.path())?;
+    let digest = chksum(&mut file)?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    assert_eq!(file.metadata()?.len(), 4);
+
     Ok(())
 }
 
@@ -585,10 +627,712 @@ async fn async_runtime_tokio_non_empty_file_as_file() -> Result<(), Error> {
         let digest = async_chksum(file).await?.to_hex_lowercase();
         assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
 
-        // TODO: missing `&File` implementation
-        // let file = &TokioFile::open(child.path()).await?;
-        // let digest = async_chksum(file).await?.to_hex_lowercase();
-        // assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+        // `&File` of Tokio does not implement `AsyncRead`, so only `&mut File` is accepted
+        let mut file = TokioFile::open(child.path()).await?;
+        let digest = async_chksum(&mut file).await?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(file.metadata().await?.len(), 4);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn smart_pointers_as_bytes() {
+    let data: Box<[u8]> = Box::from(&b"data"[..]);
+    let digest = hash(data).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let data: Rc<[u8]> = Rc::from(&b"data"[..]);
+    let digest = hash(data).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let data: Arc<[u8]> = Arc::from(&b"data"[..]);
+    let digest = hash(data).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let data: Cow<[u8]> = Cow::Borrowed(b"data");
+    let digest = hash(data).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+}
+
+#[test]
+fn in_memory_bytes() -> Result<(), Error> {
+    let data: Vec<u8> = (0..=255).cycle().take(1024 * 1024 + 1).collect();
+    let digest = hash(&data);
+    assert_eq!(chksum(&data[..])?, digest);
+    assert_eq!(chksum(&data)?, digest);
+    assert_eq!(chksum(data)?, digest);
+    assert_eq!(chksum([0u8; 0])?.to_hex_lowercase(), "d41d8cd98f00b204e9800998ecf8427e");
+
+    Ok(())
+}
+
+#[test]
+fn empty_chunks() {
+    let chunks: Vec<Vec<u8>> = Vec::new();
+    let digest = hash_chunks(chunks).to_hex_lowercase();
+    assert_eq!(digest, "d41d8cd98f00b204e9800998ecf8427e");
+
+    let chunks = [&b""[..], &b""[..]];
+    let digest = hash_chunks(chunks).to_hex_lowercase();
+    assert_eq!(digest, "d41d8cd98f00b204e9800998ecf8427e");
+}
+
+#[test]
+fn non_empty_chunks() {
+    let chunks = vec![b"da".to_vec(), Vec::new(), b"ta".to_vec()];
+    let digest = hash_chunks(&chunks).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let chunks = "data".split_inclusive('a');
+    let digest = hash_chunks(chunks).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+}
+
+#[test]
+fn batches() {
+    let inputs: [&[u8]; 0] = [];
+    assert!(hash_batch(&inputs).is_empty());
+
+    let digests = hash_batch(&["", "data"]);
+    assert_eq!(digests[0].to_hex_lowercase(), "d41d8cd98f00b204e9800998ecf8427e");
+    assert_eq!(digests[1].to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+
+    // lengths around the block boundaries, in many groups of lanes
+    let data: Vec<u8> = (0..=255).cycle().take(300).collect();
+    let inputs: Vec<&[u8]> = (0..data.len()).rev().map(|length| &data[..length]).collect();
+    let digests = hash_batch(&inputs);
+    assert_eq!(digests.len(), inputs.len());
+    for (input, digest) in inputs.iter().zip(digests) {
+        assert_eq!(digest, hash(input), "length {}", input.len());
+    }
+}
+
+#[test]
+fn batches_of_random_inputs() {
+    // a linear congruential generator, so lanes of the same group differ in contents and lengths
+    let mut seed = 0x2545_F491_u32;
+    let mut next = move || {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        seed >> 8
+    };
+    let inputs: Vec<Vec<u8>> = (0..200)
+        .map(|_| {
+            let length = next() as usize % 500;
+            (0..length).map(|_| next() as u8).collect()
+        })
+        .collect();
+    let digests = hash_batch(&inputs);
+    for (input, digest) in inputs.iter().zip(digests) {
+        assert_eq!(digest, hash(input), "length {}", input.len());
+    }
+}
+
+#[test]
+fn intermediate_digests() {
+    let data: Vec<u8> = (0..=255).cycle().take(300).collect();
+    let mut md5 = MD5::new();
+    for (index, chunk) in data.chunks(7).enumerate() {
+        md5.update(chunk);
+        let length = (index * 7 + chunk.len()).min(data.len());
+        assert_eq!(md5.digest(), hash(&data[..length]), "length {length}");
+    }
+    assert_eq!(md5.digest(), hash(&data));
+}
+
+#[test]
+fn non_empty_files_as_chain() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let first = {
+        let file = temp_dir.child("first.txt");
+        file.write_binary(b"da")?;
+        file
+    };
+    let second = {
+        let file = temp_dir.child("second.txt");
+        file.write_binary(b"ta")?;
+        file
+    };
+
+    let reader = File::open(first.path())?.chain(File::open(second.path())?);
+    let digest = chksum_reader(reader)?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_non_empty_files_as_chain() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let first = {
+            let file = temp_dir.child("first.txt");
+            file.write_binary(b"da")?;
+            file
+        };
+        let second = {
+            let file = temp_dir.child("second.txt");
+            file.write_binary(b"ta")?;
+            file
+        };
+
+        let reader = TokioFile::open(first.path())
+            .await?
+            .chain(TokioFile::open(second.path()).await?);
+        let digest = async_chksum_reader(reader).await?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn non_empty_file_as_take() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = {
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"data\n")?;
+        file
+    };
+
+    let reader = File::open(file.path())?.take(4);
+    let digest = chksum_reader(reader)?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let mut reader = File::open(file.path())?;
+    let digest = chksum_reader((&mut reader).take(4))?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    let digest = chksum_reader(&mut reader)?.to_hex_lowercase();
+    assert_eq!(digest, "68b329da9893e34099c7d8ad5cb9c940");
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_non_empty_file_as_take() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = {
+            let file = temp_dir.child("file.txt");
+            file.write_binary(b"data\n")?;
+            file
+        };
+
+        let reader = TokioFile::open(file.path()).await?.take(4);
+        let digest = async_chksum_reader(reader).await?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn non_empty_file_as_dyn_read() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = {
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"data")?;
+        file
+    };
+
+    let mut reader = File::open(file.path())?;
+    let reader: &mut dyn Read = &mut reader;
+    let digest = chksum_reader(reader)?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let reader: Box<dyn Read + Send> = Box::new(File::open(file.path())?);
+    let digest = chksum_reader(reader)?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_non_empty_file_as_dyn_async_read() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = {
+            let file = temp_dir.child("file.txt");
+            file.write_binary(b"data")?;
+            file
+        };
+
+        let reader: Box<dyn AsyncRead + Unpin> = Box::new(TokioFile::open(file.path()).await?);
+        let digest = async_chksum_reader(reader).await?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+        let reader: Box<dyn AsyncRead + Unpin + Send> = Box::new(TokioFile::open(file.path()).await?);
+        let digest = tokio::spawn(async_chksum_reader(reader))
+            .await
+            .unwrap()?
+            .to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+        let reader: Pin<Box<dyn AsyncRead + Send>> = Box::pin(TokioFile::open(file.path()).await?);
+        let digest = tokio::spawn(async_chksum_reader(reader))
+            .await
+            .unwrap()?
+            .to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn sequences() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = {
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"ta")?;
+        file
+    };
+
+    let digest = chksum_seq((b"da", File::open(file.path())?))?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let digest = chksum_seq(("d", vec![b'a'], file.path()))?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let digest = chksum_seq(["d", "a", "t", "a"])?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let mut inputs = vec!["da", "ta"];
+    let digest = chksum_seq(inputs.as_mut_slice())?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let digest = chksum_seq(Vec::<&str>::new())?.to_hex_lowercase();
+    assert_eq!(digest, "d41d8cd98f00b204e9800998ecf8427e");
+
+    Ok(())
+}
+
+#[test]
+fn hex_digests() -> Result<(), Error> {
+    assert_eq!(hash_hex(b"data"), "8d777f385d3dfec8815d20f7496026dc");
+    assert_eq!(chksum_hex(b"data")?, "8d777f385d3dfec8815d20f7496026dc");
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_hex_digests() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        assert_eq!(async_chksum_hex(b"data").await?, "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn uuids() {
+    let hex = |uuid: [u8; 16]| uuid.iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+    assert_eq!(
+        hex(uuid_v3(NAMESPACE_DNS, b"python.org")),
+        "6fa459eaee8a3ca4894edb77e160355e"
+    );
+    assert_eq!(
+        hex(uuid_v3(NAMESPACE_URL, b"https://example.com/")),
+        "b9dcdff8af4a365d80430f8361942709"
+    );
+    assert_eq!(hex(uuid_v3(NAMESPACE_OID, b"")), "596b79dc00dd3991a72fd3696c38c64f");
+    assert_eq!(hex(NAMESPACE_X500), "6ba7b8149dad11d180b400c04fd430c8");
+}
+
+#[test]
+fn gravatar_hashes() {
+    let expected = "0bc83cb571cd1c50ba6f3e8a78ef1346";
+    assert_eq!(gravatar_hash("myemailaddress@example.com"), expected);
+    assert_eq!(gravatar_hash("  MyEmailAddress@Example.COM\n"), expected);
+    assert_eq!(
+        gravatar_url("MyEmailAddress@example.com"),
+        "https://www.gravatar.com/avatar/0bc83cb571cd1c50ba6f3e8a78ef1346"
+    );
+}
+
+#[test]
+fn apop_digests() -> Result<(), Error> {
+    let expected = "c4c9334bac560ecc979e58001b3e22fb";
+    assert_eq!(apop("<1896.697170952@dbc.mtview.ca.us>", "tanstaaf")?, expected);
+    assert_eq!(
+        apop(
+            "+OK POP3 server ready <1896.697170952@dbc.mtview.ca.us>\r\n",
+            b"tanstaaf"
+        )?,
+        expected
+    );
+
+    for banner in [
+        "+OK POP3 server ready",
+        "+OK <>",
+        "+OK <1896.697170952",
+        "+OK <1896 697170952@host>",
+        "+OK <<x@host>",
+    ] {
+        assert!(
+            matches!(apop(banner, "tanstaaf"), Err(ChksumError::InvalidApopTimestamp { .. })),
+            "{banner}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn content_md5_headers() -> Result<(), Error> {
+    assert_eq!(hash(b"").to_content_md5(), "1B2M2Y8AsgTpgAmY7PhCfg==");
+    assert_eq!(hash(b"data").to_content_md5(), "jXd/OF09/siBXSD3SWAm3A==");
+    assert_eq!(content_md5(b"example data")?, "XHHbsodjDWXKk3ZMNNmqDQ==");
+
+    assert_eq!(Digest::from_content_md5("jXd/OF09/siBXSD3SWAm3A==")?, hash(b"data"));
+    assert_eq!(Digest::from_content_md5(" 1B2M2Y8AsgTpgAmY7PhCfg==\r\n")?, hash(b""));
+
+    let invalid = [
+        "",
+        "jXd/OF09/siBXSD3SWAm3A",
+        "jXd/OF09/siBXSD3SWAm3B==",
+        "jXd_OF09-siBXSD3SWAm3A==",
+        "jXd/OF09/siBXSD3SWAm3A===",
+        "jXd/OF09/siBXSD3SWAm",
+        "8d777f385d3dfec8815d20f7496026dc",
+    ];
+    for value in invalid {
+        assert!(
+            matches!(
+                Digest::from_content_md5(value),
+                Err(ChksumError::InvalidContentMd5 { .. })
+            ),
+            "{value}"
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "serde"), ignore)]
+#[test]
+fn serializable_values() -> Result<(), Error> {
+    #[cfg(feature = "serde")]
+    {
+        use chksum_md5::hash_serialize;
+
+        #[derive(serde::Serialize)]
+        struct Entry<'a> {
+            id: u16,
+            name: &'a str,
+        }
+
+        let digest = hash_serialize("data")?;
+        assert_eq!(digest, hash_chunks([&4u64.to_le_bytes()[..], b"data"]));
+
+        let entry = Entry { id: 1, name: "data" };
+        let digest = hash_serialize(&entry)?;
+        assert_eq!(
+            digest,
+            hash_chunks([&1u16.to_le_bytes()[..], &4u64.to_le_bytes(), b"data"])
+        );
+        assert_eq!(digest, hash_serialize(&(1u16, String::from("data")))?);
+        assert_ne!(digest, hash_serialize(&(2u16, "data"))?);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "json"), ignore)]
+#[test]
+fn canonical_json() {
+    #[cfg(feature = "json")]
+    {
+        use chksum_md5::hash_json_canonical;
+        use serde_json::{json, Value};
+
+        // examples from RFC 8785
+        let value: Value = serde_json::from_str(
+            r#"{
+                "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+                "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+                "literals": [null, true, false]
+            }"#,
+        )
+        .expect("valid JSON");
+        let canonical = r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#;
+        assert_eq!(hash_json_canonical(&value), hash(canonical));
+
+        let value = json!({
+            "\u{20ac}": "Euro Sign",
+            "\r": "Carriage Return",
+            "\u{fb33}": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "\u{1f600}": "Emoji: Grinning Face",
+            "\u{80}": "Control",
+            "\u{f6}": "Latin Small Letter O With Diaeresis",
+        });
+        let canonical = "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",\"\u{f6}\":\"Latin Small \
+                         Letter O With Diaeresis\",\"\u{20ac}\":\"Euro Sign\",\"\u{1f600}\":\"Emoji: Grinning \
+                         Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}";
+        assert_eq!(hash_json_canonical(&value), hash(canonical));
+
+        let value = json!([0, -0.0, 1e21, 1e20, -1.5e-7, 0.000001, 123456789012u64, -42]);
+        let canonical = "[0,0,1e+21,100000000000000000000,-1.5e-7,0.000001,123456789012,-42]";
+        assert_eq!(hash_json_canonical(&value), hash(canonical));
+    }
+}
+
+#[test]
+fn os_strings() {
+    let digest = hash_os_str(OsStr::new("")).to_hex_lowercase();
+    assert_eq!(digest, "d41d8cd98f00b204e9800998ecf8427e");
+
+    let digest = hash_os_str(OsStr::new("data")).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let digest = hash_os_str(OsString::from("data")).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+}
+
+#[test]
+fn c_strings() {
+    let digest = hash_c_str(c"").to_hex_lowercase();
+    assert_eq!(digest, "d41d8cd98f00b204e9800998ecf8427e");
+
+    let digest = hash_c_str(c"data").to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let digest = hash_c_str(CString::new("data").unwrap()).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+}
+
+#[test]
+fn cursors() {
+    let mut cursor = Cursor::new(b"data".to_vec());
+    let digest = hash_cursor(&mut cursor).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    assert_eq!(cursor.position(), 4);
+
+    let digest = hash_cursor(&mut cursor).to_hex_lowercase();
+    assert_eq!(digest, "d41d8cd98f00b204e9800998ecf8427e");
+
+    let mut cursor = Cursor::new(&b"..data"[..]);
+    cursor.set_position(2);
+    let digest = hash_cursor(&mut cursor).to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    let mut cursor = Cursor::new(&b"data"[..]);
+    cursor.set_position(10);
+    let digest = hash_cursor(&mut cursor).to_hex_lowercase();
+    assert_eq!(digest, "d41d8cd98f00b204e9800998ecf8427e");
+    assert_eq!(cursor.position(), 4);
+}
+
+#[cfg(unix)]
+#[test]
+fn child_process_output() -> Result<(), Error> {
+    let mut child = Command::new("printf")
+        .arg("data")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let digest = chksum_reader(stdout)?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    assert!(child.wait()?.success());
+
+    let mut child = Command::new("sh")
+        .args(["-c", "printf data >&2"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stderr = child.stderr.take().expect("stderr is piped");
+    let digest = chksum_reader(stderr)?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    assert!(child.wait()?.success());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_child_process_output() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let mut child = tokio::process::Command::new("printf")
+            .arg("data")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let digest = async_chksum_reader(stdout).await?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+        assert!(child.wait().await?.success());
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn commands() -> Result<(), Error> {
+    use chksum_md5::chksum_command;
+
+    let (digest, status) = chksum_command(Command::new("printf").arg("data"))?;
+    assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    assert!(status.success());
+
+    let (digest, status) = chksum_command(Command::new("sh").args(["-c", "printf data; exit 3"]))?;
+    assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    assert_eq!(status.code(), Some(3));
+
+    assert!(chksum_command(&mut Command::new("/nonexistent/command")).is_err());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_commands() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use chksum_md5::async_chksum_command;
+        use tokio::process::Command;
+
+        let (digest, status) = async_chksum_command(Command::new("printf").arg("data")).await?;
+        assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert!(status.success());
+
+        let (digest, status) = async_chksum_command(Command::new("sh").args(["-c", "printf data; exit 3"])).await?;
+        assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(status.code(), Some(3));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn many_files() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let first = {
+        let file = temp_dir.child("first.txt");
+        file.write_binary(b"data")?;
+        file
+    };
+    let second = temp_dir.child("second.txt");
+
+    let digests = chksum_many([first.path(), second.path(), temp_dir.path()]);
+    assert_eq!(digests.len(), 3);
+    assert_eq!(
+        digests[0].as_ref().unwrap().to_hex_lowercase(),
+        "8d777f385d3dfec8815d20f7496026dc"
+    );
+    assert!(digests[1].is_err());
+    assert_eq!(
+        digests[2].as_ref().unwrap().to_hex_lowercase(),
+        "8d777f385d3dfec8815d20f7496026dc"
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "rayon"), ignore)]
+#[test]
+fn par_many_files() -> Result<(), Error> {
+    #[cfg(feature = "rayon")]
+    {
+        let temp_dir = TempDir::new()?;
+        let files = (0..16)
+            .map(|index| {
+                let file = temp_dir.child(format!("{index}.txt"));
+                file.write_binary(b"data")?;
+                Ok(file.to_path_buf())
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let digests = par_chksum_many(&files);
+        assert_eq!(digests.len(), 16);
+        for digest in digests {
+            assert_eq!(digest?.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        }
+
+        let digests = par_chksum_many([temp_dir.child("missing.txt").path()]);
+        assert!(digests[0].is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn copy_and_hash() -> Result<(), Error> {
+    let mut destination = Vec::new();
+    let (length, digest) = copy(&b"data"[..], &mut destination)?;
+    assert_eq!(length, 4);
+    assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    assert_eq!(destination, b"data");
+
+    let mut destination = Vec::new();
+    let (length, digest) = copy(&b""[..], &mut destination)?;
+    assert_eq!(length, 0);
+    assert_eq!(digest.to_hex_lowercase(), "d41d8cd98f00b204e9800998ecf8427e");
+    assert!(destination.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn copy_verified_files() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let source = temp_dir.child("source.txt");
+    source.write_binary(b"example data")?;
+    let destination = temp_dir.child("destination.txt");
+    destination.write_binary(b"old data which is longer")?;
+
+    let copied = copy_verified(source.path(), destination.path(), true)?;
+    assert_eq!(copied.length, 12);
+    assert_eq!(copied.source.to_hex_lowercase(), "5c71dbb287630d65ca93764c34d9aa0d");
+    assert_eq!(copied.destination, Some(copied.source));
+    assert!(copied.is_verified());
+    assert_eq!(std::fs::read(destination.path())?, b"example data");
+
+    let copied = copy_verified(source.path(), destination.path(), false)?;
+    assert_eq!(copied.source.to_hex_lowercase(), "5c71dbb287630d65ca93764c34d9aa0d");
+    assert_eq!(copied.destination, None);
+    assert!(!copied.is_verified());
+
+    let result = copy_verified(temp_dir.path(), destination.path(), true);
+    assert!(matches!(result, Err(ChksumError::IsADirectory { .. })));
+
+    let result = copy_verified(source.path(), source.path(), true);
+    assert!(matches!(result, Err(ChksumError::SameFile { .. })));
+    let result = copy_verified(source.path(), temp_dir.child(".").child("source.txt").path(), true);
+    assert!(matches!(result, Err(ChksumError::SameFile { .. })));
+    let link = temp_dir.child("link.txt");
+    std::fs::hard_link(source.path(), link.path())?;
+    let result = copy_verified(source.path(), link.path(), true);
+    assert!(matches!(result, Err(ChksumError::SameFile { .. })));
+    assert_eq!(std::fs::read(source.path())?, b"example data");
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_copy_and_hash() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let mut destination = Vec::new();
+        let (length, digest) = async_copy(&b"data"[..], &mut destination).await?;
+        assert_eq!(length, 4);
+        assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(destination, b"data");
     }
 
     Ok(())
@@ -0,0 +1,75 @@
+use std::io::{Cursor, Error as IoError};
+
+use chksum_md5::merkle::{build, leaf, MerkleTree};
+use chksum_md5::{hash_chunks, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[test]
+fn root() -> Result<(), Error> {
+    let tree = build(Cursor::new(b"example data"), 4, 2)?;
+    let leaves = [leaf(b"exam"), leaf(b"ple "), leaf(b"data")];
+    assert_eq!(tree.leaves(), leaves);
+    assert_eq!(tree.chunk_size(), 4);
+    assert_eq!(tree.fanout(), 2);
+
+    let left = hash_chunks([&[0x01][..], leaves[0].as_bytes(), leaves[1].as_bytes()]);
+    let right = hash_chunks([&[0x01][..], leaves[2].as_bytes()]);
+    let root = hash_chunks([&[0x01][..], left.as_bytes(), right.as_bytes()]);
+    assert_eq!(*tree.root(), root);
+    assert_eq!(MerkleTree::from_leaves(leaves.to_vec(), 4, 2), tree);
+
+    Ok(())
+}
+
+#[test]
+fn proofs() -> Result<(), Error> {
+    let data = b"example data";
+    for fanout in 2..6 {
+        let tree = build(Cursor::new(data), 2, fanout)?;
+        for (index, chunk) in data.chunks(2).enumerate() {
+            let proof = tree.proof(index).expect("chunk exists");
+            assert_eq!(proof.index(), index);
+            assert!(proof.verify(chunk, tree.root()));
+            assert!(!proof.verify(b"xx", tree.root()));
+        }
+        assert!(tree.proof(data.len() / 2).is_none());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn empty() -> Result<(), Error> {
+    let tree = build(Cursor::new(b""), 4, 2)?;
+    assert_eq!(*tree.root(), leaf(b""));
+    let proof = tree.proof(0).expect("chunk exists");
+    assert!(proof.verify(b"", tree.root()));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic = "fanout must be at least two"]
+fn invalid_fanout() {
+    let _ = build(Cursor::new(b"data"), 4, 1);
+}
+
+#[cfg_attr(not(feature = "rayon"), ignore)]
+#[test]
+fn par_build() -> Result<(), Error> {
+    #[cfg(feature = "rayon")]
+    {
+        let data = vec![0x5A; 10_000];
+        let tree = build(Cursor::new(&data), 64, 3)?;
+        assert_eq!(chksum_md5::merkle::par_build(&data, 64, 3), tree);
+    }
+
+    Ok(())
+}
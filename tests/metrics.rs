@@ -0,0 +1,167 @@
+#[cfg(feature = "metrics")]
+use std::collections::HashMap;
+use std::io::Error as IoError;
+#[cfg(feature = "metrics")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "metrics")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "metrics")]
+use ::metrics::{Counter, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+use assert_fs::fixture::FixtureError;
+#[cfg(feature = "metrics")]
+use assert_fs::prelude::{FileWriteBin, PathChild};
+#[cfg(feature = "metrics")]
+use assert_fs::TempDir;
+use chksum_md5::Error as ChksumError;
+#[cfg(feature = "metrics")]
+use chksum_md5::{chksum, chksum_with, metrics, Options};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// Collects the recorded values of counters and histograms by their keys.
+#[cfg(feature = "metrics")]
+#[derive(Default)]
+struct TestRecorder {
+    counters: Mutex<HashMap<Key, Arc<AtomicU64>>>,
+    histograms: Mutex<HashMap<Key, Arc<Values>>>,
+}
+
+#[cfg(feature = "metrics")]
+#[derive(Default)]
+struct Values(Mutex<Vec<f64>>);
+
+#[cfg(feature = "metrics")]
+impl HistogramFn for Values {
+    fn record(&self, value: f64) {
+        self.0.lock().unwrap().push(value);
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl TestRecorder {
+    fn counter(&self, name: &str, labels: &[(&'static str, &'static str)]) -> u64 {
+        let labels: Vec<_> = labels.iter().map(::metrics::Label::from).collect();
+        let key = Key::from_parts(name.to_owned(), labels);
+        let counters = self.counters.lock().unwrap();
+        counters.get(&key).map_or(0, |counter| counter.load(Ordering::Relaxed))
+    }
+
+    fn histogram(&self, name: &str) -> Vec<f64> {
+        let key = Key::from_name(name.to_owned());
+        let histograms = self.histograms.lock().unwrap();
+        histograms
+            .get(&key)
+            .map_or_else(Vec::new, |values| values.0.lock().unwrap().clone())
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl Recorder for TestRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        let mut counters = self.counters.lock().unwrap();
+        Counter::from_arc(Arc::clone(counters.entry(key.clone()).or_default()))
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+        let mut histograms = self.histograms.lock().unwrap();
+        Histogram::from_arc(Arc::clone(histograms.entry(key.clone()).or_default()))
+    }
+}
+
+#[cfg_attr(not(feature = "metrics"), ignore)]
+#[test]
+fn bytes_and_files() -> Result<(), Error> {
+    #[cfg(feature = "metrics")]
+    {
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("a.txt").write_binary(b"data")?;
+        temp_dir.child("b.txt").write_binary(b"example data")?;
+
+        let recorder = TestRecorder::default();
+        let result: Result<(), Error> = ::metrics::with_local_recorder(&recorder, || {
+            metrics::describe();
+            chksum(b"example data")?;
+            chksum_with(temp_dir.path(), &Options::new())?;
+            Ok(())
+        });
+        result?;
+
+        assert_eq!(recorder.counter(metrics::FILES, &[]), 2);
+        assert_eq!(recorder.counter(metrics::BYTES, &[]), 12 + 4 + 12);
+        assert!(recorder.histogram(metrics::THROUGHPUT).len() <= 2);
+        assert!(recorder
+            .histogram(metrics::THROUGHPUT)
+            .iter()
+            .all(|&throughput| throughput > 0.0));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "metrics"), ignore)]
+#[test]
+fn bytes_of_dropped_hashes() {
+    #[cfg(feature = "metrics")]
+    {
+        use chksum_md5::MD5;
+
+        let recorder = TestRecorder::default();
+        ::metrics::with_local_recorder(&recorder, || {
+            let mut hash = MD5::new();
+            hash.update(b"data");
+            let mut clone = hash.clone();
+            clone.update(b"example data");
+            drop(hash);
+            assert_eq!(recorder.counter(metrics::BYTES, &[]), 4);
+            drop(clone);
+
+            let mut hash = MD5::new();
+            for _ in 0..1024 {
+                hash.update([0; 1024]);
+            }
+            // the bytes are recorded once they fill a batch, before the hash is dropped
+            assert_eq!(recorder.counter(metrics::BYTES, &[]), 4 + 12 + 1024 * 1024);
+        });
+    }
+}
+
+#[cfg_attr(not(feature = "metrics"), ignore)]
+#[test]
+fn errors_by_kind() -> Result<(), Error> {
+    #[cfg(feature = "metrics")]
+    {
+        let temp_dir = TempDir::new()?;
+
+        let recorder = TestRecorder::default();
+        ::metrics::with_local_recorder(&recorder, || {
+            assert!(chksum_with(temp_dir.child("missing").path(), &Options::new()).is_err());
+            assert!(chksum_with(temp_dir.path(), &Options::new().file_only(true)).is_err());
+            assert!(chksum(temp_dir.child("missing").path()).is_err());
+        });
+
+        assert_eq!(recorder.counter(metrics::ERRORS, &[("kind", "io")]), 2);
+        assert_eq!(recorder.counter(metrics::ERRORS, &[("kind", "is_a_directory")]), 1);
+        assert_eq!(recorder.counter(metrics::FILES, &[]), 0);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+#[cfg(feature = "mmap")]
+use std::fs::File;
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+#[cfg(feature = "mmap")]
+use assert_fs::prelude::{FileWriteBin, PathChild};
+#[cfg(feature = "mmap")]
+use assert_fs::TempDir;
+#[cfg(feature = "mmap")]
+use chksum_md5::mmap::{chksum, chksum_file_with_threshold, chksum_with_threshold};
+use chksum_md5::Error as ChksumError;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[cfg_attr(not(feature = "mmap"), ignore)]
+#[test]
+fn small_file_below_threshold() -> Result<(), Error> {
+    #[cfg(feature = "mmap")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"data")?;
+
+        let digest = chksum(file.path())?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "mmap"), ignore)]
+#[test]
+fn file_above_threshold() -> Result<(), Error> {
+    #[cfg(feature = "mmap")]
+    {
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"data")?;
+
+        let digest = chksum_with_threshold(file.path(), 0)?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+        let digest = chksum_file_with_threshold(&File::open(file.path())?, 0)?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "mmap"), ignore)]
+#[test]
+fn empty_file_and_directory() -> Result<(), Error> {
+    #[cfg(feature = "mmap")]
+    {
+        let temp_dir = TempDir::new()?;
+        let digest = chksum_with_threshold(temp_dir.path(), 0)?.to_hex_lowercase();
+        assert_eq!(digest, "d41d8cd98f00b204e9800998ecf8427e");
+
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"")?;
+        let digest = chksum_with_threshold(file.path(), 0)?.to_hex_lowercase();
+        assert_eq!(digest, "d41d8cd98f00b204e9800998ecf8427e");
+    }
+
+    Ok(())
+}
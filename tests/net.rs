@@ -0,0 +1,127 @@
+use std::io::{Error as IoError, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::thread;
+use std::time::Duration;
+
+#[cfg(feature = "async-runtime-tokio")]
+use chksum_md5::net::async_chksum_tcp;
+#[cfg(all(unix, feature = "async-runtime-tokio"))]
+use chksum_md5::net::async_chksum_unix;
+use chksum_md5::net::chksum_tcp;
+#[cfg(unix)]
+use chksum_md5::net::chksum_unix;
+use chksum_md5::Error as ChksumError;
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::AsyncWriteExt;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[test]
+fn tcp_stream_until_eof() -> Result<(), Error> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = listener.local_addr()?;
+    let handle = thread::spawn(move || -> Result<(), IoError> {
+        let mut stream = TcpStream::connect(address)?;
+        stream.write_all(b"da")?;
+        stream.write_all(b"ta")?;
+        stream.shutdown(Shutdown::Write)
+    });
+
+    let (stream, _) = listener.accept()?;
+    let digest = chksum_tcp(&stream, None)?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    handle.join().expect("writer thread panicked")?;
+
+    Ok(())
+}
+
+#[test]
+fn tcp_stream_read_timeout() -> Result<(), Error> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = listener.local_addr()?;
+    let _peer = TcpStream::connect(address)?;
+
+    let (stream, _) = listener.accept()?;
+    let result = chksum_tcp(&stream, Some(Duration::from_millis(50)));
+    assert!(matches!(result, Err(ChksumError::Io(_))));
+    assert_eq!(stream.read_timeout()?, None);
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_tcp_stream_until_eof() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let handle = tokio::spawn(async move {
+            let mut stream = tokio::net::TcpStream::connect(address).await?;
+            stream.write_all(b"data").await?;
+            stream.shutdown().await
+        });
+
+        let (mut stream, _) = listener.accept().await?;
+        let digest = async_chksum_tcp(&mut stream, None).await?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+        handle.await.expect("writer task panicked")?;
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_tcp_stream_read_timeout() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let _peer = tokio::net::TcpStream::connect(address).await?;
+
+        let (mut stream, _) = listener.accept().await?;
+        let result = async_chksum_tcp(&mut stream, Some(Duration::from_millis(50))).await;
+        assert!(matches!(result, Err(ChksumError::Io(_))));
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn unix_stream_until_eof() -> Result<(), Error> {
+    let (mut writer, reader) = UnixStream::pair()?;
+    writer.write_all(b"data")?;
+    writer.shutdown(Shutdown::Write)?;
+
+    let digest = chksum_unix(&reader, Some(Duration::from_secs(1)))?.to_hex_lowercase();
+    assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_unix_stream_until_eof() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let (mut writer, mut reader) = tokio::net::UnixStream::pair()?;
+        writer.write_all(b"data").await?;
+        writer.shutdown().await?;
+
+        let digest = async_chksum_unix(&mut reader, None).await?.to_hex_lowercase();
+        assert_eq!(digest, "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+#[cfg(feature = "object-store")]
+use chksum_md5::object_store::async_chksum;
+use chksum_md5::Error as ChksumError;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[cfg(feature = "object-store")]
+    #[error(transparent)]
+    ObjectStore(#[from] object_store::Error),
+}
+
+#[cfg_attr(not(feature = "object-store"), ignore)]
+#[tokio::test]
+async fn in_memory_object() -> Result<(), Error> {
+    #[cfg(feature = "object-store")]
+    {
+        use object_store::memory::InMemory;
+        use object_store::path::Path;
+        use object_store::ObjectStore;
+
+        let store = InMemory::new();
+        let location = Path::from("data.txt");
+        store.put(&location, b"data".to_vec().into()).await?;
+
+        let (length, digest) = async_chksum(&store, &location).await?;
+        assert_eq!(length, 4);
+        assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+
+        let store: &dyn ObjectStore = &store;
+        let (_, digest) = async_chksum(store, &location).await?;
+        assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "object-store"), ignore)]
+#[tokio::test]
+async fn missing_object() {
+    #[cfg(feature = "object-store")]
+    {
+        use object_store::memory::InMemory;
+        use object_store::path::Path;
+
+        let store = InMemory::new();
+        let result = async_chksum(&store, &Path::from("missing.txt")).await;
+        assert!(matches!(result, Err(ChksumError::Io(_))));
+    }
+}
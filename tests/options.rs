@@ -0,0 +1,452 @@
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_md5::options::{BufferSize, Options, MAX_ADAPTIVE_BUFFER_SIZE, MIN_BUFFER_SIZE};
+use chksum_md5::{chksum, chksum_with, chksum_with_report, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+fn tree() -> Result<TempDir, Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"da")?;
+    temp_dir.child("b").create_dir_all()?;
+    temp_dir.child("b").child("c.txt").write_binary(b"t")?;
+    temp_dir.child("b").child("d.txt").touch()?;
+    temp_dir.child("e.txt").write_binary(b"a")?;
+    Ok(temp_dir)
+}
+
+#[test]
+fn default_options_match_chksum() -> Result<(), Error> {
+    let temp_dir = tree()?;
+
+    let options = Options::new();
+    let digest = chksum_with(temp_dir.path(), &options)?;
+    assert_eq!(digest, chksum(temp_dir.path())?);
+    assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+
+    Ok(())
+}
+
+#[test]
+fn report_statistics() -> Result<(), Error> {
+    let temp_dir = tree()?;
+
+    let options = Options::new();
+    let report = chksum_with_report(temp_dir.path(), &options)?;
+    assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    let statistics = report.statistics();
+    assert_eq!(statistics.files, 4);
+    assert_eq!(statistics.directories, 2);
+    assert_eq!(statistics.bytes, 4);
+
+    Ok(())
+}
+
+#[test]
+fn fixed_buffer_size() -> Result<(), Error> {
+    let temp_dir = tree()?;
+
+    let options = Options::new().buffer_size(BufferSize::Fixed(1));
+    let report = chksum_with_report(temp_dir.path(), &options)?;
+    assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    assert_eq!(report.statistics().buffer_size, 1);
+
+    Ok(())
+}
+
+#[test]
+fn adaptive_buffer_size() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let small = temp_dir.child("small.bin");
+    small.write_binary(b"data")?;
+    let large = temp_dir.child("large.bin");
+    large.write_binary(&vec![0; 2 * MAX_ADAPTIVE_BUFFER_SIZE])?;
+
+    let size = BufferSize::Adaptive.resolve(&small.path().metadata()?);
+    assert!(size >= MIN_BUFFER_SIZE);
+    assert!(size < MAX_ADAPTIVE_BUFFER_SIZE);
+
+    let size = BufferSize::Adaptive.resolve(&large.path().metadata()?);
+    assert!(size >= MAX_ADAPTIVE_BUFFER_SIZE);
+
+    let options = Options::new();
+    let report = chksum_with_report(temp_dir.path(), &options)?;
+    assert_eq!(report.statistics().buffer_size, size);
+    assert_eq!(report.statistics().bytes, 2 * MAX_ADAPTIVE_BUFFER_SIZE as u64 + 4);
+
+    Ok(())
+}
+
+#[cfg_attr(not(all(feature = "sparse", target_os = "linux")), ignore)]
+#[test]
+fn sparse_file() -> Result<(), Error> {
+    #[cfg(all(feature = "sparse", target_os = "linux"))]
+    {
+        use std::fs::OpenOptions;
+        use std::io::{Seek, SeekFrom, Write};
+
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.child("sparse.bin");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(path.path())?;
+        file.write_all(b"head")?;
+        file.seek(SeekFrom::Start(16 * 1024 * 1024))?;
+        file.write_all(b"tail")?;
+        file.set_len(32 * 1024 * 1024)?;
+        drop(file);
+
+        let digest = chksum(path.path())?;
+
+        let options = Options::new().sparse(true);
+        let report = chksum_with_report(path.path(), &options)?;
+        assert_eq!(*report.digest(), digest);
+        assert_eq!(report.statistics().bytes, 32 * 1024 * 1024);
+        assert!(report.statistics().sparse_bytes <= 32 * 1024 * 1024 - 8);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "indicatif"), ignore)]
+#[test]
+fn progress_bars() -> Result<(), Error> {
+    #[cfg(feature = "indicatif")]
+    {
+        use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget};
+
+        let temp_dir = tree()?;
+
+        let bar = ProgressBar::hidden();
+        let options = Options::new().progress(bar.clone());
+        let digest = chksum_with(temp_dir.path(), &options)?;
+        assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(bar.position(), 4);
+        assert!(bar.message().ends_with("e.txt"));
+        assert_eq!(options.clone(), options);
+        assert_ne!(Options::new().progress(bar), options);
+
+        let multi = MultiProgress::with_draw_target(ProgressDrawTarget::hidden());
+        let options = Options::new().progress(multi);
+        let digest = chksum_with(temp_dir.path(), &options)?;
+        assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "io-uring"), ignore)]
+#[test]
+fn io_uring() -> Result<(), Error> {
+    #[cfg(feature = "io-uring")]
+    {
+        use chksum_md5::hash;
+
+        let temp_dir = tree()?;
+        let options = Options::new().io_uring(true);
+        let report = chksum_with_report(temp_dir.path(), &options)?;
+        assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(report.statistics().files, 4);
+        assert_eq!(report.statistics().bytes, 4);
+
+        // more chunks than queued reads
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let file = temp_dir.child("large.bin");
+        file.write_binary(&data)?;
+        for buffer_size in [1, 7, 256, 1000, 4096] {
+            let options = options.clone().buffer_size(BufferSize::Fixed(buffer_size));
+            let report = chksum_with_report(file.path(), &options)?;
+            assert_eq!(*report.digest(), hash(&data));
+            assert_eq!(report.statistics().bytes, 1000);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "direct-io"), ignore)]
+#[test]
+fn direct_io() -> Result<(), Error> {
+    #[cfg(feature = "direct-io")]
+    {
+        use chksum_md5::hash;
+
+        let temp_dir = tree()?;
+        let options = Options::new().direct_io(true);
+        let report = chksum_with_report(temp_dir.path(), &options)?;
+        assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(report.statistics().files, 4);
+        assert_eq!(report.statistics().bytes, 4);
+
+        // lengths which are and aren't multiples of the alignment
+        for length in [4096, 8192, 10000] {
+            let data: Vec<u8> = (0..=255).cycle().take(length).collect();
+            let file = temp_dir.child("large.bin");
+            file.write_binary(&data)?;
+            for buffer_size in [1, 4096, 5000, 16384] {
+                let options = options.clone().buffer_size(BufferSize::Fixed(buffer_size));
+                let report = chksum_with_report(file.path(), &options)?;
+                assert_eq!(*report.digest(), hash(&data));
+                assert_eq!(report.statistics().bytes, length as u64);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn tuned_buffer_size() -> Result<(), Error> {
+    use chksum_md5::hash;
+
+    let temp_dir = TempDir::new()?;
+    let data: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+    let file = temp_dir.child("large.bin");
+    file.write_binary(&data)?;
+
+    let buffer_size = BufferSize::Tuned { min: 1000, max: 2000 };
+    let size = buffer_size.resolve(&file.path().metadata()?);
+    assert!((1000..=2000).contains(&size));
+
+    for (min, max) in [(1, 1), (1, 7), (100, 1000), (4096, 1024 * 1024), (1000, 1)] {
+        let options = Options::new().buffer_size(BufferSize::Tuned { min, max });
+        let report = chksum_with_report(file.path(), &options)?;
+        assert_eq!(*report.digest(), hash(&data));
+        assert_eq!(report.statistics().bytes, 100_000);
+        let buffer_size = report.statistics().buffer_size;
+        assert!((min..=max.max(min)).contains(&buffer_size), "buffer size {buffer_size}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn read_ahead() -> Result<(), Error> {
+    use chksum_md5::hash;
+
+    let temp_dir = tree()?;
+    let options = Options::new().read_ahead(true);
+    let report = chksum_with_report(temp_dir.path(), &options)?;
+    assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+
+    // more chunks than buffers
+    let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+    let file = temp_dir.child("large.bin");
+    file.write_binary(&data)?;
+    for buffer_size in [1, 7, 256, 999, 4096] {
+        let options = options.clone().buffer_size(BufferSize::Fixed(buffer_size));
+        let report = chksum_with_report(file.path(), &options)?;
+        assert_eq!(*report.digest(), hash(&data));
+        assert_eq!(report.statistics().bytes, 1000);
+        assert_eq!(report.statistics().files, 1);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn vectored_reads() -> Result<(), Error> {
+    use chksum_md5::hash;
+
+    let temp_dir = tree()?;
+    let options = Options::new().vectored_reads(true);
+    let report = chksum_with_report(temp_dir.path(), &options)?;
+    assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+
+    // more chunks than buffers
+    let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+    let file = temp_dir.child("large.bin");
+    file.write_binary(&data)?;
+    for buffer_size in [1, 7, 100, 256, 4096] {
+        let options = options.clone().buffer_size(BufferSize::Fixed(buffer_size));
+        let report = chksum_with_report(file.path(), &options)?;
+        assert_eq!(*report.digest(), hash(&data));
+        assert_eq!(report.statistics().bytes, 1000);
+        assert_eq!(report.statistics().files, 1);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(unix), ignore)]
+#[test]
+fn error_policy() -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        use std::io::ErrorKind;
+        use std::os::unix::fs::symlink;
+
+        use chksum_md5::error::Operation;
+        use chksum_md5::options::OnError;
+
+        let temp_dir = tree()?;
+        let link = temp_dir.child("b").child("link");
+        symlink(temp_dir.child("missing.txt").path(), link.path())?;
+
+        let options = Options::new();
+        assert!(chksum_with(temp_dir.path(), &options).is_err());
+
+        let options = Options::new().on_error(OnError::Skip);
+        let report = chksum_with_report(temp_dir.path(), &options)?;
+        assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(report.statistics().files, 4);
+        assert_eq!(report.statistics().skipped, 1);
+        assert!(report.skipped().is_empty());
+
+        let options = Options::new().on_error(OnError::Collect);
+        let report = chksum_with_report(temp_dir.path(), &options)?;
+        assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(report.statistics().skipped, 1);
+        let [skipped] = report.skipped() else {
+            panic!("one entry is skipped");
+        };
+        assert_eq!(skipped.path, link.path());
+        assert_eq!(skipped.operation, Operation::Metadata);
+        assert_eq!(skipped.kind, ErrorKind::NotFound);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(unix), ignore)]
+#[test]
+fn dedup_hardlinks() -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        use std::fs::hard_link;
+
+        use chksum_md5::{hash, hash_chunks};
+
+        let links = TempDir::new()?;
+        links.child("a.txt").write_binary(b"data")?;
+        hard_link(links.child("a.txt").path(), links.child("b.txt").path())?;
+        links.child("c.txt").write_binary(b"data")?;
+
+        let copies = TempDir::new()?;
+        copies.child("a.txt").write_binary(b"data")?;
+        copies.child("b.txt").write_binary(b"data")?;
+        copies.child("c.txt").write_binary(b"data")?;
+
+        let options = Options::new().dedup_hardlinks(true);
+        let report = chksum_with_report(links.path(), &options)?;
+        let digest = hash(b"data");
+        let expected = hash_chunks([digest.as_bytes(), digest.as_bytes(), digest.as_bytes()]);
+        assert_eq!(*report.digest(), expected);
+        assert_eq!(report.statistics().files, 3);
+        assert_eq!(report.statistics().hardlinks, 1);
+        assert_eq!(report.statistics().bytes, 8);
+        assert_eq!(chksum_with(copies.path(), &options)?, expected);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(all(feature = "unicode-normalization", target_os = "linux")), ignore)]
+#[test]
+fn normalize_unicode() -> Result<(), Error> {
+    #[cfg(all(feature = "unicode-normalization", target_os = "linux"))]
+    {
+        let temp_dir = TempDir::new()?;
+        // decomposed "é" sorts before "f", while the precomposed one sorts after it
+        temp_dir.child("e\u{301}.txt").write_binary(b"ta")?;
+        temp_dir.child("f.txt").write_binary(b"da")?;
+
+        let options = Options::new();
+        let digest = chksum_with(temp_dir.path(), &options)?;
+        assert_eq!(digest, chksum_md5::hash(b"tada"));
+
+        let options = Options::new().normalize_unicode(true);
+        let digest = chksum_with(temp_dir.path(), &options)?;
+        assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(windows), ignore)]
+#[test]
+fn long_paths() -> Result<(), Error> {
+    #[cfg(windows)]
+    {
+        use std::fs;
+
+        let temp_dir = TempDir::new()?;
+        // directories are created through the canonical extended-length form
+        let mut path = temp_dir.path().canonicalize()?;
+        path.extend(std::iter::repeat_n("directory-with-a-long-name", 16));
+        fs::create_dir_all(&path)?;
+        fs::write(path.join("file.txt"), b"data")?;
+        assert!(path.as_os_str().len() > 260);
+
+        let options = Options::new();
+        let digest = chksum_with(temp_dir.path(), &options)?;
+        assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        let duplicates = chksum_md5::find_duplicates(temp_dir.path(), &options)?;
+        assert!(duplicates.is_empty());
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(unix), ignore)]
+#[test]
+fn special_files() -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::symlink;
+
+        use chksum_md5::options::SpecialFiles;
+
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("a.txt").write_binary(b"data")?;
+        let link = temp_dir.child("zero");
+        symlink("/dev/zero", link.path())?;
+
+        let options = Options::new().special_files(SpecialFiles::Skip);
+        let report = chksum_with_report(temp_dir.path(), &options)?;
+        assert_eq!(report.digest().to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+        assert_eq!(report.statistics().special_files, 1);
+
+        let options = Options::new().special_files(SpecialFiles::Error);
+        let error = chksum_with(temp_dir.path(), &options).unwrap_err();
+        assert!(matches!(error, ChksumError::SpecialFile { ref path } if path == link.path()));
+
+        let options = Options::new().special_files(SpecialFiles::ReadLimit(4));
+        let digest = chksum_with(temp_dir.path(), &options)?;
+        assert_eq!(digest, chksum_md5::hash(b"data\0\0\0\0"));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_blocking_offload() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use chksum_md5::{async_chksum_with, async_chksum_with_report};
+
+        let temp_dir = tree()?;
+
+        let options = Options::new();
+        let digest = async_chksum_with(temp_dir.path(), &options).await?;
+        assert_eq!(digest, chksum_with(temp_dir.path(), &options)?);
+        let report = async_chksum_with_report(temp_dir.path(), &options).await?;
+        assert_eq!(report, chksum_with_report(temp_dir.path(), &options)?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+#[cfg(feature = "rayon")]
+use assert_fs::prelude::{FileWriteBin, PathChild};
+#[cfg(feature = "rayon")]
+use assert_fs::TempDir;
+use chksum_md5::Error as ChksumError;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[cfg(feature = "rayon")]
+    #[error(transparent)]
+    Pool(#[from] rayon::ThreadPoolBuildError),
+}
+
+#[cfg_attr(not(feature = "rayon"), ignore)]
+#[test]
+fn spawner() -> Result<(), Error> {
+    #[cfg(feature = "rayon")]
+    {
+        use std::sync::{Arc, Mutex};
+        use std::thread;
+
+        use chksum_md5::pool::Pool;
+        use chksum_md5::{find_duplicates, hash, merkle, par_chksum_many_in, par_find_duplicates_in, Options};
+
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let pool = {
+            let names = Arc::clone(&names);
+            Pool::with_spawner(3, move |index, run| {
+                let name = format!("scan-{index}");
+                names.lock().expect("lock is not poisoned").push(name.clone());
+                thread::Builder::new().name(name).spawn(run).map(|_| ())
+            })?
+        };
+        let mut names = names.lock().expect("lock is not poisoned").clone();
+        names.sort();
+        assert_eq!(names, ["scan-0", "scan-1", "scan-2"]);
+
+        let temp_dir = TempDir::new()?;
+        let first = temp_dir.child("first.txt");
+        first.write_binary(b"data")?;
+        let second = temp_dir.child("second.txt");
+        second.write_binary(b"data")?;
+        let digests = par_chksum_many_in(
+            &pool,
+            [first.path(), second.path(), temp_dir.child("missing.txt").path()],
+        );
+        assert_eq!(digests.len(), 3);
+        assert_eq!(*digests[0].as_ref().expect("file is hashed"), hash(b"data"));
+        assert_eq!(*digests[1].as_ref().expect("file is hashed"), hash(b"data"));
+        assert!(digests[2].is_err());
+
+        let options = Options::new();
+        let duplicates = par_find_duplicates_in(&pool, temp_dir.path(), &options)?;
+        assert_eq!(duplicates, find_duplicates(temp_dir.path(), &options)?);
+        assert_eq!(duplicates.len(), 1);
+
+        let data = vec![0x5A; 10_000];
+        assert_eq!(
+            merkle::par_build_in(&pool, &data, 64, 3),
+            merkle::par_build(&data, 64, 3)
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "rayon"), ignore)]
+#[test]
+fn thread_pool() -> Result<(), Error> {
+    #[cfg(feature = "rayon")]
+    {
+        use chksum_md5::pool::Pool;
+        use chksum_md5::{hash, par_chksum_many_in};
+        use rayon::ThreadPoolBuilder;
+
+        let pool = Pool::from(ThreadPoolBuilder::new().num_threads(1).build()?);
+        let temp_dir = TempDir::new()?;
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"data")?;
+        let digests = par_chksum_many_in(&pool.clone(), vec![file.path(); 10]);
+        for digest in digests {
+            assert_eq!(digest?, hash(b"data"));
+        }
+    }
+
+    Ok(())
+}
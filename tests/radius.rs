@@ -0,0 +1,79 @@
+use chksum_md5::{radius, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+}
+
+/// The Request Authenticator of the `Access-Request` example of RFC 2865.
+const REQUEST_AUTHENTICATOR: [u8; 16] = [
+    0x0F, 0x40, 0x3F, 0x94, 0x73, 0x97, 0x80, 0x57, 0xBD, 0x83, 0xD5, 0xCB, 0x98, 0xF4, 0x22, 0x7A,
+];
+
+const SECRET: &[u8] = b"xyzzy5461";
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[test]
+fn rfc_2865_response_authenticator() {
+    let attributes = [
+        0x06, 0x06, 0x00, 0x00, 0x00, 0x01, 0x0F, 0x06, 0x00, 0x00, 0x00, 0x00, 0x0E, 0x06, 0xC0, 0xA8, 0x01, 0x03,
+    ];
+    let authenticator = radius::response_authenticator(2, 0, 38, &REQUEST_AUTHENTICATOR, &attributes, SECRET);
+    assert_eq!(hex(&authenticator), "86fe220e7624ba2a1005f6bf9b55e0b2");
+}
+
+#[test]
+fn rfc_2865_user_password() -> Result<(), Error> {
+    let hidden = radius::hide_password(b"arctangent", SECRET, &REQUEST_AUTHENTICATOR)?;
+    assert_eq!(hex(&hidden), "0dbe708d93d413ce3196e43f782a0aee");
+    assert_eq!(
+        radius::unhide_password(&hidden, SECRET, &REQUEST_AUTHENTICATOR)?,
+        b"arctangent"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn multiple_blocks() -> Result<(), Error> {
+    let hidden = radius::hide_password(&[b'a'; 20], SECRET, &REQUEST_AUTHENTICATOR)?;
+    assert_eq!(
+        hex(&hidden),
+        "0dad729893db15ca3e83855e194b6b8fc48650054efc306de24db5c35f64a884"
+    );
+    assert_eq!(
+        radius::unhide_password(&hidden, SECRET, &REQUEST_AUTHENTICATOR)?,
+        [b'a'; 20]
+    );
+
+    let hidden = radius::hide_password(&[b'a'; 128], SECRET, &REQUEST_AUTHENTICATOR)?;
+    assert_eq!(hidden.len(), 128);
+    assert_eq!(
+        radius::unhide_password(&hidden, SECRET, &REQUEST_AUTHENTICATOR)?,
+        [b'a'; 128]
+    );
+
+    let hidden = radius::hide_password(b"", SECRET, &REQUEST_AUTHENTICATOR)?;
+    assert_eq!(hidden.len(), 16);
+    assert!(radius::unhide_password(&hidden, SECRET, &REQUEST_AUTHENTICATOR)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn invalid_lengths() {
+    assert!(matches!(
+        radius::hide_password(&[b'a'; 129], SECRET, &REQUEST_AUTHENTICATOR),
+        Err(ChksumError::InvalidRadiusPassword { length: 129 })
+    ));
+    for length in [0, 15, 17, 144] {
+        assert!(matches!(
+            radius::unhide_password(&vec![0; length], SECRET, &REQUEST_AUTHENTICATOR),
+            Err(ChksumError::InvalidRadiusPassword { .. })
+        ));
+    }
+}
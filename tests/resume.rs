@@ -0,0 +1,91 @@
+use std::fs::OpenOptions;
+use std::io::{Error as IoError, Write};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_md5::resume::{ResumeState, CHECKPOINT_INTERVAL};
+use chksum_md5::{chksum, hash, hash_batch, hash_file_resumable, resume, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[test]
+fn padding() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    for length in [0, 1, 55, 56, 63, 64, 65, 119, 120, 1000, 1024 * 1024 + 13] {
+        let data: Vec<u8> = (0..length).map(|index| (index % 251) as u8).collect();
+        file.write_binary(&data)?;
+
+        let digest = hash_file_resumable(file.path(), |_| panic!("no checkpoint is expected"))?;
+        assert_eq!(digest, hash(&data), "length {length}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn random_inputs() -> Result<(), Error> {
+    // the resumable and the batch hashing share the compression function, which is checked against the hash crate
+    let mut seed = 0x9E37_79B9_u32;
+    let mut next = move || {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        seed >> 8
+    };
+    let inputs: Vec<Vec<u8>> = (0..64)
+        .map(|_| {
+            let length = next() as usize % 4096;
+            (0..length).map(|_| next() as u8).collect()
+        })
+        .collect();
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    for (input, digest) in inputs.iter().zip(hash_batch(&inputs)) {
+        let expected = chksum_hash_md5::hash(input).to_hex_lowercase();
+        assert_eq!(digest.to_hex_lowercase(), expected, "length {}", input.len());
+        file.write_binary(input)?;
+        let digest = hash_file_resumable(file.path(), |_| panic!("no checkpoint is expected"))?;
+        assert_eq!(digest.to_hex_lowercase(), expected, "length {}", input.len());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn resume_from_checkpoint() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    let length = usize::try_from(CHECKPOINT_INTERVAL).unwrap() + 100;
+    let data: Vec<u8> = (0..length).map(|index| (index % 251) as u8).collect();
+    file.write_binary(&data)?;
+
+    let mut states = Vec::new();
+    let digest = hash_file_resumable(file.path(), |state| states.push(state.to_bytes()))?;
+    assert_eq!(digest, chksum(file.path())?);
+    assert_eq!(states.len(), 1);
+
+    let state = ResumeState::from_bytes(&states[0]).expect("state is valid");
+    assert_eq!(state.offset(), CHECKPOINT_INTERVAL);
+    assert_eq!(resume(file.path(), &state, |_| {})?, digest);
+
+    // the file identity changes when it is modified
+    OpenOptions::new().append(true).open(file.path())?.write_all(b"data")?;
+    let error = resume(file.path(), &state, |_| {}).unwrap_err();
+    assert!(matches!(error, ChksumError::ResumeMismatch { ref path } if path == file.path()));
+
+    Ok(())
+}
+
+#[test]
+fn invalid_state() {
+    assert!(ResumeState::from_bytes(b"").is_none());
+    assert!(ResumeState::from_bytes(&[0; 61]).is_none());
+}
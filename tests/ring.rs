@@ -0,0 +1,61 @@
+use chksum_md5::hash;
+use chksum_md5::ring::{self, Ring};
+
+const KEYS: [&str; 8] = [
+    "user:42", "user:1", "user:2", "user:3", "user:4", "user:5", "user:6", "user:7",
+];
+
+#[test]
+fn buckets() {
+    assert_eq!(ring::bucket("user:42", 16), 14);
+    assert_eq!(ring::bucket("user:1", 10), 8);
+    assert_eq!(ring::bucket("user:2", 10), 3);
+    assert_eq!(ring::bucket("user:42", 1), 0);
+    assert_eq!(u128::from(hash(b"")), 0xD41D8CD98F00B204E9800998ECF8427E);
+}
+
+#[test]
+#[should_panic = "number of buckets must be non-zero"]
+fn zero_buckets() {
+    let _ = ring::bucket("user:42", 0);
+}
+
+#[test]
+fn rings() {
+    let nodes = ["10.0.0.1:11211", "10.0.0.2:11211", "10.0.0.3:11211"];
+
+    let ring = Ring::builder().node(nodes[0], 0).node(nodes[1], 1).build();
+    let assigned: Vec<_> = KEYS.iter().filter_map(|key| ring.get(key).copied()).collect();
+    assert_eq!(assigned, [0, 1, 0, 0, 0, 0, 1, 0]);
+    assert_eq!(ring.nodes(), [0, 1]);
+    assert_eq!(ring.len(), 2);
+
+    // keys move only to the added node
+    let ring = Ring::builder()
+        .node(nodes[0], 0)
+        .node(nodes[1], 1)
+        .node(nodes[2], 2)
+        .build();
+    let assigned: Vec<_> = KEYS.iter().filter_map(|key| ring.get(key).copied()).collect();
+    assert_eq!(assigned, [0, 2, 2, 0, 2, 0, 1, 0]);
+
+    let ring = Ring::builder()
+        .replicas(1)
+        .node(nodes[0], 0)
+        .node(nodes[1], 1)
+        .node(nodes[2], 2)
+        .build();
+    let assigned: Vec<_> = KEYS.iter().filter_map(|key| ring.get(key).copied()).collect();
+    assert_eq!(assigned, [1, 1, 0, 0, 2, 2, 1, 2]);
+}
+
+#[test]
+fn empty_rings() {
+    let ring = Ring::<()>::builder().build();
+    assert!(ring.is_empty());
+    assert_eq!(ring.get("user:42"), None);
+
+    let ring = Ring::builder().replicas(0).node("10.0.0.1:11211", ()).build();
+    assert_eq!(ring.len(), 1);
+    assert_eq!(ring.get("user:42"), None);
+}
@@ -0,0 +1,21 @@
+#[cfg(feature = "md-5")]
+use chksum_md5::{hash, Digest};
+#[cfg(feature = "md-5")]
+use md5::digest::Output;
+#[cfg(feature = "md-5")]
+use md5::{Digest as _, Md5};
+
+#[cfg_attr(not(feature = "md-5"), ignore)]
+#[test]
+fn conversions() {
+    #[cfg(feature = "md-5")]
+    {
+        let output = Md5::digest(b"example data");
+        let digest = Digest::from(output);
+        assert_eq!(digest, hash(b"example data"));
+        assert_eq!(Output::<Md5>::from(digest), output);
+
+        let output: Output<Md5> = hash(b"").into();
+        assert_eq!(output, Md5::new().finalize());
+    }
+}
@@ -0,0 +1,135 @@
+use std::fs;
+use std::io::{self, Error as IoError, Read};
+use std::path::Path;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use chksum_md5::sidecar::sidecar_path;
+use chksum_md5::{hash, verify_sidecar, write_with_sidecar, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[test]
+fn sidecar_path_appends_extension() {
+    assert_eq!(sidecar_path("dir/file.tar.gz"), Path::new("dir/file.tar.gz.md5"));
+}
+
+#[test]
+fn bare_digest() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("file.bin").write_binary(b"data")?;
+    temp_dir
+        .child("file.bin.md5")
+        .write_str("8D777F385D3DFEC8815D20F7496026DC\r\n")?;
+
+    let verification = verify_sidecar(temp_dir.child("file.bin").path())?;
+    assert!(verification.is_ok());
+    assert_eq!(verification.expected, hash(b"data"));
+    assert_eq!(verification.actual, hash(b"data"));
+
+    Ok(())
+}
+
+#[test]
+fn checksum_file_formats() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("file.bin").write_binary(b"data")?;
+
+    temp_dir
+        .child("file.bin.md5")
+        .write_str("8d777f385d3dfec8815d20f7496026dc *some/other/name.bin\n")?;
+    assert!(verify_sidecar(temp_dir.child("file.bin").path())?.is_ok());
+
+    temp_dir
+        .child("file.bin.md5")
+        .write_str("MD5 (file.bin) = 5c71dbb287630d65ca93764c34d9aa0d\n")?;
+    let verification = verify_sidecar(temp_dir.child("file.bin").path())?;
+    assert!(!verification.is_ok());
+    assert_eq!(verification.expected, hash(b"example data"));
+    assert_eq!(verification.actual, hash(b"data"));
+
+    // entries of checksum files with multiple entries are matched by the file name
+    temp_dir
+        .child("file.bin.md5")
+        .write_str("5c71dbb287630d65ca93764c34d9aa0d  other.bin\n8d777f385d3dfec8815d20f7496026dc  dist/file.bin\n")?;
+    assert!(verify_sidecar(temp_dir.child("file.bin").path())?.is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn invalid_sidecars() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("file.bin").write_binary(b"data")?;
+
+    let result = verify_sidecar(temp_dir.child("file.bin").path());
+    assert!(matches!(result, Err(ChksumError::Path { path, .. }) if path == temp_dir.child("file.bin.md5").path()));
+
+    temp_dir
+        .child("file.bin.md5")
+        .write_str("8d777f385d3dfec8815d20f74960\n")?;
+    let result = verify_sidecar(temp_dir.child("file.bin").path());
+    assert!(matches!(result, Err(ChksumError::InvalidDigest(_))));
+
+    temp_dir
+        .child("file.bin.md5")
+        .write_str("5c71dbb287630d65ca93764c34d9aa0d  other.bin\n8d777f385d3dfec8815d20f7496026dc  another.bin\n")?;
+    let result = verify_sidecar(temp_dir.child("file.bin").path());
+    assert!(matches!(result, Err(ChksumError::NotInSidecar { path }) if path == temp_dir.child("file.bin").path()));
+
+    Ok(())
+}
+
+#[test]
+fn write_file_with_sidecar() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    file.write_binary(b"old data")?;
+
+    let digest = write_with_sidecar(file.path(), &b"example data"[..])?;
+    assert_eq!(digest, hash(b"example data"));
+    assert_eq!(fs::read(file.path())?, b"example data");
+    assert_eq!(
+        fs::read_to_string(temp_dir.child("file.bin.md5").path())?,
+        "5c71dbb287630d65ca93764c34d9aa0d  file.bin\n"
+    );
+    assert!(verify_sidecar(file.path())?.is_ok());
+    // temporary files are renamed into place
+    assert_eq!(fs::read_dir(temp_dir.path())?.count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn failed_write_leaves_no_files() -> Result<(), Error> {
+    struct Failing(usize);
+
+    impl Read for Failing {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            if self.0 == 0 {
+                return Err(io::Error::other("broken input"));
+            }
+            self.0 -= 1;
+            buffer[0] = b'x';
+            Ok(1)
+        }
+    }
+
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+
+    let result = write_with_sidecar(file.path(), Failing(16));
+    assert!(matches!(result, Err(ChksumError::Io(error)) if error.to_string() == "broken input"));
+    assert_eq!(fs::read_dir(temp_dir.path())?.count(), 0);
+
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use std::convert::Infallible;
+#[cfg(feature = "tower")]
+use std::pin::Pin;
+#[cfg(feature = "tower")]
+use std::task::{Context, Poll};
+
+#[cfg(feature = "tower")]
+use bytes::Bytes;
+#[cfg(feature = "tower")]
+use chksum_md5::tower::{ContentMd5Layer, Mode};
+#[cfg(feature = "tower")]
+use http::header::{HeaderValue, InvalidHeaderValue};
+#[cfg(feature = "tower")]
+use http::{HeaderMap, Request, Response, StatusCode};
+#[cfg(feature = "tower")]
+use http_body::{Body, Frame};
+#[cfg(feature = "tower")]
+use http_body_util::{BodyExt, Full};
+#[cfg(feature = "tower")]
+use tower::{service_fn, Layer, ServiceExt};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[cfg(feature = "tower")]
+    #[error(transparent)]
+    Header(#[from] InvalidHeaderValue),
+    #[error(transparent)]
+    Infallible(#[from] Infallible),
+}
+
+#[cfg(feature = "tower")]
+async fn echo(request: Request<Full<Bytes>>) -> Result<Response<Full<Bytes>>, Infallible> {
+    let body = request.into_body().collect().await?.to_bytes();
+    Ok(Response::new(Full::new(body)))
+}
+
+/// A body of unknown size streaming the chunks.
+#[cfg(feature = "tower")]
+struct Chunks(Vec<&'static str>);
+
+#[cfg(feature = "tower")]
+impl Body for Chunks {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        let chunks = &mut self.get_mut().0;
+        let chunk = (!chunks.is_empty()).then(|| chunks.remove(0));
+        Poll::Ready(chunk.map(|chunk| Ok(Frame::data(Bytes::from(chunk)))))
+    }
+}
+
+#[cfg(feature = "tower")]
+fn request(body: &'static str, content_md5: Option<&'static str>) -> Result<Request<Full<Bytes>>, Error> {
+    let mut request = Request::new(Full::new(Bytes::from(body)));
+    if let Some(content_md5) = content_md5 {
+        request.headers_mut().insert("content-md5", content_md5.parse()?);
+    }
+    Ok(request)
+}
+
+#[cfg_attr(not(feature = "tower"), ignore)]
+#[tokio::test]
+async fn buffered_responses() -> Result<(), Error> {
+    #[cfg(feature = "tower")]
+    {
+        let service = ContentMd5Layer::new().layer(service_fn(echo));
+
+        let response = service.clone().oneshot(request("example data", None)?).await?;
+        assert_eq!(response.headers()["content-md5"], "XHHbsodjDWXKk3ZMNNmqDQ==");
+        let body = response.into_body().collect().await?.to_bytes();
+        assert_eq!(body, "example data");
+
+        // empty bodies are passed without the header
+        let response = service.oneshot(request("", None)?).await?;
+        assert!(!response.headers().contains_key("content-md5"));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "tower"), ignore)]
+#[tokio::test]
+async fn trailer_responses() -> Result<(), Error> {
+    #[cfg(feature = "tower")]
+    {
+        let service = ContentMd5Layer::new().mode(Mode::Trailer).layer(service_fn(echo));
+        let response = service.oneshot(request("example data", None)?).await?;
+        assert_eq!(response.headers()["trailer"], "Content-MD5");
+        assert!(!response.headers().contains_key("content-md5"));
+        let collected = response.into_body().collect().await?;
+        let trailers = collected.trailers().expect("body has trailers");
+        assert_eq!(trailers["content-md5"], "XHHbsodjDWXKk3ZMNNmqDQ==");
+        assert_eq!(collected.to_bytes(), "example data");
+
+        // the value is added to the trailers of the body
+        let service = ContentMd5Layer::new()
+            .mode(Mode::Trailer)
+            .layer(service_fn(|_: Request<Full<Bytes>>| {
+                async {
+                    let mut trailers = HeaderMap::new();
+                    trailers.insert("x-trailer", HeaderValue::from_static("value"));
+                    let body = Full::new(Bytes::from("example data")).with_trailers(async { Some(Ok(trailers)) });
+                    Ok::<_, Infallible>(Response::new(body))
+                }
+            }));
+        let response = service.oneshot(request("", None)?).await?;
+        let collected = response.into_body().collect().await?;
+        let trailers = collected.trailers().expect("body has trailers");
+        assert_eq!(trailers["x-trailer"], "value");
+        assert_eq!(trailers["content-md5"], "XHHbsodjDWXKk3ZMNNmqDQ==");
+        assert_eq!(collected.to_bytes(), "example data");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "tower"), ignore)]
+#[tokio::test]
+async fn validated_requests() -> Result<(), Error> {
+    #[cfg(feature = "tower")]
+    {
+        let service = ContentMd5Layer::new().validate_requests(true).layer(service_fn(echo));
+
+        let response = service
+            .clone()
+            .oneshot(request("example data", Some("XHHbsodjDWXKk3ZMNNmqDQ=="))?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await?.to_bytes();
+        assert_eq!(body, "example data");
+
+        let response = service.clone().oneshot(request("example data", None)?).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = service
+            .clone()
+            .oneshot(request("changed data", Some("XHHbsodjDWXKk3ZMNNmqDQ=="))?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = service.oneshot(request("example data", Some("invalid"))?).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // requests are not validated by default
+        let service = ContentMd5Layer::new().layer(service_fn(echo));
+        let response = service
+            .oneshot(request("changed data", Some("XHHbsodjDWXKk3ZMNNmqDQ=="))?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "tower"), ignore)]
+#[tokio::test]
+async fn limits() -> Result<(), Error> {
+    #[cfg(feature = "tower")]
+    {
+        let service = ContentMd5Layer::new()
+            .limit(8)
+            .validate_requests(true)
+            .layer(service_fn(echo));
+
+        // responses exceeding the limit are passed without the header
+        let response = service.clone().oneshot(request("example data", None)?).await?;
+        assert!(!response.headers().contains_key("content-md5"));
+        let body = response.into_body().collect().await?.to_bytes();
+        assert_eq!(body, "example data");
+
+        let response = service.clone().oneshot(request("example", None)?).await?;
+        assert_eq!(response.headers()["content-md5"], "Gnmk1g3mcY6OWzJuM4rlMw==");
+
+        let response = service
+            .oneshot(request("example data", Some("XHHbsodjDWXKk3ZMNNmqDQ=="))?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // bodies of unknown size are streamed once they exceed the limit
+        let service = ContentMd5Layer::new()
+            .limit(8)
+            .layer(service_fn(|_: Request<Full<Bytes>>| {
+                async { Ok::<_, Infallible>(Response::new(Chunks(vec!["exam", "ple ", "da", "ta"]))) }
+            }));
+        let response = service.oneshot(request("", None)?).await?;
+        assert!(!response.headers().contains_key("content-md5"));
+        let body = response.into_body().collect().await?.to_bytes();
+        assert_eq!(body, "example data");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+#[cfg(feature = "reqwest")]
+use chksum_md5::async_chksum_url;
+use chksum_md5::Error as ChksumError;
+#[cfg(feature = "reqwest")]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+#[cfg(feature = "reqwest")]
+use tokio::net::TcpListener;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(feature = "reqwest")]
+async fn serve(response: &'static [u8]) -> Result<String, Error> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    tokio::spawn(async move {
+        if let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).await;
+            let _ = stream.write_all(response).await;
+            let _ = stream.shutdown().await;
+        }
+    });
+    Ok(format!("http://{address}/file.txt"))
+}
+
+#[cfg_attr(not(feature = "reqwest"), ignore)]
+#[tokio::test]
+async fn url_content() -> Result<(), Error> {
+    #[cfg(feature = "reqwest")]
+    {
+        let url = serve(b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\nConnection: close\r\n\r\ndata").await?;
+        let (length, digest) = async_chksum_url(url).await?;
+        assert_eq!(length, 4);
+        assert_eq!(digest.to_hex_lowercase(), "8d777f385d3dfec8815d20f7496026dc");
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "reqwest"), ignore)]
+#[tokio::test]
+async fn url_error_status() -> Result<(), Error> {
+    #[cfg(feature = "reqwest")]
+    {
+        let url = serve(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").await?;
+        let result = async_chksum_url(url).await;
+        assert!(matches!(result, Err(ChksumError::Io(_))));
+    }
+
+    Ok(())
+}
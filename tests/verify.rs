@@ -0,0 +1,36 @@
+use chksum_md5::{hash, verify, Digest, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+}
+
+#[test]
+fn expected_digests() -> Result<(), Error> {
+    let digest = hash(b"data");
+    assert!(verify(b"data", digest)?);
+    assert!(verify(b"data", digest.into_inner())?);
+    assert!(verify(b"data", "8d777f385d3dfec8815d20f7496026dc")?);
+    assert!(verify(b"data", "8D777F385D3DFEC8815D20F7496026DC")?);
+    assert!(verify(b"data", String::from("8d777f385d3dfec8815d20f7496026dc"))?);
+
+    assert!(!verify(b"data", hash(b"atad"))?);
+    assert!(!verify(b"data", [0; 16])?);
+    assert!(!verify(b"", "8d777f385d3dfec8815d20f7496026dc")?);
+    assert!(verify(
+        b"",
+        Digest::new([0xD4, 0x1D, 0x8C, 0xD9, 0x8F, 0x00, 0xB2, 0x04, 0xE9, 0x80, 0x09, 0x98, 0xEC, 0xF8, 0x42, 0x7E,])
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn invalid_hex() {
+    assert!(matches!(verify(b"data", "8d777f"), Err(ChksumError::InvalidDigest(_))));
+    assert!(matches!(
+        verify(b"data", "zz777f385d3dfec8815d20f7496026dc"),
+        Err(ChksumError::InvalidDigest(_))
+    ));
+}
@@ -0,0 +1,53 @@
+use std::io::Error as IoError;
+#[cfg(feature = "watch")]
+use std::sync::mpsc;
+#[cfg(feature = "watch")]
+use std::time::Duration;
+
+use assert_fs::fixture::FixtureError;
+#[cfg(feature = "watch")]
+use assert_fs::prelude::{FileWriteBin, PathChild};
+#[cfg(feature = "watch")]
+use assert_fs::TempDir;
+#[cfg(feature = "watch")]
+use chksum_md5::watch::{watch, WatchOptions};
+use chksum_md5::Error as ChksumError;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+#[cfg_attr(not(feature = "watch"), ignore)]
+#[test]
+fn changed_file() -> Result<(), Error> {
+    #[cfg(feature = "watch")]
+    {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.canonicalize()?;
+
+        let (sender, receiver) = mpsc::channel();
+        let options = WatchOptions::new().debounce(Duration::from_millis(100));
+        let watcher = watch(&root, &options, move |path, digest| {
+            let _ = sender.send((path, digest.map(|digest| digest.to_hex_lowercase())));
+        })?;
+
+        let file = temp_dir.child("file.txt");
+        file.write_binary(b"da")?;
+        file.write_binary(b"data")?;
+
+        let (path, digest) = receiver
+            .recv_timeout(Duration::from_secs(10))
+            .expect("change is reported");
+        assert_eq!(path, root.join("file.txt"));
+        assert_eq!(digest?, "8d777f385d3dfec8815d20f7496026dc");
+        drop(watcher);
+    }
+
+    Ok(())
+}
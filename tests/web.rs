@@ -0,0 +1,47 @@
+#![cfg(all(feature = "web", target_arch = "wasm32"))]
+
+use chksum_md5::web::{async_chksum_blob, async_chksum_stream};
+use chksum_md5::{hash, Error};
+use futures_util::stream;
+use js_sys::wasm_bindgen::JsValue;
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_sys::Blob;
+
+#[wasm_bindgen_test]
+async fn blob() -> Result<(), Error> {
+    let parts = Array::of2(&Uint8Array::from(&b"Hello, "[..]), &Uint8Array::from(&b"world!"[..]));
+    let blob = Blob::new_with_u8_array_sequence(&parts).expect("blob is created");
+    let (length, digest) = async_chksum_blob(&blob).await?;
+    assert_eq!(length, 13);
+    assert_eq!(digest, hash(b"Hello, world!"));
+
+    let blob = Blob::new().expect("blob is created");
+    let (length, digest) = async_chksum_blob(&blob).await?;
+    assert_eq!(length, 0);
+    assert_eq!(digest, hash(b""));
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn stream() -> Result<(), Error> {
+    let chunks = [&b"Hello, "[..], &b""[..], &b"world!"[..]].map(|chunk| Ok(Uint8Array::from(chunk).into()));
+    let readable = wasm_streams::ReadableStream::from_stream(stream::iter(chunks));
+    let (length, digest) = async_chksum_stream(readable.into_raw()).await?;
+    assert_eq!(length, 13);
+    assert_eq!(digest, hash(b"Hello, world!"));
+
+    let chunks = [Ok(JsValue::from_str("Hello, world!"))];
+    let readable = wasm_streams::ReadableStream::from_stream(stream::iter(chunks));
+    assert!(async_chksum_stream(readable.into_raw()).await.is_err());
+
+    let chunks = [Err(JsValue::from(js_sys::Error::new("aborted")))];
+    let readable = wasm_streams::ReadableStream::from_stream(stream::iter(chunks));
+    let error = async_chksum_stream(readable.into_raw())
+        .await
+        .expect_err("stream fails");
+    assert!(error.to_string().contains("aborted"));
+
+    Ok(())
+}